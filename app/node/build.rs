@@ -0,0 +1,17 @@
+//! 构建脚本：记录编译器版本，供 web3_clientVersion 等版本字符串使用
+
+use std::process::Command;
+
+fn main() {
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    // `rustc --version` 输出形如 "rustc 1.80.0 (051478957 2024-07-21)"
+    let version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .and_then(|text| text.split_whitespace().nth(1).map(str::to_string))
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=RUSTETH_RUSTC_VERSION={}", version);
+    println!("cargo:rerun-if-env-changed=RUSTC");
+}
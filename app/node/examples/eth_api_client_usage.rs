@@ -189,11 +189,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut success_count = 0;
     for handle in handles {
         if let Ok((id, result)) = handle.await {
-            if result.is_ok() {
-                success_count += 1;
-                println!("   ✅ 请求 {} 完成", id);
-            } else {
-                println!("   ❌ 请求 {} 失败: {:?}", id, result.unwrap_err());
+            match result {
+                Ok(_) => {
+                    success_count += 1;
+                    println!("   ✅ 请求 {} 完成", id);
+                }
+                Err(e) => println!("   ❌ 请求 {} 失败: {:?}", id, e),
             }
         }
     }
@@ -14,7 +14,7 @@ use alloy_primitives::{address, Address, Bytes, U256};
 use revm::{
     db::{CacheDB, EmptyDB},
     primitives::{AccountInfo, Bytecode, ExecutionResult, Output, TransactTo},
-    Database, Evm,
+    Evm,
 };
 
 /// 示例 1: 简单的以太坊价值转账
@@ -69,12 +69,10 @@ fn example_value_transfer() {
     // 6. 释放 EVM 后查询账户余额变化
     drop(evm);
 
-    if let Ok(sender_after) = cache_db.load_account(sender) {
-        println!("   发送方余额: {} Wei", sender_after.info.balance);
-    }
-    if let Ok(receiver_after) = cache_db.load_account(receiver) {
-        println!("   接收方余额: {} Wei", receiver_after.info.balance);
-    }
+    let Ok(sender_after) = cache_db.load_account(sender);
+    println!("   发送方余额: {} Wei", sender_after.info.balance);
+    let Ok(receiver_after) = cache_db.load_account(receiver);
+    println!("   接收方余额: {} Wei", receiver_after.info.balance);
 }
 
 /// 示例 2: 部署智能合约
@@ -222,19 +220,21 @@ fn example_contract_call() {
             println!("✅ 合约调用成功!");
             println!("   Gas 使用量: {:?}", execution_result.gas_used());
 
-            if let ExecutionResult::Success { ref output, .. } = execution_result {
-                if let Output::Call(ref return_data) = output {
-                    println!("   返回数据长度: {} bytes", return_data.len());
-                    if !return_data.is_empty() {
-                        if return_data.len() == 32 {
-                            let value = U256::from_be_slice(&return_data);
-                            println!("   返回值: {}", value);
-                        }
-                        println!(
-                            "   返回数据(hex): {}",
-                            alloy_primitives::hex::encode(&return_data)
-                        );
+            if let ExecutionResult::Success {
+                output: Output::Call(ref return_data),
+                ..
+            } = execution_result
+            {
+                println!("   返回数据长度: {} bytes", return_data.len());
+                if !return_data.is_empty() {
+                    if return_data.len() == 32 {
+                        let value = U256::from_be_slice(return_data);
+                        println!("   返回值: {}", value);
                     }
+                    println!(
+                        "   返回数据(hex): {}",
+                        alloy_primitives::hex::encode(return_data)
+                    );
                 }
             }
         }
@@ -303,12 +303,12 @@ fn example_eth_call_simulation() {
 
                 if let Output::Call(ref return_data) = output {
                     if return_data.len() == 32 {
-                        let value = U256::from_be_slice(&return_data);
+                        let value = U256::from_be_slice(return_data);
                         println!("   返回值: {}", value);
                     } else {
                         println!(
                             "   返回数据: {}",
-                            alloy_primitives::hex::encode(&return_data)
+                            alloy_primitives::hex::encode(return_data)
                         );
                     }
                 }
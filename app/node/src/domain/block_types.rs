@@ -1,15 +1,15 @@
-/// 区块相关类型定义 - 领域层实体
-///
-/// 参考标准：
-/// - EIP-1559: 费用市场
-/// - EIP-3675: PoS共识
-/// - EIP-4399: PREVRANDAO
-/// - EIP-4844: Blob交易
-/// - EIP-4895: 验证者提款
-/// - Geth: core/types/block.go
+//! 区块相关类型定义 - 领域层实体
+//!
+//! 参考标准：
+//! - EIP-1559: 费用市场
+//! - EIP-3675: PoS共识
+//! - EIP-4399: PREVRANDAO
+//! - EIP-4844: Blob交易
+//! - EIP-4895: 验证者提款
+//! - Geth: core/types/block.go
 
 use crate::domain::tx_types::DynamicFeeTx;
-use ethereum_types::{Address, Bloom, H256, U256, U64};
+use ethereum_types::{Address, Bloom, H256, H64, U256, U64};
 use std::fmt;
 
 /// 区块头信息
@@ -64,11 +64,51 @@ pub struct BlockHeader {
 impl BlockHeader {
     /// 计算区块头哈希 (Keccak256)
     ///
-    /// TODO: 实现完整的RLP编码和哈希计算
     /// hash = keccak256(rlp([parent_hash, ommers_hash, ..., parent_beacon_block_root]))
+    ///
+    /// 分叉引入的可选字段按顺序追加，遇到第一个 None 即停止
+    /// (与 geth Header 的 `rlp:"optional"` 编码一致)
     pub fn hash(&self) -> H256 {
-        // 暂时返回零值，后续实现RLP编码
-        H256::zero()
+        use rlp::RlpStream;
+        use sha3::{Digest, Keccak256};
+
+        let mut stream = RlpStream::new();
+        stream.begin_unbounded_list();
+        stream.append(&self.parent_hash);
+        stream.append(&self.ommers_hash);
+        stream.append(&self.fee_recipient);
+        stream.append(&self.state_root);
+        stream.append(&self.transactions_root);
+        stream.append(&self.receipts_root);
+        stream.append(&self.logs_bloom);
+        stream.append(&self.difficulty);
+        stream.append(&self.number);
+        stream.append(&self.gas_limit);
+        stream.append(&self.gas_used);
+        stream.append(&self.timestamp);
+        stream.append(&self.extra_data);
+        stream.append(&self.mix_hash);
+        stream.append(&H64::from_low_u64_be(self.nonce));
+
+        // 可选字段 (London / Shanghai / Cancun)
+        if let Some(base_fee) = self.base_fee_per_gas {
+            stream.append(&base_fee);
+            if let Some(withdrawals_root) = self.withdrawals_root {
+                stream.append(&withdrawals_root);
+                if let (Some(blob_gas_used), Some(excess_blob_gas)) =
+                    (self.blob_gas_used, self.excess_blob_gas)
+                {
+                    stream.append(&blob_gas_used);
+                    stream.append(&excess_blob_gas);
+                    if let Some(beacon_root) = self.parent_beacon_block_root {
+                        stream.append(&beacon_root);
+                    }
+                }
+            }
+        }
+        stream.finalize_unbounded_list();
+
+        H256::from_slice(&Keccak256::digest(stream.out()))
     }
 
     /// 获取空ommers列表的哈希值 (PoS固定值)
@@ -159,6 +199,21 @@ impl Block {
     }
 }
 
+/// 已上链交易及其在链上的位置
+///
+/// 参考: geth/core/rawdb ReadTransaction
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexedTransaction {
+    /// 交易本身
+    pub transaction: DynamicFeeTx,
+    /// 所在区块哈希
+    pub block_hash: H256,
+    /// 所在区块号
+    pub block_number: U64,
+    /// 区块内的交易索引
+    pub transaction_index: U64,
+}
+
 /// 提款信息 (EIP-4895)
 ///
 /// 参考: https://eips.ethereum.org/EIPS/eip-4895
@@ -201,6 +256,179 @@ pub struct BuildEnvironment {
     pub parent_beacon_block_root: Option<H256>,
 }
 
+impl BuildEnvironment {
+    /// 创建构建环境的构建器
+    pub fn builder() -> BuildEnvironmentBuilder {
+        BuildEnvironmentBuilder::default()
+    }
+}
+
+/// 构建环境不满足不变量
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildEnvironmentError {
+    /// 父区块 gas 使用量超过其 gas 限制
+    ParentGasUsedExceedsLimit { used: U64, limit: U64 },
+    /// 父区块不是创世区块，但父区块哈希为零
+    ZeroParentHash { parent_number: U64 },
+    /// 时间戳未设置或为零
+    ZeroTimestamp,
+}
+
+impl fmt::Display for BuildEnvironmentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ParentGasUsedExceedsLimit { used, limit } => {
+                write!(f, "Parent gas used {} exceeds parent gas limit {}", used, limit)
+            }
+            Self::ZeroParentHash { parent_number } => {
+                write!(f, "Parent hash is zero for non-genesis parent #{}", parent_number)
+            }
+            Self::ZeroTimestamp => write!(f, "Timestamp must be greater than zero"),
+        }
+    }
+}
+
+impl std::error::Error for BuildEnvironmentError {}
+
+/// `BuildEnvironment` 构建器
+///
+/// 未设置的字段取默认值（gas limit 30M、base fee 1 gwei、其余为零或空），
+/// 时间戳必须显式设置；`build()` 校验字段间的不变量
+#[derive(Debug, Clone)]
+pub struct BuildEnvironmentBuilder {
+    env: BuildEnvironment,
+}
+
+impl Default for BuildEnvironmentBuilder {
+    fn default() -> Self {
+        Self {
+            env: BuildEnvironment {
+                parent_hash: H256::zero(),
+                parent_number: U64::zero(),
+                parent_gas_used: U64::zero(),
+                parent_gas_limit: U64::from(30_000_000),
+                // EIP-1559 初始 base fee
+                parent_base_fee: U256::from(1_000_000_000u64),
+                timestamp: U64::zero(),
+                fee_recipient: Address::zero(),
+                prev_randao: H256::zero(),
+                withdrawals: Vec::new(),
+                parent_beacon_block_root: None,
+            },
+        }
+    }
+}
+
+impl BuildEnvironmentBuilder {
+    /// 从父区块取哈希、区块号、gas 使用量、gas 限制和 base fee
+    pub fn parent(mut self, parent: &Block) -> Self {
+        self.env.parent_hash = parent.hash();
+        self.env.parent_number = parent.number();
+        self.env.parent_gas_used = parent.gas_used();
+        self.env.parent_gas_limit = parent.gas_limit();
+        if let Some(base_fee) = parent.base_fee() {
+            self.env.parent_base_fee = base_fee;
+        }
+        self
+    }
+
+    pub fn parent_hash(mut self, parent_hash: H256) -> Self {
+        self.env.parent_hash = parent_hash;
+        self
+    }
+
+    pub fn parent_number(mut self, parent_number: U64) -> Self {
+        self.env.parent_number = parent_number;
+        self
+    }
+
+    pub fn parent_gas_used(mut self, parent_gas_used: U64) -> Self {
+        self.env.parent_gas_used = parent_gas_used;
+        self
+    }
+
+    pub fn parent_gas_limit(mut self, parent_gas_limit: U64) -> Self {
+        self.env.parent_gas_limit = parent_gas_limit;
+        self
+    }
+
+    pub fn parent_base_fee(mut self, parent_base_fee: U256) -> Self {
+        self.env.parent_base_fee = parent_base_fee;
+        self
+    }
+
+    pub fn timestamp(mut self, timestamp: U64) -> Self {
+        self.env.timestamp = timestamp;
+        self
+    }
+
+    pub fn fee_recipient(mut self, fee_recipient: Address) -> Self {
+        self.env.fee_recipient = fee_recipient;
+        self
+    }
+
+    pub fn prev_randao(mut self, prev_randao: H256) -> Self {
+        self.env.prev_randao = prev_randao;
+        self
+    }
+
+    pub fn withdrawals(mut self, withdrawals: Vec<Withdrawal>) -> Self {
+        self.env.withdrawals = withdrawals;
+        self
+    }
+
+    pub fn parent_beacon_block_root(mut self, root: H256) -> Self {
+        self.env.parent_beacon_block_root = Some(root);
+        self
+    }
+
+    /// 校验不变量并返回构建环境
+    ///
+    /// - 父区块 gas_used ≤ gas_limit
+    /// - 父区块不是创世区块时父区块哈希非零
+    /// - 时间戳大于零
+    pub fn build(self) -> Result<BuildEnvironment, BuildEnvironmentError> {
+        let env = self.env;
+        if env.parent_gas_used > env.parent_gas_limit {
+            return Err(BuildEnvironmentError::ParentGasUsedExceedsLimit {
+                used: env.parent_gas_used,
+                limit: env.parent_gas_limit,
+            });
+        }
+        if !env.parent_number.is_zero() && env.parent_hash.is_zero() {
+            return Err(BuildEnvironmentError::ZeroParentHash {
+                parent_number: env.parent_number,
+            });
+        }
+        if env.timestamp.is_zero() {
+            return Err(BuildEnvironmentError::ZeroTimestamp);
+        }
+        Ok(env)
+    }
+}
+
+/// 区块构建摘要（出块收益核算）
+///
+/// 用于与 relay 报价比较：提议者收益为优先费总和，base fee 部分被销毁 (EIP-1559)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockBuildSummary {
+    /// 提议者获得的优先费总和 = Σ effective_priority_fee * gas_used
+    pub total_priority_fees: U256,
+    /// 区块总 gas 使用量
+    pub total_gas_used: u64,
+    /// 打包的交易数
+    pub tx_count: usize,
+    /// 销毁的 base fee = gas_used * base_fee
+    pub base_fee_burned: U256,
+}
+
+/// 构建结果：区块及其收益摘要
+#[derive(Debug, Clone)]
+pub struct BuiltBlock {
+    pub block: Block,
+    pub summary: BlockBuildSummary,
+}
+
 /// 区块验证错误
 #[derive(Debug, Clone, PartialEq)]
 pub enum BlockValidationError {
@@ -222,6 +450,10 @@ pub enum BlockValidationError {
     InvalidStateRoot { expected: H256, actual: H256 },
     /// Gas limit调整超出范围
     GasLimitAdjustmentTooLarge { parent: u64, current: u64 },
+    /// 区块号不高于当前链头（回退链头需走 set_head 重组路径）
+    NotAboveHead { number: U64, head: U64 },
+    /// 出块收益地址为零地址（配置为拒绝时）
+    ZeroFeeRecipient,
     /// 其他错误
     Other(String),
 }
@@ -258,6 +490,10 @@ impl fmt::Display for BlockValidationError {
                     parent, current
                 )
             }
+            Self::NotAboveHead { number, head } => {
+                write!(f, "Block #{} does not advance head #{}", number, head)
+            }
+            Self::ZeroFeeRecipient => write!(f, "Fee recipient is the zero address"),
             Self::Other(msg) => write!(f, "Block validation error: {}", msg),
         }
     }
@@ -363,4 +599,131 @@ mod tests {
         assert_eq!(block.gas_limit(), U64::from(30_000_000));
         assert_eq!(block.base_fee(), Some(U256::from(1_000_000_000u64)));
     }
+
+    fn h256(s: &str) -> H256 {
+        H256::from_slice(&hex::decode(s).unwrap())
+    }
+
+    #[test]
+    fn test_header_hash_mainnet_genesis() {
+        // 以太坊主网创世区块头
+        let genesis = BlockHeader {
+            parent_hash: H256::zero(),
+            ommers_hash: BlockHeader::empty_ommers_hash(),
+            fee_recipient: Address::zero(),
+            state_root: h256("d7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544"),
+            transactions_root: h256(
+                "56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            ),
+            receipts_root: h256("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"),
+            logs_bloom: Bloom::zero(),
+            difficulty: U256::from(0x400000000u64),
+            number: U64::zero(),
+            gas_limit: U64::from(5000),
+            gas_used: U64::zero(),
+            timestamp: U64::zero(),
+            extra_data: hex::decode(
+                "11bbe8db4e347b4e8c937c1c8370e4b5ed33adb3db69cbdb7a38e1e50b1b82fa",
+            )
+            .unwrap(),
+            mix_hash: H256::zero(),
+            nonce: 0x42,
+            base_fee_per_gas: None,
+            withdrawals_root: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            parent_beacon_block_root: None,
+        };
+
+        assert_eq!(
+            genesis.hash(),
+            h256("d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3")
+        );
+    }
+
+    #[test]
+    fn test_header_hash_depends_on_fields() {
+        let header = BlockHeader {
+            parent_hash: H256::zero(),
+            ommers_hash: BlockHeader::empty_ommers_hash(),
+            fee_recipient: Address::zero(),
+            state_root: H256::zero(),
+            transactions_root: H256::zero(),
+            receipts_root: H256::zero(),
+            logs_bloom: Bloom::zero(),
+            difficulty: U256::zero(),
+            number: U64::one(),
+            gas_limit: U64::from(30_000_000),
+            gas_used: U64::zero(),
+            timestamp: U64::from(1234567890),
+            extra_data: vec![],
+            mix_hash: H256::zero(),
+            nonce: 0,
+            base_fee_per_gas: Some(U256::from(1_000_000_000u64)),
+            withdrawals_root: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            parent_beacon_block_root: None,
+        };
+
+        assert_ne!(header.hash(), H256::zero());
+        assert_eq!(header.hash(), header.clone().hash());
+
+        let next = BlockHeader {
+            number: U64::from(2),
+            ..header.clone()
+        };
+        assert_ne!(header.hash(), next.hash());
+
+        let shanghai = BlockHeader {
+            withdrawals_root: Some(H256::zero()),
+            ..header.clone()
+        };
+        assert_ne!(header.hash(), shanghai.hash());
+    }
+
+    #[test]
+    fn test_build_environment_builder() {
+        let env = BuildEnvironment::builder()
+            .parent_hash(H256::repeat_byte(0x01))
+            .parent_number(U64::from(10))
+            .parent_gas_used(U64::from(15_000_000))
+            .timestamp(U64::from(1_700_000_000))
+            .prev_randao(H256::repeat_byte(0x42))
+            .build()
+            .unwrap();
+        assert_eq!(env.parent_gas_limit, U64::from(30_000_000));
+        assert_eq!(env.parent_base_fee, U256::from(1_000_000_000u64));
+        assert_eq!(env.prev_randao, H256::repeat_byte(0x42));
+        assert!(env.withdrawals.is_empty());
+
+        // 创世区块之上允许零父哈希
+        assert!(BuildEnvironment::builder().timestamp(U64::one()).build().is_ok());
+    }
+
+    #[test]
+    fn test_build_environment_builder_rejects_invalid() {
+        let valid = || {
+            BuildEnvironment::builder()
+                .parent_hash(H256::repeat_byte(0x01))
+                .parent_number(U64::one())
+                .timestamp(U64::one())
+        };
+
+        assert_eq!(
+            valid().parent_gas_used(U64::from(30_000_001)).build().unwrap_err(),
+            BuildEnvironmentError::ParentGasUsedExceedsLimit {
+                used: U64::from(30_000_001),
+                limit: U64::from(30_000_000),
+            }
+        );
+        assert_eq!(
+            valid().parent_hash(H256::zero()).build().unwrap_err(),
+            BuildEnvironmentError::ZeroParentHash { parent_number: U64::one() }
+        );
+        assert_eq!(
+            valid().timestamp(U64::zero()).build().unwrap_err(),
+            BuildEnvironmentError::ZeroTimestamp
+        );
+    }
 }
@@ -10,8 +10,13 @@
 //! - 缓存行对齐优化性能
 //! - 遵循 CQRS 模式
 
+use crate::domain::tx_types::{
+    effective_gas_price, legacy_chain_id, DynamicFeeTx, TransactionValidationError,
+};
+use crate::domain::typed_data::TypedData;
 use ethereum_types::{Address, Bloom, H256, H64, U256, U64};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 
 // ============================================================================
@@ -37,6 +42,14 @@ pub enum CommandError {
     DatabaseError(String),
     /// 超时
     Timeout(String),
+    /// 执行回滚（message 为完整错误信息，data 为回滚返回数据）
+    ExecutionReverted { message: String, data: Vec<u8> },
+    /// 执行失败
+    ExecutionFailed(String),
+    /// 超出请求限额
+    LimitExceeded(String),
+    /// 输入无效（EIP-1898：requireCanonical 的区块不在主链上）
+    InvalidInput(String),
 }
 
 impl fmt::Display for CommandError {
@@ -50,6 +63,10 @@ impl fmt::Display for CommandError {
             Self::NetworkError(msg) => write!(f, "网络错误: {}", msg),
             Self::DatabaseError(msg) => write!(f, "数据库错误: {}", msg),
             Self::Timeout(msg) => write!(f, "超时: {}", msg),
+            Self::ExecutionReverted { message, .. } => write!(f, "{}", message),
+            Self::ExecutionFailed(msg) => write!(f, "执行失败: {}", msg),
+            Self::LimitExceeded(msg) => write!(f, "超出限额: {}", msg),
+            Self::InvalidInput(msg) => write!(f, "无效输入: {}", msg),
         }
     }
 }
@@ -63,7 +80,23 @@ impl From<crate::service::ethereum_service_trait::ServiceError> for CommandError
         match err {
             ServiceError::BlockNotFound => Self::NotFound("区块未找到".to_string()),
             ServiceError::TransactionNotFound => Self::NotFound("交易未找到".to_string()),
+            ServiceError::UnknownAccount(address) => {
+                Self::NotFound(format!("未知账户: {:?}", address))
+            }
+            ServiceError::InvalidParams(msg) => Self::InvalidParams(msg),
             ServiceError::ValidationError(msg) => Self::ValidationError(msg),
+            ServiceError::ExecutionReverted { reason, data } => Self::ExecutionReverted {
+                message: match reason {
+                    Some(reason) => format!("execution reverted: {}", reason),
+                    None => "execution reverted".to_string(),
+                },
+                data,
+            },
+            ServiceError::ExecutionFailed(msg) => Self::ExecutionFailed(msg),
+            ServiceError::RateLimited(msg) => Self::LimitExceeded(msg),
+            ServiceError::CallLimitExceeded(msg) => Self::LimitExceeded(msg),
+            err @ ServiceError::TooManyResults(_) => Self::LimitExceeded(err.to_string()),
+            err @ ServiceError::NonCanonicalBlock(_) => Self::InvalidInput(err.to_string()),
             ServiceError::InternalError(msg) => Self::InternalError(msg),
             ServiceError::Other(msg) => Self::InternalError(msg),
         }
@@ -74,21 +107,32 @@ impl From<crate::service::ethereum_service_trait::ServiceError> for CommandError
 // 核心以太坊类型
 // ============================================================================
 
-/// 区块标识符 - 可以是区块号、"latest"、"earliest"、"pending"
+/// 区块标识符 - 可以是区块号、"latest"、"earliest"、"pending"、"safe"、"finalized"，
+/// 或 EIP-1898 的 `{"blockHash": ..., "requireCanonical": ...}`
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum BlockId {
-    Number(U64),
+    Number(#[serde(with = "quantity")] U64),
     Tag(BlockTag),
+    /// 按哈希固定区块，避免读取与调用之间发生重组导致基础状态变化
+    #[serde(rename_all = "camelCase")]
+    Hash {
+        block_hash: H256,
+        /// 为 true 时，哈希对应的区块不在主链上则报错
+        #[serde(default)]
+        require_canonical: bool,
+    },
 }
 
 /// 区块标签枚举
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum BlockTag {
-    Latest,   // 最新区块
-    Earliest, // 创世区块
-    Pending,  // 待处理区块
+    Latest,    // 最新区块
+    Earliest,  // 创世区块
+    Pending,   // 待处理区块
+    Safe,      // 共识层标记的安全区块
+    Finalized, // 共识层标记的最终确定区块
 }
 
 /// 以太坊区块结构（符合 EIP-1474，缓存行对齐优化性能）
@@ -114,8 +158,69 @@ pub struct Block {
     pub gas_limit: U256,         // Gas 限制
     pub gas_used: U256,          // 已使用 Gas
     pub timestamp: U256,         // 时间戳
-    pub transactions: Vec<Transaction>, // 交易列表
+    pub transactions: BlockTransactions, // 交易列表（完整交易或仅哈希）
     pub uncles: Vec<H256>,       // 叔块哈希列表
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_fee_per_gas: Option<U256>, // EIP-1559: 基础费用（London 之前为 None）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excess_blob_gas: Option<U64>, // EIP-4844: 超额 blob gas（Cancun 之前为 None）
+}
+
+impl Block {
+    /// 按 `full_tx` 返回区块：false 时交易列表只保留哈希
+    pub fn with_full_transactions(mut self, full_tx: bool) -> Self {
+        if !full_tx {
+            self.transactions = BlockTransactions::Hashes(self.transactions.hashes());
+        }
+        self
+    }
+}
+
+/// 区块交易列表
+///
+/// eth_getBlockBy* 的 `full_tx` 为 false 时返回交易哈希数组，否则返回完整交易对象数组。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BlockTransactions {
+    /// 仅交易哈希
+    Hashes(Vec<H256>),
+    /// 完整交易
+    Full(Vec<Transaction>),
+}
+
+impl BlockTransactions {
+    /// 交易数量
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Hashes(hashes) => hashes.len(),
+            Self::Full(txs) => txs.len(),
+        }
+    }
+
+    /// 是否没有交易
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 交易哈希列表
+    pub fn hashes(&self) -> Vec<H256> {
+        match self {
+            Self::Hashes(hashes) => hashes.clone(),
+            Self::Full(txs) => txs.iter().map(|tx| tx.hash).collect(),
+        }
+    }
+}
+
+impl Default for BlockTransactions {
+    fn default() -> Self {
+        Self::Full(Vec::new())
+    }
+}
+
+impl From<Vec<Transaction>> for BlockTransactions {
+    fn from(txs: Vec<Transaction>) -> Self {
+        Self::Full(txs)
+    }
 }
 
 /// 以太坊交易结构（符合 EIP-1474 和 EIP-1559，缓存行对齐）
@@ -148,6 +253,33 @@ pub struct Transaction {
     pub transaction_type: Option<U64>, // 交易类型（0=Legacy, 2=EIP-1559）
 }
 
+impl Transaction {
+    /// 从 Legacy 交易的 v 值还原链 ID（EIP-155）
+    ///
+    /// Legacy 交易的规则见 [`legacy_chain_id`]；类型化交易的 v 只是 y-parity，
+    /// 不含链 ID → Ok(None)
+    pub fn chain_id(&self) -> Result<Option<U64>, TransactionValidationError> {
+        let is_typed = self.transaction_type.is_some_and(|tx_type| !tx_type.is_zero());
+        if is_typed {
+            return Ok(None);
+        }
+        legacy_chain_id(self.v.as_u64())
+    }
+
+    /// 计算实际支付的单位 gas 价格
+    ///
+    /// - EIP-1559 交易：min(max_fee_per_gas, base_fee + max_priority_fee_per_gas)
+    /// - Legacy 交易：gas_price
+    pub fn effective_gas_price(&self, base_fee: Option<U256>) -> U256 {
+        match (self.max_fee_per_gas, self.max_priority_fee_per_gas) {
+            (Some(max_fee), Some(max_priority_fee)) => {
+                effective_gas_price(max_fee, max_priority_fee, base_fee.unwrap_or_default())
+            }
+            _ => self.gas_price.unwrap_or_default(),
+        }
+    }
+}
+
 /// 交易收据结构（符合 EIP-1474）
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -160,10 +292,24 @@ pub struct TransactionReceipt {
     pub to: Option<Address>,               // 接收方地址
     pub cumulative_gas_used: U256,         // 累计使用的 Gas
     pub gas_used: U256,                    // 本交易使用的 Gas
+    #[serde(default)]
+    pub effective_gas_price: U256, // 实际支付的单位 Gas 价格
     pub contract_address: Option<Address>, // 合约地址（如果是合约创建）
     pub logs: Vec<Log>,                    // 日志列表
     pub logs_bloom: Bloom,                 // 日志布隆过滤器
     pub status: U64,                       // 交易状态（1=成功，0=失败）
+    #[serde(default, rename = "type")]
+    pub transaction_type: U64, // 交易类型（0=Legacy, 2=EIP-1559）
+}
+
+impl TransactionReceipt {
+    /// 按所属交易与区块 base fee 填充 `type` 与 `effectiveGasPrice`
+    ///
+    /// Legacy 交易（未标注类型）的 type 为 0，实际价格即 gas_price
+    pub fn set_fee_fields(&mut self, tx: &Transaction, base_fee: Option<U256>) {
+        self.transaction_type = tx.transaction_type.unwrap_or_default();
+        self.effective_gas_price = tx.effective_gas_price(base_fee);
+    }
 }
 
 /// 事件日志结构（符合 EIP-1474）
@@ -200,6 +346,40 @@ pub struct CallRequest {
     pub max_priority_fee_per_gas: Option<U256>, // EIP-1559: 每 gas 最大优先费用（可选）
 }
 
+/// eth_call 单个账户的状态覆盖（geth stateOverride 格式）
+///
+/// 覆盖仅作用于本次调用，不会持久化。`state` 替换账户的全部存储，
+/// `stateDiff` 只修改指定的存储槽，两者不能同时出现。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>, // 覆盖余额
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<U64>, // 覆盖 nonce
+    #[serde(default, with = "hex_data", skip_serializing_if = "Option::is_none")]
+    pub code: Option<Vec<u8>>, // 覆盖合约代码
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<HashMap<H256, H256>>, // 替换全部存储
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_diff: Option<HashMap<H256, H256>>, // 修改指定存储槽
+}
+
+/// eth_call 状态覆盖集合（地址 -> 覆盖项）
+pub type StateOverride = HashMap<Address, AccountOverride>;
+
+/// eth_callBundle 中单个调用的执行结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallBundleResult {
+    #[serde(with = "hex_bytes")]
+    pub return_data: Vec<u8>, // 返回数据（回滚时为回滚数据）
+    pub gas_used: U64,        // 实际消耗的 gas
+    pub status: U64,          // 1 成功，0 回滚或异常
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>, // 失败原因（回滚原因或异常类型）
+}
+
 /// 日志过滤器参数（符合 EIP-1474）
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -306,6 +486,46 @@ mod hex_bytes {
     }
 }
 
+/// 严格的数量（QUANTITY）编码（EIP-1474）
+///
+/// 必须是 `0x` 前缀的最简十六进制：`0x0`、`0x1`、`0x400` 合法，
+/// `0x`、`0x01`、`0x0400` 与不带前缀的输入均拒绝；输出同样为最简形式
+pub mod quantity {
+    use ethereum_types::U64;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(value: &U64, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&format!("{:#x}", value.as_u64()))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<U64, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        parse(&s).map_err(serde::de::Error::custom)
+    }
+
+    /// 解析最简十六进制数量
+    pub fn parse(s: &str) -> Result<U64, String> {
+        let digits = s
+            .strip_prefix("0x")
+            .ok_or_else(|| format!("数量缺少 0x 前缀: {}", s))?;
+        if digits.is_empty() {
+            return Err("数量不能为空: 0x".to_string());
+        }
+        if digits.len() > 1 && digits.starts_with('0') {
+            return Err(format!("数量含前导零: {}", s));
+        }
+        u64::from_str_radix(digits, 16)
+            .map(U64::from)
+            .map_err(|e| format!("无效的数量 {}: {}", s, e))
+    }
+}
+
 // ============================================================================
 // CQRS 命令定义
 // ============================================================================
@@ -350,6 +570,10 @@ pub enum EthCommand {
     /// (地址, 区块ID)
     GetBalance(Address, BlockId),
 
+    /// 批量获取账户余额（非标准扩展，避免前端逐个请求）
+    /// (地址列表, 区块ID)
+    GetBalances(Vec<Address>, BlockId),
+
     /// 获取存储值
     /// (地址, 存储位置, 区块ID)
     GetStorageAt(Address, U256, BlockId),
@@ -362,12 +586,19 @@ pub enum EthCommand {
     /// (地址, 区块ID)
     GetCode(Address, BlockId),
 
+    /// 获取本节点管理的账户列表
+    GetAccounts,
+
     // ========================================================================
     // 合约调用命令
     // ========================================================================
     /// 执行只读合约调用
-    /// (调用请求, 区块ID)
-    Call(CallRequest, BlockId),
+    /// (调用请求, 区块ID, 状态覆盖)
+    Call(CallRequest, BlockId, Option<StateOverride>),
+
+    /// 在同一状态快照上依次执行多个只读调用（非标准扩展）
+    /// (调用请求列表, 区块ID, 是否隔离执行)
+    CallBundle(Vec<CallRequest>, BlockId, bool),
 
     /// 估算交易 Gas 消耗
     EstimateGas(CallRequest),
@@ -390,15 +621,24 @@ pub enum EthCommand {
     /// 获取客户端版本
     GetClientVersion,
 
+    /// 获取 devp2p eth 协议版本（兼容旧工具）
+    GetProtocolVersion,
+
+    /// 获取挖矿算力（合并后恒为 0，兼容旧工具）
+    GetHashrate,
+
     // ========================================================================
     // EIP-1559 交易命令
     // ========================================================================
     /// 发送交易（需要签名）
     SendTransaction(SendTransactionRequest),
 
-    /// 发送原始交易（已签名）
-    /// (原始交易字节, 发送者地址)
-    SendRawTransaction(Vec<u8>, Address),
+    /// 发送原始交易（已签名，发送者由签名恢复）
+    SendRawTransaction(Vec<u8>),
+
+    /// 对 EIP-712 结构化数据签名
+    /// (签名账户, 结构化数据)
+    SignTypedData(Address, TypedData),
 
     /// 获取费用历史
     /// (区块数量, 结束区块, 奖励百分位数)
@@ -406,12 +646,33 @@ pub enum EthCommand {
 
     /// 获取建议的最大优先费用
     GetMaxPriorityFeePerGas,
+
+    /// 获取当前 blob base fee（EIP-4844）
+    GetBlobBaseFee,
+
+    // ========================================================================
+    // 调试命令
+    // ========================================================================
+    /// 获取交易的 EIP-2718 原始编码
+    GetRawTransaction(H256),
+
+    /// 获取区块内所有收据的原始编码
+    GetRawReceipts(BlockId),
+
+    // ========================================================================
+    // 自定义命令
+    // ========================================================================
+    /// 非内置方法（方法名, 原始参数），由注册的自定义处理器执行
+    Custom(String, serde_json::Value),
 }
 
 /// 命令执行结果
 ///
 /// 封装命令执行后的返回值
 /// 使用 enum 实现类型安全的多态返回值
+///
+/// 注：`Block` 按缓存行对齐，体积较大；结果值生命周期很短，不做装箱以避免额外分配
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone)]
 pub enum CommandResult {
     // ========================================================================
@@ -444,6 +705,12 @@ pub enum CommandResult {
     /// 地址
     Address(Address),
 
+    /// 地址列表
+    Addresses(Vec<Address>),
+
+    /// 地址 → 余额
+    Balances(BTreeMap<Address, U256>),
+
     // ========================================================================
     // 复杂类型结果
     // ========================================================================
@@ -461,11 +728,23 @@ pub enum CommandResult {
 
     /// 费用历史
     FeeHistory(FeeHistory),
+
+    /// eth_callBundle 各调用的执行结果
+    CallBundle(Vec<CallBundleResult>),
+
+    /// 已签名交易（接口层负责 EIP-2718 编码）
+    RawTransaction(Option<DynamicFeeTx>),
+
+    /// 区块收据列表（接口层负责 EIP-2718 编码）
+    RawReceipts(Option<Vec<TransactionReceipt>>),
+
+    /// 自定义处理器返回的原始 JSON
+    Json(serde_json::Value),
 }
 
 impl EthCommand {
     /// 获取命令名称（用于日志和调试）
-    pub fn name(&self) -> &'static str {
+    pub fn name(&self) -> &str {
         match self {
             Self::GetBlockNumber => "eth_blockNumber",
             Self::GetBlockByNumber(..) => "eth_getBlockByNumber",
@@ -473,20 +752,30 @@ impl EthCommand {
             Self::GetTransactionByHash(..) => "eth_getTransactionByHash",
             Self::GetTransactionReceipt(..) => "eth_getTransactionReceipt",
             Self::GetBalance(..) => "eth_getBalance",
+            Self::GetBalances(..) => "eth_getBalances",
             Self::GetStorageAt(..) => "eth_getStorageAt",
             Self::GetTransactionCount(..) => "eth_getTransactionCount",
             Self::GetCode(..) => "eth_getCode",
+            Self::GetAccounts => "eth_accounts",
             Self::Call(..) => "eth_call",
+            Self::CallBundle(..) => "eth_callBundle",
             Self::EstimateGas(..) => "eth_estimateGas",
             Self::GetLogs(..) => "eth_getLogs",
             Self::GetChainId => "eth_chainId",
             Self::GetGasPrice => "eth_gasPrice",
             Self::GetNetVersion => "net_version",
             Self::GetClientVersion => "web3_clientVersion",
+            Self::GetProtocolVersion => "eth_protocolVersion",
+            Self::GetHashrate => "eth_hashrate",
             Self::SendTransaction(..) => "eth_sendTransaction",
             Self::SendRawTransaction(..) => "eth_sendRawTransaction",
+            Self::SignTypedData(..) => "eth_signTypedData_v4",
             Self::GetFeeHistory(..) => "eth_feeHistory",
             Self::GetMaxPriorityFeePerGas => "eth_maxPriorityFeePerGas",
+            Self::GetBlobBaseFee => "eth_blobBaseFee",
+            Self::GetRawTransaction(..) => "debug_getRawTransaction",
+            Self::GetRawReceipts(..) => "debug_getRawReceipts",
+            Self::Custom(method, _) => method,
         }
     }
 
@@ -494,7 +783,7 @@ impl EthCommand {
     pub fn is_write_operation(&self) -> bool {
         matches!(
             self,
-            Self::SendTransaction(_) | Self::SendRawTransaction(_, _)
+            Self::SendTransaction(_) | Self::SendRawTransaction(_)
         )
     }
 
@@ -505,7 +794,7 @@ impl EthCommand {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
 
     #[test]
@@ -517,6 +806,28 @@ mod tests {
         assert_eq!(cmd.name(), "eth_getBalance");
     }
 
+    #[test]
+    fn test_quantity_requires_minimal_hex() {
+        assert_eq!(quantity::parse("0x0"), Ok(U64::zero()));
+        assert_eq!(quantity::parse("0x1"), Ok(U64::one()));
+        assert_eq!(quantity::parse("0x400"), Ok(U64::from(1024)));
+        assert!(quantity::parse("0x01").is_err());
+        assert!(quantity::parse("0x00").is_err());
+        assert!(quantity::parse("0x").is_err());
+        assert!(quantity::parse("1").is_err());
+
+        // 区块号参数按严格规则解析，标签不受影响
+        let parse = |v| serde_json::from_value::<BlockId>(v);
+        assert!(matches!(parse(serde_json::json!("0x0")), Ok(BlockId::Number(n)) if n.is_zero()));
+        assert!(matches!(parse(serde_json::json!("0x1")), Ok(BlockId::Number(n)) if n == U64::one()));
+        assert!(parse(serde_json::json!("0x01")).is_err());
+        assert!(matches!(parse(serde_json::json!("latest")), Ok(BlockId::Tag(BlockTag::Latest))));
+
+        // 输出为最简形式
+        assert_eq!(serde_json::to_value(BlockId::Number(U64::zero())).unwrap(), serde_json::json!("0x0"));
+        assert_eq!(serde_json::to_value(BlockId::Number(U64::from(1024))).unwrap(), serde_json::json!("0x400"));
+    }
+
     #[test]
     fn test_command_classification() {
         let read_cmd = EthCommand::GetBlockNumber;
@@ -537,4 +848,148 @@ mod tests {
         assert!(write_cmd.is_write_operation());
         assert!(!write_cmd.is_read_operation());
     }
+
+    pub(crate) fn sample_transaction(hash: H256) -> Transaction {
+        Transaction {
+            hash,
+            nonce: U256::zero(),
+            block_hash: Some(H256::from_low_u64_be(1)),
+            block_number: Some(U64::one()),
+            transaction_index: Some(U64::zero()),
+            from: Address::from_low_u64_be(1),
+            to: Some(Address::from_low_u64_be(2)),
+            value: U256::from(1000),
+            gas_price: None,
+            gas: U256::from(21000),
+            input: vec![],
+            v: U64::zero(),
+            r: U256::one(),
+            s: U256::one(),
+            max_fee_per_gas: Some(U256::from(2_000_000_000u64)),
+            max_priority_fee_per_gas: Some(U256::from(1_000_000_000u64)),
+            transaction_type: Some(U64::from(2)),
+        }
+    }
+
+    #[test]
+    fn test_block_transactions_hashes_serde() {
+        let hash = H256::repeat_byte(0xaa);
+        let txs = BlockTransactions::Hashes(vec![hash]);
+
+        let json = serde_json::to_value(&txs).unwrap();
+        assert_eq!(json, serde_json::json!([hash]));
+
+        let decoded: BlockTransactions = serde_json::from_value(json).unwrap();
+        assert!(matches!(decoded, BlockTransactions::Hashes(hashes) if hashes == vec![hash]));
+    }
+
+    #[test]
+    fn test_block_transactions_full_serde() {
+        let hash = H256::repeat_byte(0xbb);
+        let txs = BlockTransactions::Full(vec![sample_transaction(hash)]);
+
+        let json = serde_json::to_value(&txs).unwrap();
+        assert_eq!(json[0]["hash"], serde_json::json!(hash));
+        assert_eq!(json[0]["type"], serde_json::json!("0x2"));
+
+        let decoded: BlockTransactions = serde_json::from_value(json).unwrap();
+        match decoded {
+            BlockTransactions::Full(txs) => assert_eq!(txs[0].hash, hash),
+            other => panic!("应解码为完整交易: {:?}", other),
+        }
+        assert_eq!(txs.hashes(), vec![hash]);
+    }
+
+    #[test]
+    fn test_effective_gas_price() {
+        let gwei = |n: u64| U256::from(n * 1_000_000_000);
+        let tx = sample_transaction(H256::zero()); // max_fee 2 Gwei，小费 1 Gwei
+
+        // 小费生效：base_fee + 小费 未超过 max_fee
+        assert_eq!(tx.effective_gas_price(Some(gwei(1) / 2)), gwei(3) / 2);
+        // max_fee 封顶
+        assert_eq!(tx.effective_gas_price(Some(gwei(3) / 2)), gwei(2));
+
+        // Legacy 交易直接使用 gas_price
+        let legacy = Transaction {
+            gas_price: Some(gwei(3)),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            transaction_type: Some(U64::zero()),
+            ..tx
+        };
+        assert_eq!(legacy.effective_gas_price(Some(gwei(1))), gwei(3));
+    }
+
+    #[test]
+    fn test_receipt_serializes_type_and_effective_gas_price() {
+        let gwei = |n: u64| U256::from(n * 1_000_000_000);
+        let dynamic = sample_transaction(H256::repeat_byte(0x01)); // max_fee 2 Gwei，小费 1 Gwei
+        let legacy = Transaction {
+            hash: H256::repeat_byte(0x02),
+            gas_price: Some(gwei(3)),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            transaction_type: None,
+            ..dynamic.clone()
+        };
+        let receipt_json = |tx: &Transaction| {
+            let mut receipt = TransactionReceipt {
+                transaction_hash: tx.hash,
+                transaction_index: U64::zero(),
+                block_hash: H256::zero(),
+                block_number: U64::one(),
+                from: tx.from,
+                to: tx.to,
+                cumulative_gas_used: U256::from(21000),
+                gas_used: U256::from(21000),
+                effective_gas_price: U256::zero(),
+                contract_address: None,
+                logs: vec![],
+                logs_bloom: Bloom::zero(),
+                status: U64::one(),
+                transaction_type: U64::zero(),
+            };
+            receipt.set_fee_fields(tx, Some(gwei(1) / 2));
+            serde_json::to_value(&receipt).unwrap()
+        };
+
+        // EIP-1559：base_fee 0.5 Gwei + 小费 1 Gwei
+        let json = receipt_json(&dynamic);
+        assert_eq!(json["type"], serde_json::json!("0x2"));
+        assert_eq!(json["effectiveGasPrice"], serde_json::json!(gwei(3) / 2));
+
+        // Legacy：type 0，价格为 gas_price
+        let json = receipt_json(&legacy);
+        assert_eq!(json["type"], serde_json::json!("0x0"));
+        assert_eq!(json["effectiveGasPrice"], serde_json::json!(gwei(3)));
+    }
+
+    #[test]
+    fn test_legacy_chain_id_from_v() {
+        let legacy = |v: u64| Transaction {
+            gas_price: Some(U256::from(1_000_000_000u64)),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            transaction_type: Some(U64::zero()),
+            v: U64::from(v),
+            ..sample_transaction(H256::zero())
+        };
+
+        // EIP-155 之前
+        assert_eq!(legacy(27).chain_id(), Ok(None));
+        assert_eq!(legacy(28).chain_id(), Ok(None));
+        // 主网：v = 1 * 2 + 35/36
+        assert_eq!(legacy(37).chain_id(), Ok(Some(U64::one())));
+        assert_eq!(legacy(38).chain_id(), Ok(Some(U64::one())));
+        // Sepolia (11155111)
+        assert_eq!(legacy(11155111 * 2 + 35).chain_id(), Ok(Some(U64::from(11155111))));
+        // 29..=34 不是合法的 v，与 legacy_chain_id 一致
+        for v in 29..=34 {
+            assert_eq!(legacy(v).chain_id(), Err(TransactionValidationError::InvalidSignature));
+        }
+
+        // EIP-1559 交易的 v 为 y-parity
+        assert_eq!(sample_transaction(H256::zero()).chain_id(), Ok(None));
+    }
 }
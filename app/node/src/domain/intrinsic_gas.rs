@@ -0,0 +1,142 @@
+//! 交易固有 gas（执行前扣除的最低 gas）
+//!
+//! 参考: geth core/state_transition.go IntrinsicGas
+//! - 基础费用 21000，合约创建额外 32000 (EIP-2)
+//! - 调用数据：零字节 4 gas，非零字节 16 gas (EIP-2028，此前为 68)
+//! - 访问列表：每地址 2400 gas，每存储键 1900 gas (EIP-2930)
+//! - 合约创建的 initcode 每 32 字节字 2 gas (EIP-3860)
+//!
+//! 各常量随硬分叉变化，由 `IntrinsicGasSchedule` 按分叉提供。
+
+use crate::domain::tx_types::AccessListItem;
+
+/// 固有 gas 费率表
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntrinsicGasSchedule {
+    /// 交易基础费用
+    pub tx_gas: u64,
+    /// 合约创建额外费用
+    pub tx_create_gas: u64,
+    /// 调用数据零字节费用
+    pub data_zero_gas: u64,
+    /// 调用数据非零字节费用
+    pub data_non_zero_gas: u64,
+    /// 访问列表每地址费用
+    pub access_list_address_gas: u64,
+    /// 访问列表每存储键费用
+    pub access_list_storage_key_gas: u64,
+    /// initcode 每 32 字节字费用（0 表示不收取）
+    pub init_code_word_gas: u64,
+}
+
+impl IntrinsicGasSchedule {
+    /// Homestead：引入合约创建费用
+    pub const HOMESTEAD: Self = Self {
+        tx_gas: 21_000,
+        tx_create_gas: 32_000,
+        data_zero_gas: 4,
+        data_non_zero_gas: 68,
+        access_list_address_gas: 0,
+        access_list_storage_key_gas: 0,
+        init_code_word_gas: 0,
+    };
+
+    /// Istanbul：非零字节降为 16 (EIP-2028)
+    pub const ISTANBUL: Self = Self {
+        data_non_zero_gas: 16,
+        ..Self::HOMESTEAD
+    };
+
+    /// Berlin：访问列表 (EIP-2930)
+    pub const BERLIN: Self = Self {
+        access_list_address_gas: 2_400,
+        access_list_storage_key_gas: 1_900,
+        ..Self::ISTANBUL
+    };
+
+    /// Shanghai：initcode 计费 (EIP-3860)，Cancun 沿用
+    pub const SHANGHAI: Self = Self {
+        init_code_word_gas: 2,
+        ..Self::BERLIN
+    };
+
+    /// 计算固有 gas
+    pub fn intrinsic_gas(&self, data: &[u8], is_create: bool, access_list: &[AccessListItem]) -> u64 {
+        let zero_bytes = data.iter().filter(|b| **b == 0).count() as u64;
+        let non_zero_bytes = data.len() as u64 - zero_bytes;
+
+        let mut gas = self.tx_gas
+            + zero_bytes * self.data_zero_gas
+            + non_zero_bytes * self.data_non_zero_gas;
+        if is_create {
+            gas += self.tx_create_gas + (data.len() as u64).div_ceil(32) * self.init_code_word_gas;
+        }
+
+        let storage_keys: u64 = access_list
+            .iter()
+            .map(|item| item.storage_keys.len() as u64)
+            .sum();
+        gas + access_list.len() as u64 * self.access_list_address_gas
+            + storage_keys * self.access_list_storage_key_gas
+    }
+}
+
+impl Default for IntrinsicGasSchedule {
+    /// 当前主网规则
+    fn default() -> Self {
+        Self::SHANGHAI
+    }
+}
+
+/// 按当前主网规则计算固有 gas
+pub fn intrinsic_gas(data: &[u8], is_create: bool, access_list: &[AccessListItem]) -> u64 {
+    IntrinsicGasSchedule::default().intrinsic_gas(data, is_create, access_list)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethereum_types::{Address, H256};
+
+    #[test]
+    fn test_plain_transfer() {
+        assert_eq!(intrinsic_gas(&[], false, &[]), 21_000);
+    }
+
+    #[test]
+    fn test_mixed_calldata() {
+        let data = [0, 0, 1, 2];
+        assert_eq!(intrinsic_gas(&data, false, &[]), 21_000 + 2 * 4 + 2 * 16);
+        // Istanbul 之前非零字节 68 gas
+        assert_eq!(
+            IntrinsicGasSchedule::HOMESTEAD.intrinsic_gas(&data, false, &[]),
+            21_000 + 2 * 4 + 2 * 68
+        );
+    }
+
+    #[test]
+    fn test_creation_with_access_list() {
+        let init_code = [0x60u8; 33];
+        let access_list = vec![
+            AccessListItem {
+                address: Address::from_low_u64_be(1),
+                storage_keys: vec![H256::zero(), H256::from_low_u64_be(1)],
+            },
+            AccessListItem {
+                address: Address::from_low_u64_be(2),
+                storage_keys: vec![],
+            },
+        ];
+
+        // 21000 + 32000 + 33 个非零字节 + 2 个字 + 2 个地址 + 2 个存储键
+        assert_eq!(
+            intrinsic_gas(&init_code, true, &access_list),
+            53_000 + 33 * 16 + 2 * 2 + 2 * 2_400 + 2 * 1_900
+        );
+        // Berlin 尚未对 initcode 计费
+        assert_eq!(
+            IntrinsicGasSchedule::BERLIN.intrinsic_gas(&init_code, true, &access_list),
+            53_000 + 33 * 16 + 2 * 2_400 + 2 * 1_900
+        );
+    }
+}
@@ -2,3 +2,9 @@ pub mod tx_types;
 pub mod command_types;
 pub mod block_types;
 pub mod receipt_types;
+pub mod units;
+pub mod slot_types;
+pub mod intrinsic_gas;
+pub mod typed_data;
+pub mod version;
+pub mod rpc_config;
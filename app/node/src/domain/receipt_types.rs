@@ -1,11 +1,11 @@
-/// 交易收据相关类型定义 - 领域层实体
-///
-/// 参考标准：
-/// - Geth: core/types/receipt.go
-/// - EIP-658: 收据中的状态字段
-/// - EIP-2718: 类型化交易收据
+//! 交易收据相关类型定义 - 领域层实体
+//!
+//! 参考标准：
+//! - Geth: core/types/receipt.go
+//! - EIP-658: 收据中的状态字段
+//! - EIP-2718: 类型化交易收据
 
-use ethereum_types::{Address, Bloom, H256, U64};
+use ethereum_types::{Address, Bloom, BloomInput, H256, U256, U64};
 
 /// 交易收据
 ///
@@ -22,6 +22,10 @@ pub struct TransactionReceipt {
     pub cumulative_gas_used: U64,
     /// 本交易的gas使用量
     pub gas_used: U64,
+    /// 实际支付的单位gas价格 (base_fee + 实际小费)
+    pub effective_gas_price: U256,
+    /// 交易类型 (0=Legacy, 2=EIP-1559) - EIP-2718
+    pub transaction_type: U64,
     /// 执行状态 (1=成功, 0=失败) - EIP-658
     pub status: U64,
     /// 日志Bloom过滤器 (用于快速日志查询)
@@ -38,6 +42,8 @@ impl TransactionReceipt {
         transaction_index: u64,
         cumulative_gas_used: u64,
         gas_used: u64,
+        effective_gas_price: U256,
+        transaction_type: u8,
         success: bool,
         logs_bloom: Bloom,
         logs: Vec<Log>,
@@ -47,6 +53,8 @@ impl TransactionReceipt {
             transaction_index: U64::from(transaction_index),
             cumulative_gas_used: U64::from(cumulative_gas_used),
             gas_used: U64::from(gas_used),
+            effective_gas_price,
+            transaction_type: U64::from(transaction_type),
             status: if success { U64::one() } else { U64::zero() },
             logs_bloom,
             logs,
@@ -137,6 +145,23 @@ impl std::fmt::Display for ReceiptValidationError {
 
 impl std::error::Error for ReceiptValidationError {}
 
+/// 判断 Bloom 过滤器是否可能包含指定地址与主题
+///
+/// `topics` 按位置匹配，`None` 表示该位置不限。返回 false 时区块中一定没有匹配的日志；
+/// 返回 true 只表示可能存在（Bloom 有误判），仍需扫描日志确认。
+/// 参考: geth/core/types/bloom9.go BloomLookup
+pub fn bloom_may_contain(bloom: &Bloom, address: Option<Address>, topics: &[Option<H256>]) -> bool {
+    if let Some(address) = address {
+        if !bloom.contains_input(BloomInput::Raw(address.as_bytes())) {
+            return false;
+        }
+    }
+    topics
+        .iter()
+        .flatten()
+        .all(|topic| bloom.contains_input(BloomInput::Raw(topic.as_bytes())))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -148,6 +173,8 @@ mod tests {
             0,
             21000,
             21000,
+            U256::from(1_000_000_000u64),
+            2,
             true,
             Bloom::zero(),
             vec![],
@@ -165,6 +192,8 @@ mod tests {
             1,
             50000,
             50000,
+            U256::from(1_000_000_000u64),
+            0,
             false,
             Bloom::zero(),
             vec![],
@@ -223,4 +252,21 @@ mod tests {
         assert_eq!(log.event_signature(), None);
         assert!(log.validate_topics_count().is_ok());
     }
+
+    #[test]
+    fn test_bloom_may_contain() {
+        let address = Address::from_low_u64_be(0xc0de);
+        let topic = H256::repeat_byte(0x11);
+        let mut bloom = Bloom::zero();
+        bloom.accrue(BloomInput::Raw(address.as_bytes()));
+        bloom.accrue(BloomInput::Raw(topic.as_bytes()));
+
+        assert!(bloom_may_contain(&bloom, Some(address), &[Some(topic)]));
+        assert!(bloom_may_contain(&bloom, None, &[None, Some(topic)]));
+        assert!(!bloom_may_contain(&bloom, Some(Address::from_low_u64_be(0xbeef)), &[]));
+        assert!(!bloom_may_contain(&bloom, Some(address), &[Some(H256::repeat_byte(0x22))]));
+        // 空 Bloom 不包含任何地址，但无条件过滤总是可能匹配
+        assert!(!bloom_may_contain(&Bloom::zero(), Some(address), &[]));
+        assert!(bloom_may_contain(&Bloom::zero(), None, &[]));
+    }
 }
@@ -0,0 +1,149 @@
+//! JSON-RPC 服务配置类型
+//!
+//! 入站适配层与节点配置文件共用的设置：命名空间开关、HTTP 服务器参数、
+//! 单连接并发上限及溢出策略。
+//!
+//! 方法名前缀（`eth_blockNumber` 中的 `eth`）决定命名空间。运营方可只开放部分命名空间，
+//! 例如公网端口关闭 `debug` 与 `engine`；被关闭命名空间的方法按“方法未找到”处理，
+//! 不暴露其是否存在。无法识别前缀的方法不受开关影响，交由命令映射判断。
+
+use serde::Deserialize;
+use std::collections::HashSet;
+
+/// JSON-RPC 命名空间
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Namespace {
+    Eth,
+    Net,
+    Web3,
+    Debug,
+    Txpool,
+    Engine,
+}
+
+impl Namespace {
+    /// 全部命名空间
+    pub const ALL: [Namespace; 6] = [
+        Namespace::Eth,
+        Namespace::Net,
+        Namespace::Web3,
+        Namespace::Debug,
+        Namespace::Txpool,
+        Namespace::Engine,
+    ];
+
+    /// 方法所属的命名空间（前缀无法识别时为 None）
+    pub fn of(method: &str) -> Option<Self> {
+        let (prefix, _) = method.split_once('_')?;
+        match prefix {
+            "eth" => Some(Self::Eth),
+            "net" => Some(Self::Net),
+            "web3" => Some(Self::Web3),
+            "debug" => Some(Self::Debug),
+            "txpool" => Some(Self::Txpool),
+            "engine" => Some(Self::Engine),
+            _ => None,
+        }
+    }
+}
+
+/// 已开放的命名空间（默认全部开放）
+///
+/// 配置文件中写作命名空间列表，如 `namespaces = ["eth", "net", "web3"]`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct NamespaceConfig {
+    pub enabled: HashSet<Namespace>,
+}
+
+impl NamespaceConfig {
+    /// 只开放指定的命名空间
+    pub fn only(namespaces: impl IntoIterator<Item = Namespace>) -> Self {
+        Self {
+            enabled: namespaces.into_iter().collect(),
+        }
+    }
+
+    /// 方法是否允许调用
+    pub fn allows(&self, method: &str) -> bool {
+        Namespace::of(method).is_none_or(|namespace| self.enabled.contains(&namespace))
+    }
+}
+
+impl Default for NamespaceConfig {
+    fn default() -> Self {
+        Self::only(Namespace::ALL)
+    }
+}
+
+/// 在途请求达到上限后的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConnectionOverflow {
+    /// 立即返回 429
+    #[default]
+    Reject,
+    /// 排队等待前面的请求完成
+    Queue,
+}
+
+/// HTTP 服务器配置
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    /// 是否启用响应压缩（按客户端 Accept-Encoding 协商 gzip / br）
+    pub compression: bool,
+    /// 触发压缩的最小响应体字节数，小响应压缩得不偿失
+    pub compression_min_size: u16,
+    /// 宽松模式：接受未携带 Content-Type 的请求（部分工具不发送该头）；
+    /// 携带非 JSON 的 Content-Type 始终被拒绝
+    pub lenient_content_type: bool,
+    /// 请求体最大字节数（单个请求与整个批量请求共用），超出返回 413
+    pub max_body_size: usize,
+    /// 单个连接的在途请求上限（None 不限制）
+    ///
+    /// 需以 `into_make_service_with_connect_info::<SocketAddr>()` 提供对端地址才生效
+    pub max_requests_per_connection: Option<usize>,
+    /// 连接在途请求达到上限后排队还是返回 429
+    pub connection_overflow: ConnectionOverflow,
+}
+
+/// 默认请求体上限，与 geth 的 maxRequestContentLength 一致
+pub const DEFAULT_MAX_BODY_SIZE: usize = 5 * 1024 * 1024;
+
+/// 默认单连接在途请求上限
+pub const DEFAULT_MAX_REQUESTS_PER_CONNECTION: usize = 64;
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            compression: true,
+            compression_min_size: 1024,
+            lenient_content_type: false,
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+            max_requests_per_connection: Some(DEFAULT_MAX_REQUESTS_PER_CONNECTION),
+            connection_overflow: ConnectionOverflow::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_namespace_from_method_prefix() {
+        assert_eq!(Namespace::of("eth_blockNumber"), Some(Namespace::Eth));
+        assert_eq!(Namespace::of("debug_getRawReceipts"), Some(Namespace::Debug));
+        assert_eq!(Namespace::of("engine_forkchoiceUpdatedV3"), Some(Namespace::Engine));
+        assert_eq!(Namespace::of("personal_sign"), None);
+        assert_eq!(Namespace::of("eth"), None);
+
+        let config = NamespaceConfig::only([Namespace::Eth, Namespace::Net]);
+        assert!(config.allows("eth_blockNumber"));
+        assert!(config.allows("net_version"));
+        assert!(!config.allows("debug_rpcStats"));
+        assert!(!config.allows("web3_clientVersion"));
+        assert!(NamespaceConfig::default().allows("engine_getPayloadV3"));
+    }
+}
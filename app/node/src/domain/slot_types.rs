@@ -0,0 +1,96 @@
+//! 共识层时间类型 - Slot / Epoch 及其规格参数
+//!
+//! 参考标准：consensus-specs phase0
+//! - SECONDS_PER_SLOT = 12
+//! - SLOTS_PER_EPOCH  = 32
+//! - 主网 genesis_time = 1606824023
+
+use std::fmt;
+
+/// Slot 编号
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Slot(pub u64);
+
+/// Epoch 编号
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Epoch(pub u64);
+
+impl Slot {
+    /// 所属 epoch（compute_epoch_at_slot）
+    pub fn epoch(self, slots_per_epoch: u64) -> Epoch {
+        Epoch(self.0 / slots_per_epoch)
+    }
+}
+
+impl Epoch {
+    /// epoch 的首个 slot（compute_start_slot_at_epoch）
+    pub fn start_slot(self, slots_per_epoch: u64) -> Slot {
+        Slot(self.0.saturating_mul(slots_per_epoch))
+    }
+}
+
+impl fmt::Display for Slot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "slot {}", self.0)
+    }
+}
+
+impl fmt::Display for Epoch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "epoch {}", self.0)
+    }
+}
+
+/// 链规格中与时间相关的参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainSpec {
+    /// SECONDS_PER_SLOT
+    pub seconds_per_slot: u64,
+    /// SLOTS_PER_EPOCH
+    pub slots_per_epoch: u64,
+}
+
+impl ChainSpec {
+    /// 主网规格
+    pub const fn mainnet() -> Self {
+        Self {
+            seconds_per_slot: 12,
+            slots_per_epoch: 32,
+        }
+    }
+}
+
+impl Default for ChainSpec {
+    fn default() -> Self {
+        Self::mainnet()
+    }
+}
+
+/// 创世信息
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GenesisInfo {
+    /// 创世时间（Unix 秒）
+    pub genesis_time: u64,
+}
+
+impl GenesisInfo {
+    /// 主网信标链创世信息
+    pub const fn mainnet() -> Self {
+        Self {
+            genesis_time: 1_606_824_023,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slot_epoch_conversion() {
+        assert_eq!(Slot(0).epoch(32), Epoch(0));
+        assert_eq!(Slot(31).epoch(32), Epoch(0));
+        assert_eq!(Slot(32).epoch(32), Epoch(1));
+        assert_eq!(Epoch(194_048).start_slot(32), Slot(6_209_536));
+    }
+}
@@ -1,5 +1,6 @@
 //定义 领域层 DynamicFeeTx (EIP-1559) 后续BlobTx (EIP-4844)，参考 geth  core/types/transaction.go;
 
+use crate::domain::intrinsic_gas::IntrinsicGasSchedule;
 use ethereum_types::{Address, H256, U256, U64};
 use std::fmt;
 
@@ -46,8 +47,8 @@ pub struct AccessListItem {
 pub enum TransactionValidationError {
     /// 无效的签名
     InvalidSignature,
-    /// 无效的chain_id
-    InvalidChainId { expected: U64, actual: U64 },
+    /// 交易绑定的 chain id 与本节点不符（EIP-155 重放保护）
+    WrongChainId { expected: U64, actual: U64 },
     /// Gas价格过低
     GasPriceTooLow { min: U256, actual: U256 },
     /// Max priority fee 超过 max fee
@@ -62,14 +63,16 @@ pub enum TransactionValidationError {
     DataTooLarge { max: usize, actual: usize },
     /// RLP解码错误
     RlpDecodeError(String),
+    /// 未受重放保护的 legacy 交易（EIP-155 之前的签名）
+    UnprotectedTransaction,
 }
 
 impl fmt::Display for TransactionValidationError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::InvalidSignature => write!(f, "Invalid transaction signature"),
-            Self::InvalidChainId { expected, actual } => {
-                write!(f, "Invalid chain ID: expected {}, got {}", expected, actual)
+            Self::WrongChainId { expected, actual } => {
+                write!(f, "Wrong chain ID: expected {}, got {}", expected, actual)
             }
             Self::GasPriceTooLow { min, actual } => {
                 write!(f, "Gas price too low: minimum {}, got {}", min, actual)
@@ -90,6 +93,9 @@ impl fmt::Display for TransactionValidationError {
                 write!(f, "Transaction data too large: max {} bytes, got {}", max, actual)
             }
             Self::RlpDecodeError(msg) => write!(f, "RLP decode error: {}", msg),
+            Self::UnprotectedTransaction => {
+                write!(f, "Only replay-protected (EIP-155) transactions allowed")
+            }
         }
     }
 }
@@ -106,21 +112,31 @@ impl DynamicFeeTx {
     /// 最大交易数据大小 (128KB)
     pub const MAX_DATA_SIZE: usize = 128 * 1024;
 
-    /// 验证交易基本字段（不包括状态相关验证）
+    /// 验证交易基本字段（不包括状态相关验证），按当前主网规则计算固有 gas
     pub fn validate_basic(&self) -> Result<(), TransactionValidationError> {
+        self.validate_basic_with_schedule(&IntrinsicGasSchedule::default())
+    }
+
+    /// 验证交易基本字段，按指定分叉的费率表计算固有 gas
+    pub fn validate_basic_with_schedule(
+        &self,
+        schedule: &IntrinsicGasSchedule,
+    ) -> Result<(), TransactionValidationError> {
         // 1. 验证 max_priority_fee <= max_fee_per_gas
         if self.max_priority_fee_per_gas > self.max_fee_per_gas {
             return Err(TransactionValidationError::PriorityFeeExceedsMaxFee);
         }
 
-        // 2. 验证 gas_limit >= 最小值
-        let min_gas = if self.to.is_none() || !self.data.is_empty() {
-            // 合约创建或合约调用需要更多gas
-            Self::MIN_GAS_LIMIT
-        } else {
-            Self::MIN_GAS_LIMIT
-        };
+        // 2. 验证数据大小（先于固有 gas 计算，避免遍历超大数据）
+        if self.data.len() > Self::MAX_DATA_SIZE {
+            return Err(TransactionValidationError::DataTooLarge {
+                max: Self::MAX_DATA_SIZE,
+                actual: self.data.len(),
+            });
+        }
 
+        // 3. 验证 gas_limit >= 固有 gas
+        let min_gas = schedule.intrinsic_gas(&self.data, self.to.is_none(), &self.access_list);
         if self.gas_limit.as_u64() < min_gas {
             return Err(TransactionValidationError::InsufficientGas {
                 min: min_gas,
@@ -128,14 +144,6 @@ impl DynamicFeeTx {
             });
         }
 
-        // 3. 验证数据大小
-        if self.data.len() > Self::MAX_DATA_SIZE {
-            return Err(TransactionValidationError::DataTooLarge {
-                max: Self::MAX_DATA_SIZE,
-                actual: self.data.len(),
-            });
-        }
-
         // 4. 验证签名值有效性
         if self.v > U64::from(1) {
             return Err(TransactionValidationError::InvalidSignature);
@@ -149,26 +157,57 @@ impl DynamicFeeTx {
         self.max_fee_per_gas * U256::from(self.gas_limit.as_u64()) + self.value
     }
 
+    /// 计算实际支付的单位 gas 价格，见 [`effective_gas_price`]
+    pub fn effective_gas_price(&self, base_fee: U256) -> U256 {
+        effective_gas_price(self.max_fee_per_gas, self.max_priority_fee_per_gas, base_fee)
+    }
+
     /// 恢复发送者地址（需要验证签名）
+    ///
+    /// 对 `signing_hash()` 做 secp256k1 公钥恢复，v 即 recovery id (0/1)
     pub fn recover_sender(&self) -> Result<Address, TransactionValidationError> {
-        // TODO: 实现ECDSA签名恢复
-        // 这需要使用k256或secp256k1库进行椭圆曲线签名验证
-        // 暂时返回错误，后续实现
-        Err(TransactionValidationError::InvalidSignature)
+        use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+
+        if self.v > U64::from(1) {
+            return Err(TransactionValidationError::InvalidSignature);
+        }
+
+        let mut sig_bytes = [0u8; 64];
+        self.r.to_big_endian(&mut sig_bytes[..32]);
+        self.s.to_big_endian(&mut sig_bytes[32..]);
+
+        let signature = Signature::from_slice(&sig_bytes)
+            .map_err(|_| TransactionValidationError::InvalidSignature)?;
+        let recovery_id = RecoveryId::from_byte(self.v.as_u64() as u8)
+            .ok_or(TransactionValidationError::InvalidSignature)?;
+
+        let verifying_key = VerifyingKey::recover_from_prehash(
+            self.signing_hash().as_bytes(),
+            &signature,
+            recovery_id,
+        )
+        .map_err(|_| TransactionValidationError::InvalidSignature)?;
+
+        Ok(public_key_to_address(&verifying_key))
     }
 
-    /// 计算交易哈希
+    /// 计算签名哈希
     ///
-    /// 根据 EIP-2718 和 EIP-1559 规范：
-    /// hash = keccak256(0x02 || rlp([chain_id, nonce, max_priority_fee_per_gas,
-    ///                               max_fee_per_gas, gas_limit, to, value, data,
-    ///                               access_list, v, r, s]))
-    pub fn hash(&self) -> H256 {
+    /// 根据 EIP-1559 规范：
+    /// signing_hash = keccak256(0x02 || rlp([chain_id, nonce, max_priority_fee_per_gas,
+    ///                                       max_fee_per_gas, gas_limit, to, value, data,
+    ///                                       access_list]))
+    pub fn signing_hash(&self) -> H256 {
         use rlp::RlpStream;
-        use sha3::{Digest, Keccak256};
 
-        // 构建 RLP 编码（12 个字段）
-        let mut stream = RlpStream::new_list(12);
+        let mut stream = RlpStream::new_list(9);
+        self.append_payload(&mut stream);
+
+        Self::typed_keccak(&stream.out())
+    }
+
+    /// 追加交易负载字段（不含签名）的 RLP 编码
+    fn append_payload(&self, stream: &mut rlp::RlpStream) {
         stream.append(&self.chain_id);
         stream.append(&self.nonce);
         stream.append(&self.max_priority_fee_per_gas);
@@ -195,24 +234,84 @@ impl DynamicFeeTx {
                 stream.append(key);
             }
         }
+    }
+
+    /// keccak256(0x02 || rlp_encoded)
+    fn typed_keccak(rlp_encoded: &[u8]) -> H256 {
+        use sha3::{Digest, Keccak256};
+
+        let mut hasher = Keccak256::new();
+        hasher.update([Self::TRANSACTION_TYPE]);
+        hasher.update(rlp_encoded);
+        H256::from_slice(&hasher.finalize())
+    }
+
+    /// EIP-2718 编码（网络传输格式）
+    ///
+    /// 0x02 || rlp([chain_id, nonce, max_priority_fee_per_gas, max_fee_per_gas,
+    ///              gas_limit, to, value, data, access_list, v, r, s])
+    pub fn encode_2718(&self) -> Vec<u8> {
+        use rlp::RlpStream;
+
+        // 构建 RLP 编码（12 个字段）
+        let mut stream = RlpStream::new_list(12);
+        self.append_payload(&mut stream);
 
         // 签名字段
         stream.append(&self.v);
         stream.append(&self.r);
         stream.append(&self.s);
 
-        let rlp_encoded = stream.out();
+        let mut encoded = vec![Self::TRANSACTION_TYPE];
+        encoded.extend_from_slice(&stream.out());
+        encoded
+    }
 
-        // 添加交易类型前缀 0x02（EIP-1559）
-        let mut tx_bytes = vec![Self::TRANSACTION_TYPE];
-        tx_bytes.extend_from_slice(&rlp_encoded);
+    /// 计算交易哈希
+    ///
+    /// 根据 EIP-2718 和 EIP-1559 规范：
+    /// hash = keccak256(0x02 || rlp([chain_id, nonce, max_priority_fee_per_gas,
+    ///                               max_fee_per_gas, gas_limit, to, value, data,
+    ///                               access_list, v, r, s]))
+    pub fn hash(&self) -> H256 {
+        use sha3::{Digest, Keccak256};
 
-        // 计算 keccak256 哈希
-        let mut hasher = Keccak256::new();
-        hasher.update(&tx_bytes);
-        let hash_result = hasher.finalize();
+        H256::from_slice(&Keccak256::digest(self.encode_2718()))
+    }
+}
+
+/// 由 secp256k1 公钥推导以太坊地址：keccak256(未压缩公钥[1..])[12..]
+pub fn public_key_to_address(key: &k256::ecdsa::VerifyingKey) -> Address {
+    use sha3::{Digest, Keccak256};
 
-        H256::from_slice(&hash_result)
+    let encoded = key.to_encoded_point(false);
+    let hash = Keccak256::digest(&encoded.as_bytes()[1..]);
+    Address::from_slice(&hash[12..])
+}
+
+/// EIP-1559 交易实际支付的单位 gas 价格
+///
+/// effective_gas_price = min(max_fee_per_gas, base_fee + max_priority_fee_per_gas)，
+/// 其中超出 base_fee 的部分是出块者获得的小费
+pub fn effective_gas_price(
+    max_fee_per_gas: U256,
+    max_priority_fee_per_gas: U256,
+    base_fee: U256,
+) -> U256 {
+    max_fee_per_gas.min(base_fee.saturating_add(max_priority_fee_per_gas))
+}
+
+/// 从 legacy 交易签名的 v 值解析 chain id
+///
+/// 参考: EIP-155
+/// - v ∈ {27, 28}：EIP-155 之前的签名，不含 chain id，返回 `Ok(None)`
+/// - v >= 35：v = chain_id * 2 + 35 + recovery_id，返回 `Ok(Some(chain_id))`
+/// - 其他取值不是合法签名
+pub fn legacy_chain_id(v: u64) -> Result<Option<U64>, TransactionValidationError> {
+    match v {
+        27 | 28 => Ok(None),
+        v if v >= 35 => Ok(Some(U64::from((v - 35) / 2))),
+        _ => Err(TransactionValidationError::InvalidSignature),
     }
 }
 
@@ -371,4 +470,34 @@ mod tests {
         let expected = U256::from(2_000_000_000u64) * U256::from(21000);
         assert_eq!(tx.max_cost(), expected);
     }
+
+    #[test]
+    fn test_recover_sender_roundtrip() {
+        use k256::ecdsa::SigningKey;
+
+        let signing_key = SigningKey::from_slice(&[0x42u8; 32]).unwrap();
+        let expected = public_key_to_address(signing_key.verifying_key());
+
+        let mut tx = create_minimal_tx();
+        let (signature, recovery_id) = signing_key
+            .sign_prehash_recoverable(tx.signing_hash().as_bytes())
+            .unwrap();
+        let bytes = signature.to_bytes();
+        tx.r = U256::from_big_endian(&bytes[..32]);
+        tx.s = U256::from_big_endian(&bytes[32..]);
+        tx.v = U64::from(recovery_id.to_byte());
+
+        assert_eq!(tx.recover_sender().unwrap(), expected);
+
+        // 篡改负载后恢复出的地址不再匹配
+        tx.nonce = U64::from(99);
+        assert_ne!(tx.recover_sender().ok(), Some(expected));
+    }
+
+    #[test]
+    fn test_recover_sender_invalid_signature() {
+        let mut tx = create_minimal_tx();
+        tx.r = U256::zero();
+        assert_eq!(tx.recover_sender(), Err(TransactionValidationError::InvalidSignature));
+    }
 }
@@ -0,0 +1,404 @@
+//! EIP-712 结构化数据哈希（eth_signTypedData_v4）
+//!
+//! 参考: https://eips.ethereum.org/EIPS/eip-712 ，geth signer/core/apitypes/types.go
+//! - encodeType：主类型在前，其余引用到的结构体类型按名称字母序拼接
+//! - hashStruct(s) = keccak256(typeHash ‖ encodeData(s))
+//! - 签名摘要 = keccak256(0x19 ‖ 0x01 ‖ domainSeparator ‖ hashStruct(message))
+//!
+//! v4 语义：支持数组与嵌套结构体数组；字段缺失视为错误。
+
+use ethereum_types::{H256, U256};
+use serde::Deserialize;
+use serde_json::Value;
+use sha3::{Digest, Keccak256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// 域类型名
+pub const DOMAIN_TYPE: &str = "EIP712Domain";
+
+/// 类型定义中的单个字段
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct TypedDataField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub field_type: String,
+}
+
+/// eth_signTypedData_v4 的请求数据
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+    /// 类型名 → 字段列表（须包含 `EIP712Domain`）
+    pub types: BTreeMap<String, Vec<TypedDataField>>,
+    /// 待签名消息的类型
+    pub primary_type: String,
+    /// 域（按 `EIP712Domain` 编码）
+    pub domain: Value,
+    /// 待签名消息
+    pub message: Value,
+}
+
+/// 结构化数据编码错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypedDataError {
+    /// 引用了未定义的类型
+    UnknownType(String),
+    /// 结构体缺少字段
+    MissingField { type_name: String, field: String },
+    /// 字段值与声明的类型不匹配
+    InvalidValue { field_type: String, value: String },
+}
+
+impl fmt::Display for TypedDataError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownType(name) => write!(f, "Unknown type: {}", name),
+            Self::MissingField { type_name, field } => {
+                write!(f, "Missing field {} of type {}", field, type_name)
+            }
+            Self::InvalidValue { field_type, value } => {
+                write!(f, "Invalid {} value: {}", field_type, value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TypedDataError {}
+
+impl TypedData {
+    /// 最终签名摘要：keccak256(0x19 ‖ 0x01 ‖ domainSeparator ‖ hashStruct(message))
+    ///
+    /// 主类型为 `EIP712Domain` 时省略消息部分
+    pub fn signing_hash(&self) -> Result<H256, TypedDataError> {
+        let mut buf = Vec::with_capacity(66);
+        buf.extend_from_slice(&[0x19, 0x01]);
+        buf.extend_from_slice(self.domain_separator()?.as_bytes());
+        if self.primary_type != DOMAIN_TYPE {
+            buf.extend_from_slice(self.hash_struct(&self.primary_type, &self.message)?.as_bytes());
+        }
+        Ok(keccak(&buf))
+    }
+
+    /// domainSeparator = hashStruct(EIP712Domain, domain)
+    pub fn domain_separator(&self) -> Result<H256, TypedDataError> {
+        self.hash_struct(DOMAIN_TYPE, &self.domain)
+    }
+
+    /// hashStruct(s) = keccak256(typeHash ‖ encodeData(s))
+    pub fn hash_struct(&self, type_name: &str, data: &Value) -> Result<H256, TypedDataError> {
+        Ok(keccak(&self.encode_data(type_name, data)?))
+    }
+
+    /// typeHash = keccak256(encodeType(type))
+    pub fn type_hash(&self, type_name: &str) -> Result<H256, TypedDataError> {
+        Ok(keccak(self.encode_type(type_name)?.as_bytes()))
+    }
+
+    /// 如 `Mail(Person from,Person to,string contents)Person(string name,address wallet)`
+    pub fn encode_type(&self, type_name: &str) -> Result<String, TypedDataError> {
+        let mut deps = BTreeSet::new();
+        self.collect_dependencies(type_name, &mut deps)?;
+        deps.remove(type_name);
+
+        let mut encoded = String::new();
+        for name in std::iter::once(type_name).chain(deps.iter().map(String::as_str)) {
+            let fields = self.fields(name)?;
+            let params: Vec<String> = fields
+                .iter()
+                .map(|field| format!("{} {}", field.field_type, field.name))
+                .collect();
+            encoded.push_str(&format!("{}({})", name, params.join(",")));
+        }
+        Ok(encoded)
+    }
+
+    /// typeHash ‖ 每个字段的 32 字节编码
+    fn encode_data(&self, type_name: &str, data: &Value) -> Result<Vec<u8>, TypedDataError> {
+        let fields = self.fields(type_name)?;
+        let object = data.as_object().ok_or_else(|| TypedDataError::InvalidValue {
+            field_type: type_name.to_string(),
+            value: data.to_string(),
+        })?;
+
+        let mut encoded = Vec::with_capacity(32 * (fields.len() + 1));
+        encoded.extend_from_slice(self.type_hash(type_name)?.as_bytes());
+        for field in fields {
+            let value = object.get(&field.name).ok_or_else(|| TypedDataError::MissingField {
+                type_name: type_name.to_string(),
+                field: field.name.clone(),
+            })?;
+            encoded.extend_from_slice(self.encode_value(&field.field_type, value)?.as_bytes());
+        }
+        Ok(encoded)
+    }
+
+    /// 单个字段值编码为 32 字节
+    fn encode_value(&self, field_type: &str, value: &Value) -> Result<H256, TypedDataError> {
+        let invalid = || TypedDataError::InvalidValue {
+            field_type: field_type.to_string(),
+            value: value.to_string(),
+        };
+
+        // 数组：keccak256(各元素编码拼接)
+        if let Some(element_type) = array_element_type(field_type) {
+            let items = value.as_array().ok_or_else(invalid)?;
+            let mut encoded = Vec::with_capacity(32 * items.len());
+            for item in items {
+                encoded.extend_from_slice(self.encode_value(element_type, item)?.as_bytes());
+            }
+            return Ok(keccak(&encoded));
+        }
+
+        // 结构体：递归 hashStruct
+        if self.types.contains_key(field_type) {
+            return self.hash_struct(field_type, value);
+        }
+
+        match field_type {
+            "string" => Ok(keccak(value.as_str().ok_or_else(invalid)?.as_bytes())),
+            "bytes" => Ok(keccak(&parse_hex(value).ok_or_else(invalid)?)),
+            "bool" => {
+                let b = value.as_bool().ok_or_else(invalid)?;
+                Ok(H256::from_low_u64_be(b as u64))
+            }
+            "address" => {
+                let bytes = parse_hex(value).filter(|b| b.len() == 20).ok_or_else(invalid)?;
+                let mut word = H256::zero();
+                word.0[12..].copy_from_slice(&bytes);
+                Ok(word)
+            }
+            _ => {
+                if let Some(bits) = field_type.strip_prefix("uint").and_then(parse_int_bits) {
+                    let n = parse_integer(value).filter(|(neg, n)| !neg && fits(*n, bits));
+                    let (_, n) = n.ok_or_else(invalid)?;
+                    return Ok(u256_to_h256(n));
+                }
+                if let Some(bits) = field_type.strip_prefix("int").and_then(parse_int_bits) {
+                    let (negative, magnitude) = parse_integer(value).ok_or_else(invalid)?;
+                    // 有符号范围：[-2^(bits-1), 2^(bits-1) - 1]
+                    let limit = U256::one() << (bits - 1);
+                    let in_range = if negative { magnitude <= limit } else { magnitude < limit };
+                    if !in_range {
+                        return Err(invalid());
+                    }
+                    // 负数按 256 位补码编码
+                    let n = if negative && !magnitude.is_zero() {
+                        (!magnitude).overflowing_add(U256::one()).0
+                    } else {
+                        magnitude
+                    };
+                    return Ok(u256_to_h256(n));
+                }
+                if let Some(size) = field_type.strip_prefix("bytes").and_then(|s| s.parse::<usize>().ok()) {
+                    if !(1..=32).contains(&size) {
+                        return Err(TypedDataError::UnknownType(field_type.to_string()));
+                    }
+                    let bytes = parse_hex(value).filter(|b| b.len() == size).ok_or_else(invalid)?;
+                    let mut word = H256::zero();
+                    word.0[..size].copy_from_slice(&bytes);
+                    return Ok(word);
+                }
+                Err(TypedDataError::UnknownType(field_type.to_string()))
+            }
+        }
+    }
+
+    fn fields(&self, type_name: &str) -> Result<&[TypedDataField], TypedDataError> {
+        self.types
+            .get(type_name)
+            .map(Vec::as_slice)
+            .ok_or_else(|| TypedDataError::UnknownType(type_name.to_string()))
+    }
+
+    /// 收集 `type_name` 直接或间接引用的所有结构体类型（含自身）
+    fn collect_dependencies(
+        &self,
+        type_name: &str,
+        deps: &mut BTreeSet<String>,
+    ) -> Result<(), TypedDataError> {
+        if !deps.insert(type_name.to_string()) {
+            return Ok(());
+        }
+        for field in self.fields(type_name)? {
+            let base = base_type(&field.field_type);
+            if self.types.contains_key(base) {
+                self.collect_dependencies(base, deps)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn keccak(data: &[u8]) -> H256 {
+    H256::from_slice(&Keccak256::digest(data))
+}
+
+/// `Person[]` / `uint256[3]` → 元素类型
+fn array_element_type(field_type: &str) -> Option<&str> {
+    let stripped = field_type.strip_suffix(']')?;
+    stripped.rfind('[').map(|i| &stripped[..i])
+}
+
+/// 去掉所有数组后缀：`Person[][2]` → `Person`
+fn base_type(field_type: &str) -> &str {
+    field_type.split('[').next().unwrap_or(field_type)
+}
+
+/// `uint`/`int` 后缀的位宽：8 的倍数，1..=256；无后缀按 256
+fn parse_int_bits(suffix: &str) -> Option<usize> {
+    if suffix.is_empty() {
+        return Some(256);
+    }
+    let bits: usize = suffix.parse().ok()?;
+    (bits > 0 && bits <= 256 && bits.is_multiple_of(8)).then_some(bits)
+}
+
+fn fits(n: U256, bits: usize) -> bool {
+    bits == 256 || n < (U256::one() << bits)
+}
+
+/// 整数值：JSON 数字、十进制字符串或 0x 十六进制字符串，返回 (是否为负, 绝对值)
+fn parse_integer(value: &Value) -> Option<(bool, U256)> {
+    match value {
+        Value::Number(n) => {
+            if let Some(u) = n.as_u64() {
+                Some((false, U256::from(u)))
+            } else {
+                n.as_i64().map(|i| (i < 0, U256::from(i.unsigned_abs())))
+            }
+        }
+        Value::String(s) => {
+            let (negative, digits) = match s.strip_prefix('-') {
+                Some(rest) => (true, rest),
+                None => (false, s.as_str()),
+            };
+            let magnitude = match digits.strip_prefix("0x").or_else(|| digits.strip_prefix("0X")) {
+                Some(hex) => U256::from_str_radix(hex, 16).ok()?,
+                None => U256::from_dec_str(digits).ok()?,
+            };
+            Some((negative, magnitude))
+        }
+        _ => None,
+    }
+}
+
+fn parse_hex(value: &Value) -> Option<Vec<u8>> {
+    let s = value.as_str()?;
+    hex::decode(s.strip_prefix("0x").unwrap_or(s)).ok()
+}
+
+fn u256_to_h256(n: U256) -> H256 {
+    let mut word = H256::zero();
+    n.to_big_endian(&mut word.0);
+    word
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// EIP-712 规范中的 Mail/Person 示例
+    pub(crate) fn mail() -> TypedData {
+        serde_json::from_value(serde_json::json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" }
+                ],
+                "Person": [
+                    { "name": "name", "type": "string" },
+                    { "name": "wallet", "type": "address" }
+                ],
+                "Mail": [
+                    { "name": "from", "type": "Person" },
+                    { "name": "to", "type": "Person" },
+                    { "name": "contents", "type": "string" }
+                ]
+            },
+            "primaryType": "Mail",
+            "domain": {
+                "name": "Ether Mail",
+                "version": "1",
+                "chainId": 1,
+                "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+            },
+            "message": {
+                "from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+                "to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+                "contents": "Hello, Bob!"
+            }
+        }))
+        .unwrap()
+    }
+
+    fn h256(s: &str) -> H256 {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_mail_example_digest() {
+        let data = mail();
+
+        assert_eq!(
+            data.encode_type("Mail").unwrap(),
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+        );
+        assert_eq!(
+            data.domain_separator().unwrap(),
+            h256("0xf2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f")
+        );
+        assert_eq!(
+            data.hash_struct("Mail", &data.message).unwrap(),
+            h256("0xc52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e")
+        );
+        assert_eq!(
+            data.signing_hash().unwrap(),
+            h256("0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2")
+        );
+    }
+
+    #[test]
+    fn test_array_of_structs() {
+        let mut data = mail();
+        data.types.get_mut("Mail").unwrap()[1].field_type = "Person[]".to_string();
+        let to = data.message["to"].clone();
+        data.message["to"] = serde_json::json!([to.clone(), to]);
+
+        assert_eq!(
+            data.encode_type("Mail").unwrap(),
+            "Mail(Person from,Person[] to,string contents)Person(string name,address wallet)"
+        );
+        assert!(data.signing_hash().is_ok());
+    }
+
+    #[test]
+    fn test_invalid_values_rejected() {
+        let mut data = mail();
+        data.message.as_object_mut().unwrap().remove("contents");
+        assert_eq!(
+            data.signing_hash(),
+            Err(TypedDataError::MissingField {
+                type_name: "Mail".to_string(),
+                field: "contents".to_string()
+            })
+        );
+
+        let data = mail();
+        assert!(matches!(
+            data.encode_value("uint8", &serde_json::json!(256)),
+            Err(TypedDataError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            data.encode_value("Unknown", &serde_json::json!(1)),
+            Err(TypedDataError::UnknownType(_))
+        ));
+        // int8 的 -1 按 256 位补码编码
+        assert_eq!(
+            data.encode_value("int8", &serde_json::json!(-1)).unwrap(),
+            H256::repeat_byte(0xff)
+        );
+    }
+}
@@ -0,0 +1,192 @@
+//! 以太币单位类型 - 避免费用计算中混用 Wei/Gwei
+//!
+//! 参考标准：
+//! - 1 Gwei = 10^9 Wei
+//! - 1 ETH  = 10^18 Wei
+//!
+//! 链上与 JSON-RPC 中的数值一律以 Wei 表示，`Gwei` 仅用于常量和展示。
+
+use ethereum_types::U256;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+
+/// 每 Gwei 的 Wei 数
+pub const WEI_PER_GWEI: u64 = 1_000_000_000;
+/// 每 ETH 的 Wei 数
+pub const WEI_PER_ETHER: u64 = 1_000_000_000_000_000_000;
+
+/// Wei 金额
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Wei(pub U256);
+
+/// Gwei 金额
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Gwei(pub U256);
+
+impl Wei {
+    /// 由 u64 构造（可用于常量）
+    pub const fn new(wei: u64) -> Self {
+        Self(U256([wei, 0, 0, 0]))
+    }
+
+    /// 零值
+    pub const fn zero() -> Self {
+        Self::new(0)
+    }
+
+    /// 底层 U256 数值
+    pub fn as_u256(self) -> U256 {
+        self.0
+    }
+
+    /// 换算为 Gwei（向下取整）
+    pub fn to_gwei(self) -> Gwei {
+        Gwei(self.0 / U256::from(WEI_PER_GWEI))
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+
+    pub fn checked_mul(self, rhs: u64) -> Option<Self> {
+        self.0.checked_mul(U256::from(rhs)).map(Self)
+    }
+
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl Gwei {
+    /// 由 u64 构造（可用于常量）
+    pub const fn new(gwei: u64) -> Self {
+        Self(U256([gwei, 0, 0, 0]))
+    }
+
+    /// 换算为 Wei，溢出时返回 None
+    pub fn to_wei(self) -> Option<Wei> {
+        self.0.checked_mul(U256::from(WEI_PER_GWEI)).map(Wei)
+    }
+}
+
+impl From<U256> for Wei {
+    fn from(value: U256) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Wei> for U256 {
+    fn from(value: Wei) -> Self {
+        value.0
+    }
+}
+
+impl Add for Wei {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Wei {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl Mul<u64> for Wei {
+    type Output = Self;
+
+    fn mul(self, rhs: u64) -> Self {
+        Self(self.0 * U256::from(rhs))
+    }
+}
+
+impl Div<u64> for Wei {
+    type Output = Self;
+
+    fn div(self, rhs: u64) -> Self {
+        Self(self.0 / U256::from(rhs))
+    }
+}
+
+/// 将 value / unit 格式化为十进制小数，去掉末尾的 0
+fn format_decimal(value: U256, unit: u64, decimals: usize) -> String {
+    let unit = U256::from(unit);
+    let integer = value / unit;
+    let fraction = value % unit;
+    if fraction.is_zero() {
+        return integer.to_string();
+    }
+    let fraction = format!("{:0>width$}", fraction.to_string(), width = decimals);
+    format!("{}.{}", integer, fraction.trim_end_matches('0'))
+}
+
+/// 按数量级选择单位展示，如 1_500_000_000 Wei 显示为 "1.5 Gwei"
+impl fmt::Display for Wei {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.0 >= U256::from(WEI_PER_ETHER) {
+            write!(f, "{} ETH", format_decimal(self.0, WEI_PER_ETHER, 18))
+        } else if self.0 >= U256::from(WEI_PER_GWEI) {
+            write!(f, "{} Gwei", format_decimal(self.0, WEI_PER_GWEI, 9))
+        } else {
+            write!(f, "{} Wei", self.0)
+        }
+    }
+}
+
+impl fmt::Display for Gwei {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} Gwei", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gwei_to_wei_round_trip() {
+        let gwei = Gwei::new(42);
+        let wei = gwei.to_wei().unwrap();
+        assert_eq!(wei, Wei::new(42 * WEI_PER_GWEI));
+        assert_eq!(wei.to_gwei(), gwei);
+
+        // 不足 1 Gwei 的部分向下取整
+        assert_eq!((wei + Wei::new(1)).to_gwei(), gwei);
+    }
+
+    #[test]
+    fn test_gwei_to_wei_overflow() {
+        assert_eq!(Gwei(U256::MAX).to_wei(), None);
+        assert!(Gwei(U256::MAX / U256::from(WEI_PER_GWEI)).to_wei().is_some());
+    }
+
+    #[test]
+    fn test_checked_arithmetic() {
+        let max = Wei(U256::MAX);
+        assert_eq!(max.checked_add(Wei::new(1)), None);
+        assert_eq!(Wei::zero().checked_sub(Wei::new(1)), None);
+        assert_eq!(max.checked_mul(2), None);
+        assert_eq!(Wei::new(3).checked_mul(7), Some(Wei::new(21)));
+        assert_eq!(Wei::new(1).saturating_sub(Wei::new(2)), Wei::zero());
+        assert_eq!(Wei::new(10) / 4, Wei::new(2));
+    }
+
+    #[test]
+    fn test_display_human_units() {
+        assert_eq!(Wei::new(1_500_000_000).to_string(), "1.5 Gwei");
+        assert_eq!(Wei::new(1_000_000_000).to_string(), "1 Gwei");
+        assert_eq!(Wei::new(1_000_000_001).to_string(), "1.000000001 Gwei");
+        assert_eq!(Wei::new(2_250_000_000_000_000_000).to_string(), "2.25 ETH");
+        assert_eq!(Wei::new(999).to_string(), "999 Wei");
+        assert_eq!(Gwei::new(2).to_string(), "2 Gwei");
+    }
+}
@@ -0,0 +1,70 @@
+//! 客户端版本信息
+//!
+//! 所有对外报告版本的接口（web3_clientVersion 等）统一取自 `VersionInfo`，
+//! 格式为 `rusteth/v{crate 版本}/{os}-{arch}/rustc{编译器版本}`，
+//! 例如 `rusteth/v0.1.0/linux-x86_64/rustc1.80.0`。
+
+use std::fmt;
+
+/// 客户端版本信息（编译期确定）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VersionInfo {
+    /// 客户端名称
+    pub name: &'static str,
+    /// crate 版本（CARGO_PKG_VERSION）
+    pub version: &'static str,
+    /// 目标操作系统
+    pub os: &'static str,
+    /// 目标架构
+    pub arch: &'static str,
+    /// 编译器版本（由构建脚本写入）
+    pub rustc: &'static str,
+}
+
+impl VersionInfo {
+    /// 当前构建的版本信息
+    pub const fn current() -> Self {
+        Self {
+            name: "rusteth",
+            version: env!("CARGO_PKG_VERSION"),
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+            rustc: env!("RUSTETH_RUSTC_VERSION"),
+        }
+    }
+}
+
+impl fmt::Display for VersionInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/v{}/{}-{}/rustc{}",
+            self.name, self.version, self.os, self.arch, self.rustc
+        )
+    }
+}
+
+/// 客户端版本字符串
+pub fn version() -> String {
+    VersionInfo::current().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_string_format() {
+        let info = VersionInfo::current();
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+
+        let version = version();
+        let parts: Vec<&str> = version.split('/').collect();
+        assert_eq!(parts.len(), 4, "{}", version);
+        assert_eq!(parts[0], "rusteth");
+        assert_eq!(parts[1], format!("v{}", env!("CARGO_PKG_VERSION")));
+        assert_eq!(parts[2], format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH));
+        let rustc = parts[3].strip_prefix("rustc").expect("rustc 前缀");
+        assert!(rustc.starts_with(|c: char| c.is_ascii_digit()), "{}", version);
+    }
+}
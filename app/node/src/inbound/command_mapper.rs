@@ -7,14 +7,17 @@
 //! - 两层之间通过 Command 进行解耦
 
 use crate::domain::command_types::EthCommand;
-use crate::domain::command_types::{BlockId, BlockTag, CallRequest, FilterOptions, SendTransactionRequest};
+use crate::domain::command_types::{
+    BlockId, CallRequest, FilterOptions, SendTransactionRequest, StateOverride,
+};
+use crate::domain::typed_data::TypedData;
 use ethereum_types::{Address, H256, U256, U64};
 use thiserror::Error;
 
 /// Command 映射错误
 #[derive(Debug, Error)]
 pub enum CommandMapperError {
-    /// JSON 解析错误
+    /// 参数反序列化失败（客户端输入错误，如数值超出 U64/U256）
     #[error("JSON 解析错误: {0}")]
     JsonError(#[from] serde_json::Error),
 
@@ -30,6 +33,9 @@ pub enum CommandMapperError {
 /// JSON-RPC 请求转 Domain Command 映射器
 pub struct CommandMapper;
 
+/// eth_getBalances 单次请求的地址数量上限
+pub const MAX_BALANCES_PER_REQUEST: usize = 1000;
+
 impl CommandMapper {
     /// 将 JSON-RPC 方法和参数转换为领域 Command
     ///
@@ -75,6 +81,19 @@ impl CommandMapper {
                 Ok(EthCommand::GetBalance(params.0, params.1))
             }
 
+            // 非标准扩展：批量查询余额
+            "eth_getBalances" => {
+                let params: (Vec<Address>, BlockId) = serde_json::from_value(params)?;
+                if params.0.len() > MAX_BALANCES_PER_REQUEST {
+                    return Err(CommandMapperError::InvalidParams(format!(
+                        "地址数量 {} 超过上限 {}",
+                        params.0.len(),
+                        MAX_BALANCES_PER_REQUEST
+                    )));
+                }
+                Ok(EthCommand::GetBalances(params.0, params.1))
+            }
+
             "eth_getStorageAt" => {
                 let params: (Address, U256, BlockId) = serde_json::from_value(params)?;
                 Ok(EthCommand::GetStorageAt(params.0, params.1, params.2))
@@ -90,10 +109,31 @@ impl CommandMapper {
                 Ok(EthCommand::GetCode(params.0, params.1))
             }
 
+            "eth_accounts" => Ok(EthCommand::GetAccounts),
+
             // 合约调用方法
             "eth_call" => {
-                let params: (CallRequest, BlockId) = serde_json::from_value(params)?;
-                Ok(EthCommand::Call(params.0, params.1))
+                // 第三个参数 stateOverride 可选
+                if params.as_array().is_some_and(|p| p.len() > 2) {
+                    let params: (CallRequest, BlockId, Option<StateOverride>) =
+                        serde_json::from_value(params)?;
+                    Ok(EthCommand::Call(params.0, params.1, params.2))
+                } else {
+                    let params: (CallRequest, BlockId) = serde_json::from_value(params)?;
+                    Ok(EthCommand::Call(params.0, params.1, None))
+                }
+            }
+
+            "eth_callBundle" => {
+                // 第三个参数 isolated 可选，默认后续调用可见前序调用的状态修改
+                let params: (Vec<CallRequest>, BlockId, Option<bool>) =
+                    if params.as_array().is_some_and(|p| p.len() > 2) {
+                        serde_json::from_value(params)?
+                    } else {
+                        let params: (Vec<CallRequest>, BlockId) = serde_json::from_value(params)?;
+                        (params.0, params.1, None)
+                    };
+                Ok(EthCommand::CallBundle(params.0, params.1, params.2.unwrap_or(false)))
             }
 
             "eth_estimateGas" => {
@@ -115,6 +155,10 @@ impl CommandMapper {
 
             "web3_clientVersion" => Ok(EthCommand::GetClientVersion),
 
+            "eth_protocolVersion" => Ok(EthCommand::GetProtocolVersion),
+
+            "eth_hashrate" => Ok(EthCommand::GetHashrate),
+
             // EIP-1559 交易方法
             "eth_sendTransaction" => {
                 let params: (SendTransactionRequest,) = serde_json::from_value(params)?;
@@ -129,10 +173,17 @@ impl CommandMapper {
                     CommandMapperError::InvalidParams(format!("无效的十六进制数据: {}", e))
                 })?;
 
-                // 签名恢复 - TODO: 实现真实的签名恢复
-                let sender = Address::from_low_u64_be(0x9999); // Mock sender
+                Ok(EthCommand::SendRawTransaction(raw_tx))
+            }
 
-                Ok(EthCommand::SendRawTransaction(raw_tx, sender))
+            "eth_signTypedData_v4" => {
+                let params: (Address, serde_json::Value) = serde_json::from_value(params)?;
+                // 钱包通常以 JSON 字符串传递结构化数据，也兼容直接传对象
+                let typed_data: TypedData = match params.1 {
+                    serde_json::Value::String(json) => serde_json::from_str(&json)?,
+                    value => serde_json::from_value(value)?,
+                };
+                Ok(EthCommand::SignTypedData(params.0, typed_data))
             }
 
             "eth_feeHistory" => {
@@ -141,9 +192,21 @@ impl CommandMapper {
             }
 
             "eth_maxPriorityFeePerGas" => Ok(EthCommand::GetMaxPriorityFeePerGas),
+            "eth_blobBaseFee" => Ok(EthCommand::GetBlobBaseFee),
+
+            // 调试方法
+            "debug_getRawTransaction" => {
+                let params: (H256,) = serde_json::from_value(params)?;
+                Ok(EthCommand::GetRawTransaction(params.0))
+            }
+
+            "debug_getRawReceipts" => {
+                let params: (BlockId,) = serde_json::from_value(params)?;
+                Ok(EthCommand::GetRawReceipts(params.0))
+            }
 
-            // 不支持的方法
-            _ => Err(CommandMapperError::UnsupportedMethod(method.to_string())),
+            // 非内置方法交给分发器查找自定义处理器，未注册时返回方法未找到
+            _ => Ok(EthCommand::Custom(method.to_string(), params)),
         }
     }
 }
@@ -168,12 +231,59 @@ mod tests {
     }
 
     #[test]
-    fn test_unsupported_method() {
-        let result = CommandMapper::map_to_command("unsupported_method", serde_json::json!([]));
-        assert!(result.is_err());
+    fn test_map_eth_call_with_state_override() {
+        let to = "0x0000000000000000000000000000000000000001";
+        let params = serde_json::json!([
+            {"to": to},
+            "latest",
+            {to: {"balance": "0x10", "code": "0x6000", "stateDiff": {
+                "0x0000000000000000000000000000000000000000000000000000000000000001":
+                "0x0000000000000000000000000000000000000000000000000000000000000002"
+            }}}
+        ]);
+        let command = CommandMapper::map_to_command("eth_call", params).unwrap();
+        let EthCommand::Call(_, _, Some(overrides)) = command else {
+            panic!("应解析出状态覆盖");
+        };
+        let account = &overrides[&to.parse::<Address>().unwrap()];
+        assert_eq!(account.balance, Some(U256::from(16)));
+        assert_eq!(account.code, Some(vec![0x60, 0x00]));
+        assert_eq!(account.state_diff.as_ref().unwrap().len(), 1);
+
+        // 不带第三个参数
+        let params = serde_json::json!([{"to": to}, "latest"]);
+        let command = CommandMapper::map_to_command("eth_call", params).unwrap();
+        assert!(matches!(command, EthCommand::Call(_, _, None)));
+    }
+
+    #[test]
+    fn test_map_sign_typed_data_from_json_string() {
+        // 钱包以 JSON 字符串传递结构化数据
+        let typed_data = serde_json::json!({
+            "types": { "EIP712Domain": [{ "name": "name", "type": "string" }] },
+            "primaryType": "EIP712Domain",
+            "domain": { "name": "test" },
+            "message": {}
+        });
+        let params = serde_json::json!([
+            "0x0000000000000000000000000000000000000001",
+            typed_data.to_string()
+        ]);
+
+        let result = CommandMapper::map_to_command("eth_signTypedData_v4", params).unwrap();
+        assert!(matches!(
+            result,
+            EthCommand::SignTypedData(_, data) if data.primary_type == "EIP712Domain"
+        ));
+    }
+
+    #[test]
+    fn test_unknown_method_maps_to_custom() {
+        let result = CommandMapper::map_to_command("unsupported_method", serde_json::json!([1]));
         assert!(matches!(
-            result.unwrap_err(),
-            CommandMapperError::UnsupportedMethod(_)
+            result.unwrap(),
+            EthCommand::Custom(method, params)
+                if method == "unsupported_method" && params == serde_json::json!([1])
         ));
     }
 }
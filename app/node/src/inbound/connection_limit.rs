@@ -0,0 +1,133 @@
+//! 按连接限制并发请求数
+//!
+//! HTTP/2 允许单个连接上并发大量请求，慢客户端持续灌入请求会占满运行时、饿死其他连接。
+//! 每个连接持有独立的信号量，在途请求达到上限后按配置排队等待或直接返回 429。
+//! 连接以对端地址（IP + 端口）区分，连接上没有在途请求时释放其信号量。
+
+use crate::domain::rpc_config::ConnectionOverflow;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// 按连接的并发请求限制器
+pub struct ConnectionLimiter {
+    max_in_flight: usize,
+    overflow: ConnectionOverflow,
+    connections: Mutex<HashMap<SocketAddr, Arc<Semaphore>>>,
+}
+
+impl ConnectionLimiter {
+    pub fn new(max_in_flight: usize, overflow: ConnectionOverflow) -> Self {
+        Self {
+            max_in_flight,
+            overflow,
+            connections: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 为 `peer` 上的一个请求获取许可
+    ///
+    /// `Reject` 模式下连接已满时返回 None；`Queue` 模式下等待直到有请求完成
+    pub async fn acquire(self: &Arc<Self>, peer: SocketAddr) -> Option<ConnectionPermit> {
+        // 在锁内克隆信号量，释放时据此判断是否还有请求在使用
+        let semaphore = self
+            .connections
+            .lock()
+            .unwrap()
+            .entry(peer)
+            .or_insert_with(|| Arc::new(Semaphore::new(self.max_in_flight)))
+            .clone();
+
+        let permit = match self.overflow {
+            ConnectionOverflow::Reject => semaphore.try_acquire_owned().ok(),
+            ConnectionOverflow::Queue => semaphore.acquire_owned().await.ok(),
+        };
+        let Some(permit) = permit else {
+            self.release_idle(peer);
+            return None;
+        };
+
+        Some(ConnectionPermit {
+            permit: Some(permit),
+            peer,
+            limiter: self.clone(),
+        })
+    }
+
+    /// 当前有在途请求的连接数
+    pub fn active_connections(&self) -> usize {
+        self.connections.lock().unwrap().len()
+    }
+
+    /// 连接上已无请求持有或等待信号量时将其移除
+    fn release_idle(&self, peer: SocketAddr) {
+        let mut connections = self.connections.lock().unwrap();
+        if connections
+            .get(&peer)
+            .is_some_and(|semaphore| Arc::strong_count(semaphore) == 1)
+        {
+            connections.remove(&peer);
+        }
+    }
+}
+
+/// 请求许可，随请求处理结束释放
+pub struct ConnectionPermit {
+    permit: Option<OwnedSemaphorePermit>,
+    peer: SocketAddr,
+    limiter: Arc<ConnectionLimiter>,
+}
+
+impl Drop for ConnectionPermit {
+    fn drop(&mut self) {
+        // 先归还许可（同时释放其持有的信号量引用），再检查连接是否空闲
+        self.permit.take();
+        self.limiter.release_idle(self.peer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peer(port: u16) -> SocketAddr {
+        SocketAddr::from(([127, 0, 0, 1], port))
+    }
+
+    #[tokio::test]
+    async fn test_limit_is_per_connection() {
+        let limiter = Arc::new(ConnectionLimiter::new(2, ConnectionOverflow::Reject));
+
+        let first = limiter.acquire(peer(1)).await.unwrap();
+        let second = limiter.acquire(peer(1)).await.unwrap();
+        assert!(limiter.acquire(peer(1)).await.is_none());
+
+        // 其他连接不受影响
+        let other = limiter.acquire(peer(2)).await.unwrap();
+        assert_eq!(limiter.active_connections(), 2);
+
+        // 请求完成后恢复额度，全部完成后释放连接
+        drop(first);
+        let third = limiter.acquire(peer(1)).await.unwrap();
+        drop((second, third, other));
+        assert_eq!(limiter.active_connections(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_queue_waits_for_permit() {
+        let limiter = Arc::new(ConnectionLimiter::new(1, ConnectionOverflow::Queue));
+        let held = limiter.acquire(peer(1)).await.unwrap();
+
+        let waiter = tokio::spawn({
+            let limiter = limiter.clone();
+            async move { limiter.acquire(peer(1)).await.is_some() }
+        });
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+
+        drop(held);
+        assert!(waiter.await.unwrap());
+        assert_eq!(limiter.active_connections(), 0);
+    }
+}
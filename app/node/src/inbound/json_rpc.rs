@@ -3,12 +3,22 @@
 //! 本模块根据 EIP-1474 EIP-1559 规范实现以太坊 JSON-RPC 2.0 接口。
 //! 架构遵循整洁架构（Clean Architecture）原则，明确分离各层职责。
 
-use crate::domain::command_types::CommandError;
+use crate::domain::command_types::{CommandError, CommandResult};
+use crate::domain::rpc_config::NamespaceConfig;
 use crate::inbound::command_mapper::{CommandMapper, CommandMapperError};
-use crate::inbound::json_types::{error_codes, JsonRpcError, JsonRpcRequest, JsonRpcResponse};
-use crate::inbound::result_mapper::{ResultMapper, ResultMapperError};
+use crate::inbound::json_types::{
+    error_codes, JsonRpcError, JsonRpcRequest, JsonRpcResponse, RequestId,
+};
+use crate::inbound::result_mapper::{JsonResult, ResultMapperError};
+use crate::inbound::rpc_stats::RpcStats;
 use crate::service::command_dispatcher::CommandDispatcher;
 use crate::service::ethereum_service_trait::EthereumService;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 默认单个批量请求的最大条数
+pub const DEFAULT_MAX_BATCH_SIZE: usize = 100;
 
 // ============================================================================
 // 用例层 - JSON-RPC 方法处理器
@@ -24,6 +34,14 @@ use crate::service::ethereum_service_trait::EthereumService;
 #[derive(Clone)]
 pub struct EthJsonRpcHandler<S: EthereumService> {
     dispatcher: CommandDispatcher<S>,
+    /// 慢调用告警阈值（None 表示关闭）
+    slow_call_threshold: Option<Duration>,
+    /// 单个批量请求的最大条数，防止一次请求放大负载
+    max_batch_size: usize,
+    /// 按方法的延迟统计（debug_rpcStats），克隆的处理器共享同一份
+    stats: Arc<RpcStats>,
+    /// 开放的命名空间，关闭的命名空间按方法未找到处理
+    namespaces: NamespaceConfig,
     // TODO: 增加 command_repo 用于命令持久化/审计/溯源
     // command_repo: Arc<dyn CommandRepository>,
 }
@@ -32,10 +50,32 @@ impl<S: EthereumService> EthJsonRpcHandler<S> {
     pub fn new(dispatcher: CommandDispatcher<S>) -> Self {
         Self {
             dispatcher,
+            slow_call_threshold: None,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            stats: Arc::new(RpcStats::new()),
+            namespaces: NamespaceConfig::default(),
             // TODO: 传入 command_repo 参数
         }
     }
 
+    /// 启用慢调用日志：耗时达到阈值的请求输出 warn 日志（方法名、耗时、请求 id），不影响响应
+    pub fn with_slow_call_threshold(mut self, threshold: Duration) -> Self {
+        self.slow_call_threshold = Some(threshold);
+        self
+    }
+
+    /// 设置批量请求的最大条数（默认 `DEFAULT_MAX_BATCH_SIZE`）
+    pub fn with_max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = max_batch_size;
+        self
+    }
+
+    /// 只开放指定的命名空间（默认全部开放）
+    pub fn with_namespaces(mut self, namespaces: NamespaceConfig) -> Self {
+        self.namespaces = namespaces;
+        self
+    }
+
     /// JSON-RPC 请求主分发方法（CQRS 模式）
     ///
     /// # 处理流程
@@ -53,29 +93,9 @@ impl<S: EthereumService> EthJsonRpcHandler<S> {
     pub async fn handle(&self, request: JsonRpcRequest) -> JsonRpcResponse {
         let id = request.id.clone();
 
-        // Step 1: 将 JSON-RPC request 转换为领域 Command
-        let command = match CommandMapper::map_to_command(&request.method, request.params) {
-            Ok(cmd) => cmd,
-            Err(err) => {
-                return JsonRpcResponse::Error {
-                    jsonrpc: "2.0".to_string(),
-                    error: Self::map_mapper_error(err),
-                    id,
-                }
-            }
-        };
-
-        // TODO: 增加 commandRepo 用于命令持久化
-        // let commandRepo = self.command_repo.clone();
-        // commandRepo.save(&command).await?;
-
-        // Step 2: 通过 Dispatcher 处理命令（Erlang 风格的 ask）
-        // Dispatcher 内部会动态查找并分发命令到具体的 Handler
-        let result = self.dispatcher.ask(command).await;
-
         // Step 3: 将 CommandResult 转换为 JSON Response
-        match result {
-            Ok(command_result) => match ResultMapper::map_to_json(command_result) {
+        match self.execute(request).await {
+            Ok(command_result) => match serde_json::Value::try_from(command_result) {
                 Ok(json_value) => JsonRpcResponse::Success {
                     jsonrpc: "2.0".to_string(),
                     result: json_value,
@@ -87,14 +107,184 @@ impl<S: EthereumService> EthJsonRpcHandler<S> {
                     id,
                 },
             },
-            Err(err) => JsonRpcResponse::Error {
+            Err(error) => JsonRpcResponse::Error {
                 jsonrpc: "2.0".to_string(),
-                error: Self::map_command_error(err),
+                error,
                 id,
             },
         }
     }
 
+    /// 处理请求并直接序列化为响应字节
+    ///
+    /// 与 `handle` 输出相同的 JSON，但结果经 `JsonResult` 直接写入响应信封，
+    /// 不构造中间 `serde_json::Value`，大响应（完整区块、eth_getLogs）少一次分配和拷贝。
+    pub async fn handle_to_bytes(&self, request: JsonRpcRequest) -> Vec<u8> {
+        let id = request.id.clone();
+
+        let error = match self.execute(request).await {
+            Ok(command_result) => {
+                let envelope = SuccessEnvelope {
+                    jsonrpc: "2.0",
+                    result: JsonResult(&command_result),
+                    id: &id,
+                };
+                match serde_json::to_vec(&envelope) {
+                    Ok(bytes) => return bytes,
+                    Err(err) => Self::map_result_error(err.into()),
+                }
+            }
+            Err(error) => error,
+        };
+        Self::error_to_bytes(error, id)
+    }
+
+    /// 处理原始请求体并序列化为响应字节
+    ///
+    /// 请求体不是合法 JSON 时返回 -32700，是 JSON 但不构成请求对象时返回 -32600。
+    /// 解析失败无从得知请求 id，按 JSON-RPC 2.0 规范回显 null；
+    /// 请求对象不合法但 id 字段可识别时仍回显原 id（含字符串形式）。
+    /// 顶层为数组时按批量请求处理，见 `handle_batch`
+    pub async fn handle_body(&self, body: &[u8]) -> Vec<u8> {
+        let parse_error = match serde_json::from_slice::<JsonRpcRequest>(body) {
+            Ok(request) => return self.handle_to_bytes(request).await,
+            Err(err) => err,
+        };
+
+        match serde_json::from_slice::<serde_json::Value>(body) {
+            Err(err) => Self::error_to_bytes(
+                JsonRpcError {
+                    code: error_codes::PARSE_ERROR,
+                    message: format!("解析错误: {}", err),
+                    data: None,
+                },
+                RequestId::Null,
+            ),
+            Ok(serde_json::Value::Array(items)) => self.handle_batch(items).await,
+            Ok(value) => Self::invalid_request_to_bytes(&value, parse_error),
+        }
+    }
+
+    /// 处理批量请求，响应数组与请求数组一一对应
+    ///
+    /// 空数组或条数超过 `max_batch_size` 时整体返回单个 -32600 错误（id 为 null）；
+    /// 各条请求并发执行，单条不合法只影响对应位置的响应
+    pub async fn handle_batch(&self, items: Vec<serde_json::Value>) -> Vec<u8> {
+        let message = if items.is_empty() {
+            Some("empty batch".to_string())
+        } else if items.len() > self.max_batch_size {
+            Some("batch too large".to_string())
+        } else {
+            None
+        };
+        if let Some(message) = message {
+            let error = JsonRpcError {
+                code: error_codes::INVALID_REQUEST,
+                message,
+                data: None,
+            };
+            return Self::error_to_bytes(error, RequestId::Null);
+        }
+
+        let responses = futures::future::join_all(items.into_iter().map(|item| async move {
+            match JsonRpcRequest::deserialize(&item) {
+                Ok(request) => self.handle_to_bytes(request).await,
+                Err(err) => Self::invalid_request_to_bytes(&item, err),
+            }
+        }))
+        .await;
+
+        let len = responses.iter().map(|response| response.len() + 1).sum::<usize>() + 1;
+        let mut body = Vec::with_capacity(len);
+        body.push(b'[');
+        for (i, response) in responses.iter().enumerate() {
+            if i > 0 {
+                body.push(b',');
+            }
+            body.extend_from_slice(response);
+        }
+        body.push(b']');
+        body
+    }
+
+    /// 合法 JSON 但不构成请求对象：-32600，可识别的 id 字段原样回显
+    fn invalid_request_to_bytes(value: &serde_json::Value, err: serde_json::Error) -> Vec<u8> {
+        let error = JsonRpcError {
+            code: error_codes::INVALID_REQUEST,
+            message: format!("无效请求: {}", err),
+            data: None,
+        };
+        let id = value
+            .get("id")
+            .and_then(|id| RequestId::deserialize(id).ok())
+            .unwrap_or(RequestId::Null);
+        Self::error_to_bytes(error, id)
+    }
+
+    fn error_to_bytes(error: JsonRpcError, id: RequestId) -> Vec<u8> {
+        let response = JsonRpcResponse::Error {
+            jsonrpc: "2.0".to_string(),
+            error,
+            id,
+        };
+        serde_json::to_vec(&response).expect("JSON-RPC 错误响应可序列化")
+    }
+
+    /// 执行请求（handle / handle_to_bytes 共用），记录耗时并按配置输出慢调用日志
+    async fn execute(&self, request: JsonRpcRequest) -> Result<CommandResult, JsonRpcError> {
+        let method = request.method.clone();
+        let id = request.id.clone();
+        let started = Instant::now();
+        let result = self.execute_command(request).await;
+        let elapsed = started.elapsed();
+
+        // 未知方法不计入统计，避免任意方法名撑大统计表
+        if !matches!(&result, Err(error) if error.code == error_codes::METHOD_NOT_FOUND) {
+            self.stats.record(&method, elapsed);
+        }
+
+        if self.slow_call_threshold.is_some_and(|threshold| elapsed >= threshold) {
+            tracing::warn!(
+                method = %method,
+                elapsed_ms = elapsed.as_millis() as u64,
+                id = ?id,
+                "慢 RPC 调用"
+            );
+        }
+        result
+    }
+
+    /// 将请求映射为命令并执行
+    async fn execute_command(&self, request: JsonRpcRequest) -> Result<CommandResult, JsonRpcError> {
+        if !self.namespaces.allows(&request.method) {
+            return Err(Self::map_mapper_error(CommandMapperError::UnsupportedMethod(
+                request.method,
+            )));
+        }
+
+        // 延迟统计属于接口层自身状态，不经过领域命令
+        if request.method == "debug_rpcStats" {
+            let snapshot = serde_json::to_value(self.stats.snapshot())
+                .map_err(|err| Self::map_result_error(err.into()))?;
+            return Ok(CommandResult::Json(snapshot));
+        }
+
+        // Step 1: 将 JSON-RPC request 转换为领域 Command
+        let command = CommandMapper::map_to_command(&request.method, request.params)
+            .map_err(Self::map_mapper_error)?;
+
+        // TODO: 增加 commandRepo 用于命令持久化
+        // let commandRepo = self.command_repo.clone();
+        // commandRepo.save(&command).await?;
+
+        // Step 2: 通过 Dispatcher 处理命令（Erlang 风格的 ask）
+        // Dispatcher 内部会动态查找并分发命令到具体的 Handler
+        self.dispatcher
+            .ask(command)
+            .await
+            .map_err(Self::map_command_error)
+    }
+
     /// 将 CommandMapperError 映射为 JSON-RPC 错误
     fn map_mapper_error(error: CommandMapperError) -> JsonRpcError {
         match error {
@@ -108,9 +298,10 @@ impl<S: EthereumService> EthJsonRpcHandler<S> {
                 message: format!("无效参数: {}", msg),
                 data: None,
             },
+            // 输入反序列化失败属于客户端错误：-32602，而非 -32603
             CommandMapperError::JsonError(err) => JsonRpcError {
                 code: error_codes::INVALID_PARAMS,
-                message: format!("JSON 解析错误: {}", err),
+                message: format!("无效参数: {}", err),
                 data: None,
             },
         }
@@ -160,10 +351,33 @@ impl<S: EthereumService> EthJsonRpcHandler<S> {
                 message: format!("超时: {}", msg),
                 data: None,
             },
+            CommandError::ExecutionReverted { message, data } => JsonRpcError {
+                code: error_codes::EXECUTION_REVERTED,
+                message,
+                data: Some(serde_json::Value::String(format!("0x{}", hex::encode(data)))),
+            },
+            CommandError::ExecutionFailed(msg) => JsonRpcError {
+                code: error_codes::SERVER_ERROR,
+                message: msg,
+                data: None,
+            },
+            CommandError::LimitExceeded(msg) => JsonRpcError {
+                code: error_codes::LIMIT_EXCEEDED,
+                message: msg,
+                data: None,
+            },
+            // EIP-1898 规定非主链区块返回 -32000 Invalid input
+            CommandError::InvalidInput(msg) => JsonRpcError {
+                code: error_codes::SERVER_ERROR,
+                message: format!("无效输入: {}", msg),
+                data: None,
+            },
         }
     }
 
     /// 将 ResultMapperError 映射为 JSON-RPC 错误
+    ///
+    /// 输出序列化失败是节点自身问题，返回 -32603
     fn map_result_error(error: ResultMapperError) -> JsonRpcError {
         match error {
             ResultMapperError::SerializationError(err) => JsonRpcError {
@@ -180,6 +394,14 @@ impl<S: EthereumService> EthJsonRpcHandler<S> {
     }
 }
 
+/// 成功响应信封（借用结果，直接序列化）
+#[derive(Serialize)]
+struct SuccessEnvelope<'a> {
+    jsonrpc: &'static str,
+    result: JsonResult<'a>,
+    id: &'a RequestId,
+}
+
 
 // ============================================================================
 // 单元测试
@@ -189,8 +411,8 @@ impl<S: EthereumService> EthJsonRpcHandler<S> {
 mod tests {
     use super::*;
     use crate::infrastructure::mock_repository::MockEthereumRepository;
-    use crate::inbound::json_types::RequestId;
     use crate::service::ethereum_service_impl::EthereumServiceImpl;
+    use ethereum_types::{Address, H256, U256, U64};
     use std::sync::Arc;
 
     #[tokio::test]
@@ -221,4 +443,287 @@ mod tests {
         let json = serde_json::to_string(&id_str).unwrap();
         assert_eq!(json, "\"test\"");
     }
+
+    #[tokio::test]
+    async fn test_error_paths_echo_request_id() {
+        let rpc_handler = handler();
+        let respond = |body: &'static str| {
+            let rpc_handler = rpc_handler.clone();
+            async move {
+                let bytes = rpc_handler.handle_body(body.as_bytes()).await;
+                serde_json::from_slice::<serde_json::Value>(&bytes).unwrap()
+            }
+        };
+
+        // 字符串 id：成功与方法错误都原样回显
+        let ok = respond(r#"{"jsonrpc":"2.0","method":"eth_blockNumber","params":[],"id":"abc"}"#).await;
+        assert_eq!(ok["id"], "abc");
+        assert!(ok.get("result").is_some());
+
+        let err = respond(r#"{"jsonrpc":"2.0","method":"eth_nope","params":[],"id":"abc"}"#).await;
+        assert_eq!(err["id"], "abc");
+        assert_eq!(err["error"]["code"], error_codes::METHOD_NOT_FOUND);
+
+        // 无法解析的 JSON：id 为 null
+        let err = respond(r#"{"jsonrpc":"2.0","method""#).await;
+        assert!(err["id"].is_null());
+        assert_eq!(err["error"]["code"], error_codes::PARSE_ERROR);
+
+        // 合法 JSON 但不是请求对象：可识别的 id 仍回显
+        let err = respond(r#"{"jsonrpc":"2.0","params":[],"id":"abc"}"#).await;
+        assert_eq!(err["id"], "abc");
+        assert_eq!(err["error"]["code"], error_codes::INVALID_REQUEST);
+
+        // 数组按批量请求处理，每个不合法元素各得一个 -32600
+        let errs = respond(r#"[1, 2]"#).await;
+        assert_eq!(errs.as_array().unwrap().len(), 2);
+        assert!(errs[0]["id"].is_null());
+        assert_eq!(errs[1]["error"]["code"], error_codes::INVALID_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_rpc_stats_reports_called_methods() {
+        let rpc_handler = handler();
+        let request = |method: &str| JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            method: method.to_string(),
+            params: serde_json::json!([]),
+            id: RequestId::Number(1),
+        };
+        for _ in 0..3 {
+            rpc_handler.handle(request("eth_blockNumber")).await;
+        }
+        rpc_handler.handle(request("eth_noSuchMethod")).await;
+
+        let JsonRpcResponse::Success { result, .. } = rpc_handler.handle(request("debug_rpcStats")).await
+        else {
+            panic!("debug_rpcStats 应成功");
+        };
+        assert_eq!(result["eth_blockNumber"]["count"], 3);
+        assert!(result["eth_blockNumber"]["p99Us"].as_u64().unwrap() > 0);
+        // 未知方法不计入
+        assert!(result.get("eth_noSuchMethod").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_disabled_namespace_is_method_not_found() {
+        use crate::domain::rpc_config::{Namespace, NamespaceConfig};
+
+        let rpc_handler =
+            handler().with_namespaces(NamespaceConfig::only([Namespace::Eth, Namespace::Net]));
+        let request = |method: &str| JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            method: method.to_string(),
+            params: serde_json::json!([]),
+            id: RequestId::Number(1),
+        };
+
+        assert!(matches!(
+            rpc_handler.handle(request("eth_blockNumber")).await,
+            JsonRpcResponse::Success { .. }
+        ));
+        assert!(matches!(
+            rpc_handler.handle(request("net_version")).await,
+            JsonRpcResponse::Success { .. }
+        ));
+        for method in ["debug_rpcStats", "web3_clientVersion"] {
+            match rpc_handler.handle(request(method)).await {
+                JsonRpcResponse::Error { error, .. } => {
+                    assert_eq!(error.code, error_codes::METHOD_NOT_FOUND, "{}", method);
+                }
+                other => panic!("{} 应被拒绝: {:?}", method, other),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_size_limit() {
+        use serde_json::json;
+
+        let rpc_handler = handler().with_max_batch_size(3);
+        let batch = |n: u64| {
+            let items: Vec<_> = (0..n)
+                .map(|id| json!({"jsonrpc": "2.0", "method": "eth_blockNumber", "params": [], "id": id}))
+                .collect();
+            serde_json::to_vec(&items).unwrap()
+        };
+
+        // 恰好达到上限：逐条响应，顺序与请求一致
+        let bytes = rpc_handler.handle_body(&batch(3)).await;
+        let responses: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        let responses = responses.as_array().unwrap();
+        assert_eq!(responses.len(), 3);
+        for (i, response) in responses.iter().enumerate() {
+            assert_eq!(response["id"], i);
+            assert!(response.get("result").is_some());
+        }
+
+        // 超过上限：单个错误对象
+        let bytes = rpc_handler.handle_body(&batch(4)).await;
+        let err: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(err["error"]["code"], error_codes::INVALID_REQUEST);
+        assert_eq!(err["error"]["message"], "batch too large");
+        assert!(err["id"].is_null());
+
+        let bytes = rpc_handler.handle_body(b"[]").await;
+        let err: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(err["error"]["code"], error_codes::INVALID_REQUEST);
+    }
+
+    #[test]
+    fn test_service_errors_map_to_rpc_codes() {
+        use crate::service::ethereum_service_trait::ServiceError;
+        use crate::service::repo::transaction_repo::TxPoolError;
+
+        let cases = [
+            (ServiceError::BlockNotFound, error_codes::SERVER_ERROR, "区块未找到"),
+            (ServiceError::InvalidParams("bad".into()), error_codes::INVALID_PARAMS, "bad"),
+            (ServiceError::ValidationError("nonce".into()), error_codes::INVALID_PARAMS, "nonce"),
+            (
+                ServiceError::ExecutionReverted { reason: Some("boom".into()), data: vec![] },
+                error_codes::EXECUTION_REVERTED,
+                "execution reverted: boom",
+            ),
+            (ServiceError::ExecutionFailed("oog".into()), error_codes::SERVER_ERROR, "oog"),
+            (ServiceError::RateLimited("slow".into()), error_codes::LIMIT_EXCEEDED, "slow"),
+            (ServiceError::CallLimitExceeded("gas".into()), error_codes::LIMIT_EXCEEDED, "gas"),
+            (
+                ServiceError::TooManyResults(10),
+                error_codes::LIMIT_EXCEEDED,
+                "query returned more than 10 results",
+            ),
+            (ServiceError::NonCanonicalBlock(H256::zero()), error_codes::SERVER_ERROR, "不在主链上"),
+            (ServiceError::InternalError("db".into()), error_codes::INTERNAL_ERROR, "db"),
+            // 交易池拒绝是客户端错误，容量不足是节点错误
+            (
+                TxPoolError::NonceGap { expected: 1, actual: 3 }.into(),
+                error_codes::INVALID_PARAMS,
+                "Nonce gap: expected 1, got 3",
+            ),
+            (
+                TxPoolError::PoolFull { current: 10, max: 10 }.into(),
+                error_codes::INTERNAL_ERROR,
+                "Transaction pool full: 10/10",
+            ),
+        ];
+
+        for (err, code, message) in cases {
+            let rpc = EthJsonRpcHandler::<EthereumServiceImpl>::map_command_error(err.clone().into());
+            assert_eq!(rpc.code, code, "{:?}", err);
+            assert!(rpc.message.contains(message), "{:?} -> {}", err, rpc.message);
+        }
+    }
+
+    fn handler() -> EthJsonRpcHandler<EthereumServiceImpl> {
+        let service = Arc::new(EthereumServiceImpl::new(MockEthereumRepository::new()));
+        EthJsonRpcHandler::new(CommandDispatcher::new(service))
+    }
+
+    #[tokio::test]
+    async fn test_handle_to_bytes_matches_value_path() {
+        let rpc_handler = handler();
+        let requests = [
+            ("eth_blockNumber", serde_json::json!([]), RequestId::Number(1)),
+            ("eth_getBlockByNumber", serde_json::json!(["0x0", true]), RequestId::String("blk".to_string())),
+            ("eth_getBlockByNumber", serde_json::json!(["0x64", false]), RequestId::Number(3)),
+            ("eth_getCode", serde_json::json!(["0x0000000000000000000000000000000000000000", "latest"]), RequestId::Null),
+            ("eth_noSuchMethod", serde_json::json!([]), RequestId::Number(5)),
+            ("eth_getBalance", serde_json::json!(["not-an-address"]), RequestId::Number(6)),
+        ];
+
+        for (method, params, id) in requests {
+            let request = JsonRpcRequest {
+                jsonrpc: "2.0".to_string(),
+                method: method.to_string(),
+                params,
+                id,
+            };
+
+            let expected = serde_json::to_value(rpc_handler.handle(request.clone()).await).unwrap();
+            let bytes = rpc_handler.handle_to_bytes(request).await;
+            let actual: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+            assert_eq!(actual, expected, "{} 两条序列化路径输出不一致", method);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_block_by_number_honors_full_tx() {
+        use crate::domain::command_types::Transaction;
+
+        let repo = MockEthereumRepository::new();
+        let tx_hash = H256::repeat_byte(0xab);
+        let mut block = repo.get_block_by_hash(&H256::zero()).unwrap();
+        block.number = U64::one();
+        block.hash = H256::from_low_u64_be(1);
+        block.transactions = vec![Transaction {
+            hash: tx_hash,
+            nonce: U256::zero(),
+            block_hash: Some(block.hash),
+            block_number: Some(block.number),
+            transaction_index: Some(U64::zero()),
+            from: Address::from_low_u64_be(1),
+            to: Some(Address::from_low_u64_be(2)),
+            value: U256::zero(),
+            gas_price: None,
+            gas: U256::from(21000),
+            input: vec![],
+            v: U64::zero(),
+            r: U256::one(),
+            s: U256::one(),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            transaction_type: None,
+        }]
+        .into();
+        repo.add_block(block);
+
+        let service = Arc::new(EthereumServiceImpl::new(repo));
+        let rpc_handler = EthJsonRpcHandler::new(CommandDispatcher::new(service));
+        let get_block = |full_tx: bool| JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            method: "eth_getBlockByNumber".to_string(),
+            params: serde_json::json!(["0x1", full_tx]),
+            id: RequestId::Number(1),
+        };
+
+        let JsonRpcResponse::Success { result, .. } = rpc_handler.handle(get_block(false)).await else {
+            panic!("eth_getBlockByNumber 应成功");
+        };
+        assert_eq!(result["transactions"], serde_json::json!([tx_hash]));
+
+        let JsonRpcResponse::Success { result, .. } = rpc_handler.handle(get_block(true)).await else {
+            panic!("eth_getBlockByNumber 应成功");
+        };
+        assert_eq!(result["transactions"][0]["hash"], serde_json::json!(tx_hash));
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_slow_call_logged_only_above_threshold() {
+        let request = || JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            method: "eth_blockNumber".to_string(),
+            params: serde_json::json!([]),
+            id: RequestId::Number(42),
+        };
+
+        // 阈值远大于调用耗时：不告警
+        let fast = handler().with_slow_call_threshold(Duration::from_secs(60));
+        assert!(matches!(fast.handle(request()).await, JsonRpcResponse::Success { .. }));
+        assert!(!logs_contain("慢 RPC 调用"));
+
+        // 零阈值：任何调用都视为慢调用，响应不受影响
+        let slow = handler().with_slow_call_threshold(Duration::ZERO);
+        assert!(matches!(slow.handle(request()).await, JsonRpcResponse::Success { .. }));
+        logs_assert(|lines: &[&str]| {
+            match lines.iter().find(|l| l.contains("慢 RPC 调用")) {
+                Some(line) if line.contains("WARN")
+                    && line.contains("method=eth_blockNumber")
+                    && line.contains("elapsed_ms=")
+                    && line.contains("Number(42)") => Ok(()),
+                Some(line) => Err(format!("慢调用日志字段不全: {}", line)),
+                None => Err("缺少慢调用日志".to_string()),
+            }
+        });
+    }
 }
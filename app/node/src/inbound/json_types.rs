@@ -60,6 +60,8 @@ pub mod error_codes {
     pub const INVALID_PARAMS: i32 = -32602; // 无效参数
     pub const INTERNAL_ERROR: i32 = -32603; // 内部错误
     pub const SERVER_ERROR: i32 = -32000; // 服务器错误
+    pub const LIMIT_EXCEEDED: i32 = -32005; // 超出请求限额（EIP-1474）
+    pub const EXECUTION_REVERTED: i32 = 3; // 执行回滚（geth 约定，data 为回滚数据）
 }
 
 // ============================================================================
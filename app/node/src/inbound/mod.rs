@@ -4,3 +4,5 @@ pub mod json_types;
 pub mod transaction_decoder;
 pub mod command_mapper;
 pub mod result_mapper;
+pub mod rpc_stats;
+pub mod connection_limit;
@@ -5,7 +5,9 @@
 //! - 接口层负责数据格式转换
 //! - 将领域对象序列化为 JSON
 
-use crate::domain::command_types::CommandResult;
+use crate::domain::command_types::{CommandResult, TransactionReceipt};
+use rlp::RlpStream;
+use serde::{Serialize, Serializer};
 use thiserror::Error;
 
 /// Result 映射错误
@@ -33,47 +35,126 @@ impl ResultMapper {
     /// - `Ok(serde_json::Value)`: 转换成功
     /// - `Err(ResultMapperError)`: 转换失败
     pub fn map_to_json(result: CommandResult) -> Result<serde_json::Value, ResultMapperError> {
-        match result {
+        Ok(serde_json::to_value(JsonResult(&result))?)
+    }
+}
+
+/// CommandResult 的 JSON 视图
+///
+/// 实现 `Serialize`，可直接写入响应信封（见 `EthJsonRpcHandler::handle_to_bytes`），
+/// 避免先构造中间 `serde_json::Value` 再二次序列化；`map_to_json` 也经由此视图，
+/// 两条路径输出一致。
+pub struct JsonResult<'a>(pub &'a CommandResult);
+
+impl Serialize for JsonResult<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
             // 基本类型结果
-            CommandResult::Unit => Ok(serde_json::json!(null)),
+            CommandResult::Unit => serializer.serialize_unit(),
 
-            CommandResult::Bool(b) => Ok(serde_json::to_value(b)?),
+            CommandResult::Bool(b) => b.serialize(serializer),
 
-            CommandResult::String(s) => Ok(serde_json::to_value(s)?),
+            CommandResult::String(s) => s.serialize(serializer),
 
-            CommandResult::Bytes(bytes) => {
-                // 字节数组转十六进制字符串
-                let hex_string = format!("0x{}", hex::encode(bytes));
-                Ok(serde_json::to_value(hex_string)?)
-            }
+            // 字节数组转十六进制字符串
+            CommandResult::Bytes(bytes) => to_hex(bytes).serialize(serializer),
 
             // 以太坊类型结果
-            CommandResult::U64(value) => Ok(serde_json::to_value(value)?),
+            CommandResult::U64(value) => value.serialize(serializer),
 
-            CommandResult::U256(value) => Ok(serde_json::to_value(value)?),
+            CommandResult::U256(value) => value.serialize(serializer),
 
-            CommandResult::Hash(hash) => Ok(serde_json::to_value(hash)?),
+            CommandResult::Hash(hash) => hash.serialize(serializer),
 
-            CommandResult::Address(address) => Ok(serde_json::to_value(address)?),
+            CommandResult::Address(address) => address.serialize(serializer),
+
+            CommandResult::Addresses(addresses) => addresses.serialize(serializer),
+
+            CommandResult::Balances(balances) => balances.serialize(serializer),
 
             // 复杂类型结果
-            CommandResult::Block(block) => Ok(serde_json::to_value(block)?),
+            CommandResult::Block(block) => block.serialize(serializer),
+
+            CommandResult::Transaction(tx) => tx.serialize(serializer),
+
+            CommandResult::TransactionReceipt(receipt) => receipt.serialize(serializer),
 
-            CommandResult::Transaction(tx) => Ok(serde_json::to_value(tx)?),
+            CommandResult::Logs(logs) => logs.serialize(serializer),
 
-            CommandResult::TransactionReceipt(receipt) => Ok(serde_json::to_value(receipt)?),
+            CommandResult::FeeHistory(fee_history) => fee_history.serialize(serializer),
 
-            CommandResult::Logs(logs) => Ok(serde_json::to_value(logs)?),
+            CommandResult::CallBundle(results) => results.serialize(serializer),
 
-            CommandResult::FeeHistory(fee_history) => Ok(serde_json::to_value(fee_history)?),
+            CommandResult::RawTransaction(tx) => tx
+                .as_ref()
+                .map(|tx| to_hex(&tx.encode_2718()))
+                .serialize(serializer),
+
+            CommandResult::RawReceipts(receipts) => receipts
+                .as_ref()
+                .map(|receipts| {
+                    receipts
+                        .iter()
+                        .map(|receipt| to_hex(&encode_receipt(receipt)))
+                        .collect::<Vec<_>>()
+                })
+                .serialize(serializer),
+
+            CommandResult::Json(value) => value.serialize(serializer),
         }
     }
 }
 
+fn to_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+/// 收据的 EIP-2718 编码（共识格式）
+///
+/// - Legacy: rlp([status, cumulative_gas_used, logs_bloom, logs])
+/// - 类型化交易: tx_type || rlp([...])
+/// - 日志: rlp([address, topics, data])
+fn encode_receipt(receipt: &TransactionReceipt) -> Vec<u8> {
+    let mut stream = RlpStream::new_list(4);
+    stream.append(&receipt.status);
+    stream.append(&receipt.cumulative_gas_used);
+    stream.append(&receipt.logs_bloom);
+    stream.begin_list(receipt.logs.len());
+    for log in &receipt.logs {
+        stream.begin_list(3);
+        stream.append(&log.address);
+        stream.append_list(&log.topics);
+        stream.append(&log.data);
+    }
+
+    let mut encoded = Vec::new();
+    match receipt.transaction_type.as_u64() {
+        0 => {}
+        tx_type => encoded.push(tx_type as u8),
+    }
+    encoded.extend_from_slice(&stream.out());
+    encoded
+}
+
+/// CommandResult → JSON Value 的标准转换
+///
+/// 所有 RPC 出口统一经由 `ResultMapper::map_to_json`，避免各处序列化结果不一致
+/// （例如 `Block(None)` 必须序列化为 `null`）
+impl TryFrom<CommandResult> for serde_json::Value {
+    type Error = ResultMapperError;
+
+    fn try_from(result: CommandResult) -> Result<Self, Self::Error> {
+        ResultMapper::map_to_json(result)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ethereum_types::U256;
+    use crate::domain::command_types::FeeHistory;
+    use crate::infrastructure::mock_repository::MockEthereumRepository;
+    use ethereum_types::{Address, H256, U256, U64};
+    use serde_json::Value;
 
     #[test]
     fn test_map_unit_result() {
@@ -102,4 +183,62 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), serde_json::json!("0x010203"));
     }
+
+    #[test]
+    fn test_try_from_basic_variants() {
+        assert_eq!(Value::try_from(CommandResult::Unit).unwrap(), Value::Null);
+        assert_eq!(Value::try_from(CommandResult::Bool(true)).unwrap(), serde_json::json!(true));
+        assert_eq!(
+            Value::try_from(CommandResult::String("rusteth".to_string())).unwrap(),
+            serde_json::json!("rusteth")
+        );
+        assert_eq!(Value::try_from(CommandResult::Bytes(vec![])).unwrap(), serde_json::json!("0x"));
+        assert_eq!(Value::try_from(CommandResult::U64(U64::from(16))).unwrap(), serde_json::json!("0x10"));
+        assert_eq!(
+            Value::try_from(CommandResult::U256(U256::from(12345))).unwrap(),
+            serde_json::json!("0x3039")
+        );
+        assert_eq!(
+            Value::try_from(CommandResult::Hash(H256::from_low_u64_be(1))).unwrap(),
+            serde_json::json!("0x0000000000000000000000000000000000000000000000000000000000000001")
+        );
+        assert_eq!(
+            Value::try_from(CommandResult::Address(Address::from_low_u64_be(1))).unwrap(),
+            serde_json::json!("0x0000000000000000000000000000000000000001")
+        );
+    }
+
+    #[test]
+    fn test_try_from_none_variants_are_null() {
+        assert_eq!(Value::try_from(CommandResult::Block(None)).unwrap(), Value::Null);
+        assert_eq!(Value::try_from(CommandResult::Transaction(None)).unwrap(), Value::Null);
+        assert_eq!(Value::try_from(CommandResult::TransactionReceipt(None)).unwrap(), Value::Null);
+    }
+
+    #[test]
+    fn test_try_from_complex_variants() {
+        let repo = MockEthereumRepository::new();
+        let genesis = repo.blocks.read().unwrap().get(&U64::zero()).cloned();
+        let block = Value::try_from(CommandResult::Block(genesis)).unwrap();
+        assert_eq!(block["number"], serde_json::json!("0x0"));
+        assert!(block["transactions"].is_array());
+
+        assert_eq!(Value::try_from(CommandResult::Logs(vec![])).unwrap(), serde_json::json!([]));
+
+        let fee_history = FeeHistory {
+            oldest_block: U64::from(1),
+            base_fee_per_gas: vec![U256::from(7), U256::from(8)],
+            gas_used_ratio: vec![0.5],
+            reward: None,
+        };
+        let json = Value::try_from(CommandResult::FeeHistory(fee_history)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "oldestBlock": "0x1",
+                "baseFeePerGas": ["0x7", "0x8"],
+                "gasUsedRatio": [0.5]
+            })
+        );
+    }
 }
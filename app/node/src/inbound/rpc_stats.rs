@@ -0,0 +1,148 @@
+//! RPC 延迟统计 - 按方法记录耗时并估算分位数
+//!
+//! 每个方法一个指数直方图：桶边界按 2^(1/4) 递增（约 19%），分位数取所在桶的上界，
+//! 误差不超过一个桶宽。内存占用与调用次数无关，供没有指标系统的运维通过
+//! `debug_rpcStats` 查看热点方法。
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// 每个 2 倍区间划分的桶数
+const BUCKETS_PER_DOUBLING: f64 = 4.0;
+
+/// 桶数量：覆盖 1µs 到 2^40µs（约 12 天）
+const BUCKET_COUNT: usize = 40 * BUCKETS_PER_DOUBLING as usize;
+
+/// 单个方法的延迟直方图（微秒）
+#[derive(Debug, Clone)]
+struct LatencyHistogram {
+    buckets: Vec<u64>,
+    count: u64,
+}
+
+impl LatencyHistogram {
+    fn new() -> Self {
+        Self {
+            buckets: vec![0; BUCKET_COUNT],
+            count: 0,
+        }
+    }
+
+    fn record(&mut self, elapsed: Duration) {
+        let micros = elapsed.as_micros().max(1) as f64;
+        let index = (micros.log2() * BUCKETS_PER_DOUBLING) as usize;
+        self.buckets[index.min(BUCKET_COUNT - 1)] += 1;
+        self.count += 1;
+    }
+
+    /// 分位数 `q`（0..=1）所在桶的上界（微秒）
+    fn percentile(&self, q: f64) -> u64 {
+        let target = ((self.count as f64) * q).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (index, &n) in self.buckets.iter().enumerate() {
+            seen += n;
+            if seen >= target {
+                return 2f64.powf((index + 1) as f64 / BUCKETS_PER_DOUBLING).round() as u64;
+            }
+        }
+        0
+    }
+}
+
+/// 单个方法的延迟摘要（debug_rpcStats 的响应项）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MethodLatency {
+    /// 调用次数
+    pub count: u64,
+    pub p50_us: u64,
+    pub p95_us: u64,
+    pub p99_us: u64,
+}
+
+/// 按方法汇总的 RPC 延迟统计
+#[derive(Debug, Default)]
+pub struct RpcStats {
+    methods: Mutex<HashMap<String, LatencyHistogram>>,
+}
+
+impl RpcStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录一次调用耗时
+    pub fn record(&self, method: &str, elapsed: Duration) {
+        let mut methods = self.methods.lock().unwrap();
+        match methods.get_mut(method) {
+            Some(histogram) => histogram.record(elapsed),
+            None => {
+                let mut histogram = LatencyHistogram::new();
+                histogram.record(elapsed);
+                methods.insert(method.to_string(), histogram);
+            }
+        }
+    }
+
+    /// 当前各方法的延迟摘要（按方法名排序）
+    pub fn snapshot(&self) -> BTreeMap<String, MethodLatency> {
+        self.methods
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(method, histogram)| {
+                let latency = MethodLatency {
+                    count: histogram.count,
+                    p50_us: histogram.percentile(0.50),
+                    p95_us: histogram.percentile(0.95),
+                    p99_us: histogram.percentile(0.99),
+                };
+                (method.clone(), latency)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 估算值落在真实值的一个桶宽内
+    fn assert_near(actual_us: u64, expected: Duration) {
+        let expected = expected.as_micros() as f64;
+        let ratio = actual_us as f64 / expected;
+        assert!(
+            (1.0..=1.2).contains(&ratio),
+            "估算 {}µs 与实际 {}µs 相差过大",
+            actual_us,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_percentiles_per_method() {
+        let stats = RpcStats::new();
+        // 90 次快调用、9 次中等、1 次慢调用
+        for _ in 0..90 {
+            stats.record("eth_call", Duration::from_micros(900));
+        }
+        for _ in 0..9 {
+            stats.record("eth_call", Duration::from_millis(20));
+        }
+        stats.record("eth_call", Duration::from_millis(500));
+        stats.record("eth_blockNumber", Duration::from_micros(50));
+
+        let snapshot = stats.snapshot();
+        let call = &snapshot["eth_call"];
+        assert_eq!(call.count, 100);
+        assert_near(call.p50_us, Duration::from_micros(900));
+        assert_near(call.p95_us, Duration::from_millis(20));
+        assert_near(call.p99_us, Duration::from_millis(20));
+
+        let block_number = &snapshot["eth_blockNumber"];
+        assert_eq!(block_number.count, 1);
+        assert_near(block_number.p99_us, Duration::from_micros(50));
+    }
+}
@@ -2,18 +2,25 @@
 //!
 //! 使用 Axum 构建的低延迟 HTTP 服务器，配置经过优化
 
+use crate::domain::rpc_config::ServerConfig;
+use crate::inbound::connection_limit::ConnectionLimiter;
 use crate::inbound::json_rpc::EthJsonRpcHandler;
-use crate::inbound::json_types::JsonRpcRequest;
+use crate::inbound::json_types::{error_codes, JsonRpcError, JsonRpcResponse, RequestId};
 use crate::service::ethereum_service_trait::EthereumService;
 use axum::{
-    extract::State,
-    http::{Method, StatusCode},
+    body::Bytes,
+    extract::{ConnectInfo, DefaultBodyLimit, Request, State},
+    http::{header, HeaderMap, Method, StatusCode},
+    middleware::{self, Next},
     response::{IntoResponse, Response},
     routing::post,
-    Json, Router,
+    Router,
 };
+use std::net::SocketAddr;
+use std::sync::Arc;
 use tower::ServiceBuilder;
 use tower_http::{
+    compression::{predicate::SizeAbove, CompressionLayer},
     cors::{Any, CorsLayer},
     trace::TraceLayer,
 };
@@ -23,13 +30,26 @@ use tracing::info;
 #[derive(Clone)]
 pub struct ServerState<S: EthereumService> {
     pub rpc_handler: EthJsonRpcHandler<S>,
+    /// 是否接受缺少 Content-Type 的请求
+    pub lenient_content_type: bool,
 }
 
-/// 创建并配置 HTTP 服务器
+/// 创建并配置 HTTP 服务器（默认配置）
 pub fn create_server<S: EthereumService + Clone + 'static>(
     rpc_handler: EthJsonRpcHandler<S>,
 ) -> Router {
-    let state = ServerState { rpc_handler };
+    create_server_with_config(rpc_handler, ServerConfig::default())
+}
+
+/// 使用指定配置创建 HTTP 服务器
+pub fn create_server_with_config<S: EthereumService + Clone + 'static>(
+    rpc_handler: EthJsonRpcHandler<S>,
+    config: ServerConfig,
+) -> Router {
+    let state = ServerState {
+        rpc_handler,
+        lenient_content_type: config.lenient_content_type,
+    };
 
     // 为以太坊客户端配置 CORS
     let cors = CorsLayer::new()
@@ -37,9 +57,28 @@ pub fn create_server<S: EthereumService + Clone + 'static>(
         .allow_methods([Method::POST, Method::OPTIONS])
         .allow_headers(Any);
 
-    Router::new()
-        .route("/", post(handle_rpc_request::<S>))
+    let mut rpc_route = post(handle_rpc_request::<S>);
+    if let Some(max_in_flight) = config.max_requests_per_connection {
+        let limiter = Arc::new(ConnectionLimiter::new(max_in_flight, config.connection_overflow));
+        rpc_route = rpc_route.layer(middleware::from_fn_with_state(limiter, limit_per_connection));
+    }
+
+    let mut router = Router::new()
+        .route("/", rpc_route)
         .route("/health", axum::routing::get(health_check))
+        .layer(DefaultBodyLimit::max(config.max_body_size));
+
+    // 响应压缩：eth_getLogs、完整区块等大响应节省带宽
+    if config.compression {
+        router = router.layer(
+            CompressionLayer::new()
+                .gzip(true)
+                .br(true)
+                .compress_when(SizeAbove::new(config.compression_min_size)),
+        );
+    }
+
+    router
         .layer(
             ServiceBuilder::new()
                 .layer(TraceLayer::new_for_http())
@@ -51,10 +90,84 @@ pub fn create_server<S: EthereumService + Clone + 'static>(
 /// RPC 请求主处理器
 async fn handle_rpc_request<S: EthereumService + Clone>(
     State(state): State<ServerState<S>>,
-    Json(request): Json<JsonRpcRequest>,
+    headers: HeaderMap,
+    body: Bytes,
 ) -> Response {
-    let response = state.rpc_handler.handle(request).await;
-    Json(response).into_response()
+    if !accepts_content_type(&headers, state.lenient_content_type) {
+        return unsupported_media_type();
+    }
+
+    // 请求体交给处理器解析，解析失败也返回 JSON-RPC 错误而非 HTTP 4xx；
+    // 直接序列化为字节，避免 CommandResult → Value → 字节 的二次序列化
+    let body = state.rpc_handler.handle_body(&body).await;
+    ([(header::CONTENT_TYPE, "application/json")], body).into_response()
+}
+
+/// 按连接限制在途请求；未提供对端地址（未使用 connect info 启动）时不限制
+async fn limit_per_connection(
+    State(limiter): State<Arc<ConnectionLimiter>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(ConnectInfo(peer)) = request.extensions().get::<ConnectInfo<SocketAddr>>().copied() else {
+        return next.run(request).await;
+    };
+    match limiter.acquire(peer).await {
+        Some(_permit) => next.run(request).await,
+        None => too_many_requests(),
+    }
+}
+
+/// 检查请求的 Content-Type 是否为 application/json（忽略 charset 等参数）
+fn accepts_content_type(headers: &HeaderMap, lenient: bool) -> bool {
+    match headers.get(header::CONTENT_TYPE) {
+        Some(value) => value
+            .to_str()
+            .ok()
+            .and_then(|value| value.split(';').next())
+            .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/json")),
+        None => lenient,
+    }
+}
+
+/// 415 响应，响应体为 id 为 null 的 JSON-RPC -32600 错误
+fn unsupported_media_type() -> Response {
+    let response = JsonRpcResponse::Error {
+        jsonrpc: "2.0".to_string(),
+        error: JsonRpcError {
+            code: error_codes::INVALID_REQUEST,
+            message: "Content-Type 必须为 application/json".to_string(),
+            data: None,
+        },
+        id: RequestId::Null,
+    };
+    let body = serde_json::to_vec(&response).expect("JSON-RPC 错误响应可序列化");
+    (
+        StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        [(header::CONTENT_TYPE, "application/json")],
+        body,
+    )
+        .into_response()
+}
+
+/// 429 响应，响应体为 id 为 null 的 JSON-RPC -32005 错误
+fn too_many_requests() -> Response {
+    let response = JsonRpcResponse::Error {
+        jsonrpc: "2.0".to_string(),
+        error: JsonRpcError {
+            code: error_codes::LIMIT_EXCEEDED,
+            message: "连接的并发请求过多".to_string(),
+            data: None,
+        },
+        id: RequestId::Null,
+    };
+    let body = serde_json::to_vec(&response).expect("JSON-RPC 错误响应可序列化");
+    (
+        StatusCode::TOO_MANY_REQUESTS,
+        [(header::CONTENT_TYPE, "application/json")],
+        body,
+    )
+        .into_response()
 }
 
 /// 健康检查端点
@@ -62,24 +175,51 @@ async fn health_check() -> impl IntoResponse {
     (StatusCode::OK, "OK")
 }
 
-/// 运行服务器
+/// 运行服务器（默认配置）
 pub async fn run_server<S: EthereumService + Clone + 'static>(
     host: &str,
     port: u16,
     rpc_handler: EthJsonRpcHandler<S>,
 ) -> anyhow::Result<()> {
-    let app = create_server(rpc_handler);
+    run_server_with_config(host, port, rpc_handler, ServerConfig::default()).await
+}
+
+/// 使用指定配置运行服务器
+pub async fn run_server_with_config<S: EthereumService + Clone + 'static>(
+    host: &str,
+    port: u16,
+    rpc_handler: EthJsonRpcHandler<S>,
+    config: ServerConfig,
+) -> anyhow::Result<()> {
+    let app = create_server_with_config(rpc_handler, config);
     let addr = format!("{}:{}", host, port);
     let listener = tokio::net::TcpListener::bind(&addr).await?;
 
     info!("以太坊 JSON-RPC 服务器启动于 {}", addr);
     info!("健康检查可访问 http://{}/health", addr);
 
-    axum::serve(listener, app).await?;
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await?;
 
     Ok(())
 }
 
+/// 在已绑定的监听器上运行服务器，`shutdown` 完成后停止接受新连接并等待进行中的请求结束
+pub async fn serve_until<S, F>(
+    listener: tokio::net::TcpListener,
+    rpc_handler: EthJsonRpcHandler<S>,
+    config: ServerConfig,
+    shutdown: F,
+) -> std::io::Result<()>
+where
+    S: EthereumService + Clone + 'static,
+    F: std::future::Future<Output = ()> + Send + 'static,
+{
+    let app = create_server_with_config(rpc_handler, config);
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .with_graceful_shutdown(shutdown)
+        .await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -91,4 +231,19 @@ mod tests {
         let response = health_check().await.into_response();
         assert_eq!(response.status(), StatusCode::OK);
     }
+
+    #[test]
+    fn test_accepts_content_type() {
+        let with = |value: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::CONTENT_TYPE, value.parse().unwrap());
+            headers
+        };
+
+        assert!(accepts_content_type(&with("application/json"), false));
+        assert!(accepts_content_type(&with("Application/JSON; charset=utf-8"), false));
+        assert!(!accepts_content_type(&with("text/plain"), true));
+        assert!(!accepts_content_type(&HeaderMap::new(), false));
+        assert!(accepts_content_type(&HeaderMap::new(), true));
+    }
 }
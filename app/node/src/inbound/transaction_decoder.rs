@@ -1,9 +1,9 @@
-/// EIP-1559 交易RLP解码器
-/// 参考: https://eips.ethereum.org/EIPS/eip-1559
-/// 参考: https://eips.ethereum.org/EIPS/eip-2718 (Typed Transaction Envelope)
+//! EIP-1559 交易RLP解码器
+//! 参考: https://eips.ethereum.org/EIPS/eip-1559
+//! 参考: https://eips.ethereum.org/EIPS/eip-2718 (Typed Transaction Envelope)
 
 use crate::domain::tx_types::{AccessListItem, DynamicFeeTx, TransactionValidationError};
-use ethereum_types::{Address, H256, U256, U64};
+use ethereum_types::Address;
 use rlp::{Decodable, DecoderError, Rlp};
 
 impl Decodable for DynamicFeeTx {
@@ -90,6 +90,22 @@ fn decode_access_list(rlp: &Rlp) -> Result<Vec<AccessListItem>, DecoderError> {
     Ok(access_list)
 }
 
+/// 读取 legacy 交易签名的 v 值
+///
+/// legacy 交易没有类型前缀，整体是 RLP 列表：
+/// rlp([nonce, gas_price, gas_limit, to, value, data, v, r, s])；
+/// 不是 legacy 交易时返回 None
+pub fn legacy_signature_v(raw_tx: &[u8]) -> Option<u64> {
+    if raw_tx.first().is_none_or(|&prefix| prefix < 0xc0) {
+        return None;
+    }
+    let rlp = Rlp::new(raw_tx);
+    if rlp.item_count().ok()? != 9 {
+        return None;
+    }
+    rlp.val_at(6).ok()
+}
+
 /// 从原始字节解码EIP-2718类型化交易
 /// 格式: 0x02 || rlp([...]) for EIP-1559
 pub fn decode_raw_transaction(raw_tx: &[u8]) -> Result<DynamicFeeTx, TransactionValidationError> {
@@ -129,6 +145,7 @@ pub fn decode_raw_transaction(raw_tx: &[u8]) -> Result<DynamicFeeTx, Transaction
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ethereum_types::{H256, U256, U64};
 
     #[test]
     fn test_decode_minimal_eip1559_tx() {
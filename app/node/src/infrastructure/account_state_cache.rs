@@ -0,0 +1,362 @@
+//! 账户状态缓存 - AccountStateProvider 的短 TTL 装饰器
+//!
+//! 同一发送者连续提交大量交易时，入池验证会反复查询同一账户的 nonce/余额。
+//! 该装饰器在 TTL 内复用查询结果，新区块到来时整体失效（状态已变化）。
+//!
+//! 只缓存成功的查询结果。AccountStateProvider 返回链头状态，缓存项按
+//! (地址, 链头区块号) 索引，链头变化后旧区块的结果不会再被命中。
+
+use crate::domain::block_types::Block;
+use crate::infrastructure::clock_impl::SystemClock;
+use crate::service::clock::Clock;
+use crate::service::transaction_validator::{AccountStateProvider, StateError};
+use async_trait::async_trait;
+use ethereum_types::{Address, U256, U64};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+/// 缓存键：(地址, 查询时的链头区块号)
+type CacheKey = (Address, U64);
+
+/// 缓存配置
+#[derive(Debug, Clone)]
+pub struct AccountStateCacheConfig {
+    /// 缓存项有效期
+    pub ttl: Duration,
+    /// 每类数据（余额、nonce、是否合约）最多缓存的账户数
+    pub capacity: usize,
+}
+
+impl Default for AccountStateCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_secs(2),
+            capacity: 4096,
+        }
+    }
+}
+
+/// 单类数据的 TTL 缓存
+struct TtlCache<V> {
+    entries: HashMap<CacheKey, (V, Instant)>,
+}
+
+impl<V: Copy> TtlCache<V> {
+    fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+
+    fn get(&self, key: &CacheKey, now: Instant, ttl: Duration) -> Option<V> {
+        self.entries
+            .get(key)
+            .filter(|(_, cached_at)| now.duration_since(*cached_at) < ttl)
+            .map(|(value, _)| *value)
+    }
+
+    /// 写入缓存；满时先清理过期项，仍满则淘汰最早写入的项
+    fn insert(&mut self, key: CacheKey, value: V, now: Instant, config: &AccountStateCacheConfig) {
+        if config.capacity == 0 {
+            return;
+        }
+        if self.entries.len() >= config.capacity && !self.entries.contains_key(&key) {
+            self.entries
+                .retain(|_, (_, cached_at)| now.duration_since(*cached_at) < config.ttl);
+            if self.entries.len() >= config.capacity {
+                let oldest = self
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, (_, cached_at))| *cached_at)
+                    .map(|(key, _)| *key);
+                if let Some(oldest) = oldest {
+                    self.entries.remove(&oldest);
+                }
+            }
+        }
+        self.entries.insert(key, (value, now));
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// 缓存状态（与查询逻辑分离）
+struct CacheState {
+    /// 当前链头区块号
+    head: U64,
+    balances: TtlCache<U256>,
+    nonces: TtlCache<U64>,
+    contracts: TtlCache<bool>,
+}
+
+/// 带 TTL 缓存的账户状态查询
+///
+/// 克隆的实例共享同一份缓存。
+#[derive(Clone)]
+pub struct CachedAccountStateProvider<S: AccountStateProvider, C: Clock = SystemClock> {
+    inner: S,
+    config: AccountStateCacheConfig,
+    clock: C,
+    state: Arc<Mutex<CacheState>>,
+}
+
+impl<S: AccountStateProvider> CachedAccountStateProvider<S> {
+    /// 使用系统时钟创建
+    pub fn new(inner: S, config: AccountStateCacheConfig) -> Self {
+        Self::with_clock(inner, config, SystemClock)
+    }
+}
+
+impl<S: AccountStateProvider, C: Clock> CachedAccountStateProvider<S, C> {
+    /// 使用指定时钟创建
+    pub fn with_clock(inner: S, config: AccountStateCacheConfig, clock: C) -> Self {
+        Self {
+            inner,
+            config,
+            clock,
+            state: Arc::new(Mutex::new(CacheState {
+                head: U64::zero(),
+                balances: TtlCache::new(),
+                nonces: TtlCache::new(),
+                contracts: TtlCache::new(),
+            })),
+        }
+    }
+
+    /// 新区块到来：切换链头并清空所有缓存
+    pub fn on_new_head(&self, head: U64) {
+        let mut state = self.state.lock().unwrap();
+        state.head = head;
+        state.balances.clear();
+        state.nonces.clear();
+        state.contracts.clear();
+    }
+
+    /// 查询前对齐调用方观察到的链头，与缓存记录的链头不同时按新链头处理
+    ///
+    /// 未订阅新链头通知的调用方（如直接读取仓储链头的服务层）以此保证失效
+    pub fn sync_head(&self, head: U64) {
+        if self.state.lock().unwrap().head != head {
+            self.on_new_head(head);
+        }
+    }
+
+    /// 当前链头下 `address` 的缓存键
+    fn key(&self, address: Address) -> CacheKey {
+        (address, self.state.lock().unwrap().head)
+    }
+}
+
+impl<S, C> CachedAccountStateProvider<S, C>
+where
+    S: AccountStateProvider + Clone + 'static,
+    C: Clock + Clone + 'static,
+{
+    /// 订阅新链头（如 `BlockChain::subscribe_new_heads`），每个新链头使缓存失效
+    ///
+    /// 订阅落后丢失通知时同样清空缓存；发送端关闭后任务退出
+    pub fn follow_new_heads(&self, mut heads: broadcast::Receiver<Block>) -> JoinHandle<()> {
+        let cache = self.clone();
+        tokio::spawn(async move {
+            loop {
+                match heads.recv().await {
+                    Ok(block) => cache.on_new_head(block.number()),
+                    Err(broadcast::error::RecvError::Lagged(_)) => {
+                        let head = cache.state.lock().unwrap().head;
+                        cache.on_new_head(head);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+}
+
+#[async_trait]
+impl<S: AccountStateProvider, C: Clock> AccountStateProvider for CachedAccountStateProvider<S, C> {
+    async fn get_balance(&self, address: Address) -> Result<U256, StateError> {
+        let now = self.clock.now();
+        let key = self.key(address);
+        if let Some(balance) = self.state.lock().unwrap().balances.get(&key, now, self.config.ttl) {
+            return Ok(balance);
+        }
+
+        let balance = self.inner.get_balance(address).await?;
+        self.state
+            .lock()
+            .unwrap()
+            .balances
+            .insert(key, balance, now, &self.config);
+        Ok(balance)
+    }
+
+    async fn get_nonce(&self, address: Address) -> Result<U64, StateError> {
+        let now = self.clock.now();
+        let key = self.key(address);
+        if let Some(nonce) = self.state.lock().unwrap().nonces.get(&key, now, self.config.ttl) {
+            return Ok(nonce);
+        }
+
+        let nonce = self.inner.get_nonce(address).await?;
+        self.state
+            .lock()
+            .unwrap()
+            .nonces
+            .insert(key, nonce, now, &self.config);
+        Ok(nonce)
+    }
+
+    async fn is_contract(&self, address: Address) -> Result<bool, StateError> {
+        let now = self.clock.now();
+        let key = self.key(address);
+        if let Some(is_contract) = self.state.lock().unwrap().contracts.get(&key, now, self.config.ttl) {
+            return Ok(is_contract);
+        }
+
+        let is_contract = self.inner.is_contract(address).await?;
+        self.state
+            .lock()
+            .unwrap()
+            .contracts
+            .insert(key, is_contract, now, &self.config);
+        Ok(is_contract)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::clock_impl::MockClock;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// 记录查询次数的后端
+    #[derive(Clone, Default)]
+    struct CountingProvider {
+        nonce_queries: Arc<AtomicUsize>,
+        nonce: Arc<Mutex<u64>>,
+    }
+
+    #[async_trait]
+    impl AccountStateProvider for CountingProvider {
+        async fn get_balance(&self, _address: Address) -> Result<U256, StateError> {
+            Ok(U256::zero())
+        }
+
+        async fn get_nonce(&self, _address: Address) -> Result<U64, StateError> {
+            self.nonce_queries.fetch_add(1, Ordering::SeqCst);
+            Ok(U64::from(*self.nonce.lock().unwrap()))
+        }
+
+        async fn is_contract(&self, _address: Address) -> Result<bool, StateError> {
+            Ok(false)
+        }
+    }
+
+    fn cached(
+        config: AccountStateCacheConfig,
+    ) -> (CachedAccountStateProvider<CountingProvider, MockClock>, CountingProvider, MockClock) {
+        let backend = CountingProvider::default();
+        let clock = MockClock::default();
+        let provider = CachedAccountStateProvider::with_clock(backend.clone(), config, clock.clone());
+        (provider, backend, clock)
+    }
+
+    #[tokio::test]
+    async fn test_lookup_within_ttl_is_cached() {
+        let (provider, backend, clock) = cached(AccountStateCacheConfig::default());
+        let sender = Address::from_low_u64_be(1);
+
+        assert_eq!(provider.get_nonce(sender).await.unwrap(), U64::zero());
+        *backend.nonce.lock().unwrap() = 5;
+        assert_eq!(provider.get_nonce(sender).await.unwrap(), U64::zero());
+        assert_eq!(backend.nonce_queries.load(Ordering::SeqCst), 1);
+
+        // 过期后重新查询后端
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(provider.get_nonce(sender).await.unwrap(), U64::from(5));
+        assert_eq!(backend.nonce_queries.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_new_head_invalidates() {
+        let (provider, backend, _) = cached(AccountStateCacheConfig::default());
+        let sender = Address::from_low_u64_be(1);
+
+        provider.get_nonce(sender).await.unwrap();
+        *backend.nonce.lock().unwrap() = 1;
+        provider.on_new_head(U64::one());
+
+        assert_eq!(provider.get_nonce(sender).await.unwrap(), U64::one());
+        assert_eq!(backend.nonce_queries.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_sync_head_invalidates_on_change() {
+        let (provider, backend, _) = cached(AccountStateCacheConfig::default());
+        let sender = Address::from_low_u64_be(1);
+
+        provider.get_nonce(sender).await.unwrap();
+        *backend.nonce.lock().unwrap() = 1;
+
+        // 链头未变：继续命中缓存
+        provider.sync_head(U64::zero());
+        assert_eq!(provider.get_nonce(sender).await.unwrap(), U64::zero());
+
+        provider.sync_head(U64::one());
+        assert_eq!(provider.get_nonce(sender).await.unwrap(), U64::one());
+        assert_eq!(backend.nonce_queries.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_follow_new_heads_invalidates() {
+        use crate::service::repo::block_repo::tests::make_block;
+        use ethereum_types::H256;
+
+        let (provider, backend, _) = cached(AccountStateCacheConfig::default());
+        let (heads, receiver) = broadcast::channel(4);
+        let follower = provider.follow_new_heads(receiver);
+        let sender = Address::from_low_u64_be(1);
+
+        provider.get_nonce(sender).await.unwrap();
+        *backend.nonce.lock().unwrap() = 1;
+
+        heads.send(make_block(7, H256::zero())).unwrap();
+        drop(heads);
+        follower.await.unwrap();
+
+        // 新链头下重新查询后端
+        assert_eq!(provider.get_nonce(sender).await.unwrap(), U64::one());
+        assert_eq!(backend.nonce_queries.load(Ordering::SeqCst), 2);
+        assert_eq!(provider.key(sender), (sender, U64::from(7)));
+    }
+
+    #[tokio::test]
+    async fn test_capacity_evicts_oldest() {
+        let config = AccountStateCacheConfig {
+            capacity: 2,
+            ..AccountStateCacheConfig::default()
+        };
+        let (provider, backend, clock) = cached(config);
+        let (a, b, c) = (
+            Address::from_low_u64_be(1),
+            Address::from_low_u64_be(2),
+            Address::from_low_u64_be(3),
+        );
+
+        provider.get_nonce(a).await.unwrap();
+        clock.advance(Duration::from_millis(10));
+        provider.get_nonce(b).await.unwrap();
+        provider.get_nonce(c).await.unwrap(); // 淘汰 a
+        assert_eq!(backend.nonce_queries.load(Ordering::SeqCst), 3);
+
+        provider.get_nonce(b).await.unwrap();
+        assert_eq!(backend.nonce_queries.load(Ordering::SeqCst), 3);
+        provider.get_nonce(a).await.unwrap();
+        assert_eq!(backend.nonce_queries.load(Ordering::SeqCst), 4);
+    }
+}
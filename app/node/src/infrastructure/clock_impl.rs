@@ -0,0 +1,107 @@
+//! 时钟实现
+//! - SystemClock: 读取系统时间
+//! - MockClock: 测试用确定性时钟，只在调用 advance 时前进
+
+use crate::service::clock::Clock;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// 系统时钟
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn unix_time(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default()
+    }
+}
+
+/// 可手动推进的时钟（克隆体共享同一时间）
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    /// 创建时的单调时钟基准
+    base: Instant,
+    /// 创建时的 Unix 时间戳
+    base_unix_time: u64,
+    /// 已推进的时长
+    elapsed: Arc<RwLock<Duration>>,
+}
+
+impl MockClock {
+    /// 以指定 Unix 时间戳创建时钟
+    pub fn new(unix_time: u64) -> Self {
+        Self {
+            base: Instant::now(),
+            base_unix_time: unix_time,
+            elapsed: Arc::new(RwLock::new(Duration::ZERO)),
+        }
+    }
+
+    /// 推进时钟
+    pub fn advance(&self, duration: Duration) {
+        *self.elapsed.write().unwrap() += duration;
+    }
+
+    fn elapsed(&self) -> Duration {
+        *self.elapsed.read().unwrap()
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.base + self.elapsed()
+    }
+
+    fn unix_time(&self) -> u64 {
+        self.base_unix_time + self.elapsed().as_secs()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_clock_only_moves_on_advance() {
+        let clock = MockClock::new(1_700_000_000);
+        let start = clock.now();
+        assert_eq!(clock.now(), start);
+        assert_eq!(clock.unix_time(), 1_700_000_000);
+
+        clock.advance(Duration::from_millis(1500));
+        assert_eq!(clock.now() - start, Duration::from_millis(1500));
+        assert_eq!(clock.unix_time(), 1_700_000_001);
+    }
+
+    #[test]
+    fn test_mock_clock_clones_share_time() {
+        let clock = MockClock::default();
+        let handle = clock.clone();
+        let start = clock.now();
+
+        handle.advance(Duration::from_secs(12));
+        assert_eq!(clock.now() - start, Duration::from_secs(12));
+        assert_eq!(clock.unix_time(), 12);
+    }
+
+    #[test]
+    fn test_system_clock() {
+        let clock = SystemClock;
+        // 2023-01-01 之后
+        assert!(clock.unix_time() > 1_672_531_200);
+        assert!(clock.now() <= Instant::now());
+    }
+}
@@ -6,12 +6,16 @@
 use crate::infrastructure::json_rpc_trait::EthJsonRpc;
 use crate::infrastructure::json_rpc_trait::RpcMethodError;
 use async_trait::async_trait;
+use ethereum_types::H256;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sha3::{Digest, Keccak256};
+use std::collections::HashSet;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use tokio::sync::Semaphore;
 
 /// JSON-RPC 请求结构
 #[derive(Debug, Clone, Serialize)]
@@ -23,14 +27,14 @@ struct JsonRpcRequest {
 }
 
 /// JSON-RPC 响应结构
+///
+/// 只解析需要的字段，`jsonrpc` / `id` 由 serde 忽略
 #[derive(Debug, Deserialize)]
 struct JsonRpcResponse {
-    jsonrpc: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     result: Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     error: Option<JsonRpcError>,
-    id: Option<u64>,  // 错误响应中 id 可能为 null
 }
 
 /// JSON-RPC 错误结构
@@ -42,6 +46,74 @@ struct JsonRpcError {
     data: Option<Value>,
 }
 
+/// 单次请求失败的分类（决定提交类操作能否把重试结果视为成功）
+#[derive(Debug)]
+enum RequestError {
+    /// 请求超时：对端可能已经收到并处理
+    Timeout(RpcMethodError),
+    /// 对端明确表示已收到过相同的数据
+    AlreadyKnown(RpcMethodError),
+    Other(RpcMethodError),
+}
+
+impl From<RequestError> for RpcMethodError {
+    fn from(err: RequestError) -> Self {
+        match err {
+            RequestError::Timeout(e) | RequestError::AlreadyKnown(e) | RequestError::Other(e) => e,
+        }
+    }
+}
+
+/// 判断错误信息是否表示“已收到过该交易”
+///
+/// 各客户端措辞不同：geth "already known"、nethermind "AlreadyKnown"、
+/// erigon "ALREADY_EXISTS"、besu "Known transaction"
+fn is_already_known(message: &str) -> bool {
+    let message = message.to_ascii_lowercase();
+    ["already known", "alreadyknown", "already_exists", "already exists", "known transaction"]
+        .iter()
+        .any(|needle| message.contains(needle))
+}
+
+/// 客户端连接配置
+///
+/// 高吞吐场景下可按需调整连接池和超时；对支持 HTTP/2 的节点开启
+/// `http2_prior_knowledge` 可省去协议协商，显著降低延迟
+#[derive(Debug, Clone)]
+pub struct EthApiClientConfig {
+    /// 单次请求超时
+    pub timeout: Duration,
+    /// 每个主机保留的最大空闲连接数
+    pub pool_max_idle_per_host: usize,
+    /// 空闲连接保持时间
+    pub pool_idle_timeout: Duration,
+    /// 直接使用 HTTP/2（跳过 HTTP/1.1 升级协商）
+    pub http2_prior_knowledge: bool,
+    /// User-Agent 请求头
+    pub user_agent: String,
+    /// 记录请求/响应日志（debug: 方法、URL、状态码、body 大小；trace: 完整 body）
+    pub log_requests: bool,
+    /// 日志脱敏钩子，在 trace 输出完整 body 之前调用（如抹掉私钥、签名等字段）
+    pub redact: Option<fn(&mut Value)>,
+    /// 对端点的最大并发请求数（None 表示不限制），超出的请求排队等待
+    pub max_concurrent_requests: Option<usize>,
+}
+
+impl Default for EthApiClientConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            pool_max_idle_per_host: 10,
+            pool_idle_timeout: Duration::from_secs(90),
+            http2_prior_knowledge: false,
+            user_agent: concat!("rusteth/", env!("CARGO_PKG_VERSION")).to_string(),
+            log_requests: false,
+            redact: None,
+            max_concurrent_requests: None,
+        }
+    }
+}
+
 /// 以太坊 JSON-RPC 客户端
 ///
 /// 高性能实现,遵循低延迟标准:
@@ -55,6 +127,14 @@ pub struct EthApiClient {
     rpc_url: String,
     /// 请求 ID 计数器(原子递增)
     request_id: Arc<AtomicU64>,
+    /// 是否记录请求/响应日志
+    log_requests: bool,
+    /// 日志脱敏钩子
+    redact: Option<fn(&mut Value)>,
+    /// 在途请求许可（客户端只访问单一端点，即按主机限流）
+    in_flight: Option<Arc<Semaphore>>,
+    /// 提交超时、结果未知的交易哈希（幂等键）
+    unconfirmed_submissions: Arc<Mutex<HashSet<H256>>>,
 }
 
 impl EthApiClient {
@@ -68,10 +148,22 @@ impl EthApiClient {
     /// - 配置连接池(默认最多 10 个连接)
     /// - 设置合理的超时时间(30秒)
     pub fn new(rpc_url: String) -> Result<Self, RpcMethodError> {
-        let client = Client::builder()
-            .pool_max_idle_per_host(10) // 连接池优化
-            .timeout(Duration::from_secs(30)) // 请求超时
-            .pool_idle_timeout(Duration::from_secs(90)) // 空闲连接保持时间
+        Self::with_config(rpc_url, EthApiClientConfig::default())
+    }
+
+    /// 使用自定义配置创建客户端
+    pub fn with_config(rpc_url: String, config: EthApiClientConfig) -> Result<Self, RpcMethodError> {
+        let mut builder = Client::builder()
+            .pool_max_idle_per_host(config.pool_max_idle_per_host) // 连接池优化
+            .timeout(config.timeout) // 请求超时
+            .pool_idle_timeout(config.pool_idle_timeout) // 空闲连接保持时间
+            .user_agent(config.user_agent);
+
+        if config.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+
+        let client = builder
             .build()
             .map_err(|e| RpcMethodError::InvalidParams(format!("创建 HTTP 客户端失败: {}", e)))?;
 
@@ -79,9 +171,89 @@ impl EthApiClient {
             client,
             rpc_url,
             request_id: Arc::new(AtomicU64::new(1)),
+            log_requests: config.log_requests,
+            redact: config.redact,
+            in_flight: config
+                .max_concurrent_requests
+                .map(|limit| Arc::new(Semaphore::new(limit))),
+            unconfirmed_submissions: Arc::new(Mutex::new(HashSet::new())),
         })
     }
 
+    /// 幂等提交已签名交易（eth_sendRawTransaction），最多尝试 `max_attempts` 次
+    ///
+    /// 请求超时时对端可能已经收到交易，直接重试会得到 "already known" 错误。
+    /// 以交易哈希 keccak256(raw_tx) 作为幂等键：超时的提交被记为结果未知，
+    /// 之后同一交易的重试若被告知已存在，视为提交成功并返回交易哈希。
+    /// 没有超时记录时的 "already known" 仍按错误返回。
+    pub async fn submit_raw_transaction(
+        &self,
+        raw_tx: &[u8],
+        max_attempts: usize,
+    ) -> Result<H256, RpcMethodError> {
+        let tx_hash = H256::from_slice(&Keccak256::digest(raw_tx));
+        let params = serde_json::json!([format!("0x{}", hex::encode(raw_tx))]);
+
+        let mut attempt = 1;
+        loop {
+            match self.execute("eth_sendRawTransaction", params.clone()).await {
+                Ok(result) => {
+                    self.unconfirmed_submissions.lock().unwrap().remove(&tx_hash);
+                    return Ok(serde_json::from_value(result)?);
+                }
+                Err(RequestError::AlreadyKnown(e)) => {
+                    if self.unconfirmed_submissions.lock().unwrap().remove(&tx_hash) {
+                        tracing::debug!(tx_hash = %tx_hash, "超时后重试得到 already known，视为提交成功");
+                        return Ok(tx_hash);
+                    }
+                    return Err(e);
+                }
+                Err(RequestError::Timeout(e)) => {
+                    self.unconfirmed_submissions.lock().unwrap().insert(tx_hash);
+                    if attempt >= max_attempts {
+                        return Err(e);
+                    }
+                    tracing::warn!(tx_hash = %tx_hash, attempt, "提交交易超时，重试");
+                    attempt += 1;
+                }
+                Err(RequestError::Other(e)) => return Err(e),
+            }
+        }
+    }
+
+    /// 记录出站请求
+    fn log_request(&self, method: &str, body: &[u8]) {
+        if !self.log_requests {
+            return;
+        }
+        tracing::debug!(method, url = %self.rpc_url, size = body.len(), "RPC 请求");
+        if tracing::enabled!(tracing::Level::TRACE) {
+            tracing::trace!(method, body = %self.redacted(body), "RPC 请求体");
+        }
+    }
+
+    /// 记录响应
+    fn log_response(&self, method: &str, status: reqwest::StatusCode, body: &[u8]) {
+        if !self.log_requests {
+            return;
+        }
+        tracing::debug!(method, url = %self.rpc_url, status = status.as_u16(), size = body.len(), "RPC 响应");
+        if tracing::enabled!(tracing::Level::TRACE) {
+            tracing::trace!(method, body = %self.redacted(body), "RPC 响应体");
+        }
+    }
+
+    /// 应用脱敏钩子后的 body 文本（非 JSON 内容原样输出）
+    fn redacted(&self, body: &[u8]) -> String {
+        match (self.redact, serde_json::from_slice::<Value>(body)) {
+            (Some(redact), Ok(mut value)) => {
+                redact(&mut value);
+                value.to_string()
+            }
+            _ => String::from_utf8_lossy(body).into_owned(),
+        }
+    }
+
     /// 发送 JSON-RPC 请求
     ///
     /// # 低延迟设计
@@ -89,6 +261,11 @@ impl EthApiClient {
     /// - 直接序列化避免中间分配
     /// - 异步非阻塞 I/O
     async fn send_request(&self, method: &str, params: Value) -> Result<Value, RpcMethodError> {
+        self.execute(method, params).await.map_err(Into::into)
+    }
+
+    /// 发送请求并对失败分类（超时 / 已存在 / 其他）
+    async fn execute(&self, method: &str, params: Value) -> Result<Value, RequestError> {
         // 原子递增请求 ID
         let id = self.request_id.fetch_add(1, Ordering::Relaxed);
 
@@ -99,41 +276,81 @@ impl EthApiClient {
             id,
         };
 
+        let body = serde_json::to_vec(&request)
+            .map_err(|e| RequestError::Other(e.into()))?;
+
+        // 许可持有到响应体读取完毕
+        let _permit = match &self.in_flight {
+            Some(semaphore) => Some(semaphore.acquire().await.map_err(|e| {
+                RequestError::Other(RpcMethodError::InvalidParams(format!("获取并发许可失败: {}", e)))
+            })?),
+            None => None,
+        };
+        self.log_request(method, &body);
+
         // 发送 HTTP POST 请求
         let response = self
             .client
             .post(&self.rpc_url)
-            .json(&request)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
             .send()
             .await
             .map_err(|e| {
-                RpcMethodError::InvalidParams(format!("HTTP 请求失败: {}", e))
+                let err = RpcMethodError::InvalidParams(format!("HTTP 请求失败: {}", e));
+                if e.is_timeout() {
+                    RequestError::Timeout(err)
+                } else {
+                    RequestError::Other(err)
+                }
             })?;
 
-        // 检查 HTTP 状态码
-        if !response.status().is_success() {
-            return Err(RpcMethodError::InvalidParams(format!(
-                "HTTP 错误: {}",
-                response.status()
-            )));
+        let status = response.status();
+        let bytes = response.bytes().await.map_err(|e| {
+            let err = RpcMethodError::InvalidParams(format!("读取响应失败: {}", e));
+            if e.is_timeout() {
+                RequestError::Timeout(err)
+            } else {
+                RequestError::Other(err)
+            }
+        })?;
+        self.log_response(method, status, &bytes);
+
+        // 检查 HTTP 状态码（部分网关以 409 Conflict 或 400 + 错误信息表示重复提交）
+        if !status.is_success() {
+            let err = RpcMethodError::InvalidParams(format!("HTTP 错误: {}", status));
+            let already_known = status == reqwest::StatusCode::CONFLICT
+                || (status == reqwest::StatusCode::BAD_REQUEST
+                    && is_already_known(&String::from_utf8_lossy(&bytes)));
+            return Err(if already_known {
+                RequestError::AlreadyKnown(err)
+            } else {
+                RequestError::Other(err)
+            });
         }
 
         // 解析 JSON-RPC 响应
-        let rpc_response: JsonRpcResponse = response.json().await.map_err(|e| {
-            RpcMethodError::InvalidParams(format!("解析响应失败: {}", e))
+        let rpc_response: JsonRpcResponse = serde_json::from_slice(&bytes).map_err(|e| {
+            RequestError::Other(RpcMethodError::InvalidParams(format!("解析响应失败: {}", e)))
         })?;
 
         // 处理 JSON-RPC 错误
         if let Some(error) = rpc_response.error {
-            return Err(RpcMethodError::InvalidParams(format!(
-                "RPC 错误 [{}]: {}",
-                error.code, error.message
-            )));
+            let already_known = is_already_known(&error.message);
+            let err = RpcMethodError::InvalidParams(match error.data {
+                Some(data) => format!("RPC 错误 [{}]: {} ({})", error.code, error.message, data),
+                None => format!("RPC 错误 [{}]: {}", error.code, error.message),
+            });
+            return Err(if already_known {
+                RequestError::AlreadyKnown(err)
+            } else {
+                RequestError::Other(err)
+            });
         }
 
         // 返回结果
         rpc_response.result.ok_or_else(|| {
-            RpcMethodError::InvalidParams("响应中缺少 result 字段".to_string())
+            RequestError::Other(RpcMethodError::InvalidParams("响应中缺少 result 字段".to_string()))
         })
     }
 }
@@ -242,3 +459,144 @@ impl EthJsonRpc for EthApiClient {
         self.send_request("eth_maxPriorityFeePerGas", serde_json::json!([])).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::post, Json, Router};
+    use tracing_test::traced_test;
+
+    /// 启动本地 mock RPC 服务
+    async fn spawn_mock_server() -> String {
+        let app = Router::new().route(
+            "/",
+            post(|| async { Json(serde_json::json!({"jsonrpc": "2.0", "result": "0x10", "id": 1})) }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        format!("http://{}", addr)
+    }
+
+    fn redact_params(value: &mut Value) {
+        if let Some(params) = value.get_mut("params") {
+            *params = Value::String("<redacted>".to_string());
+        }
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn test_request_logging_enabled() {
+        let url = spawn_mock_server().await;
+        let config = EthApiClientConfig {
+            log_requests: true,
+            redact: Some(redact_params),
+            ..EthApiClientConfig::default()
+        };
+        let client = EthApiClient::with_config(url, config).unwrap();
+
+        client
+            .eth_send_raw_transaction(serde_json::json!(["0xdeadbeef"]))
+            .await
+            .unwrap();
+
+        logs_assert(|lines: &[&str]| {
+            let has = |level: &str, needle: &str| {
+                lines.iter().any(|l| l.contains(level) && l.contains(needle))
+            };
+            if !has("DEBUG", "RPC 请求") || !has("DEBUG", "status=200") {
+                return Err("缺少 debug 级别的请求/响应日志".to_string());
+            }
+            if !has("TRACE", "RPC 请求体") || !has("TRACE", "RPC 响应体") {
+                return Err("缺少 trace 级别的 body 日志".to_string());
+            }
+            if lines.iter().any(|l| l.contains("0xdeadbeef")) {
+                return Err("敏感字段未脱敏".to_string());
+            }
+            Ok(())
+        });
+        assert!(logs_contain("<redacted>"));
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn test_request_logging_disabled() {
+        let url = spawn_mock_server().await;
+        let client = EthApiClient::new(url).unwrap();
+
+        client.eth_block_number().await.unwrap();
+
+        assert!(!logs_contain("RPC 请求"));
+        assert!(!logs_contain("RPC 响应"));
+    }
+
+    /// 第一次请求挂起（触发客户端超时），之后的请求返回 "already known"
+    async fn spawn_slow_then_known_server() -> String {
+        use std::sync::atomic::AtomicUsize;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = Router::new().route(
+            "/",
+            post(move || {
+                let calls = calls.clone();
+                async move {
+                    if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                        tokio::time::sleep(Duration::from_secs(5)).await;
+                    }
+                    Json(serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "error": {"code": -32000, "message": "already known"},
+                        "id": 1
+                    }))
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_submit_after_timeout_treats_already_known_as_success() {
+        let url = spawn_slow_then_known_server().await;
+        let config = EthApiClientConfig {
+            timeout: Duration::from_millis(200),
+            ..EthApiClientConfig::default()
+        };
+        let client = EthApiClient::with_config(url, config).unwrap();
+        let raw_tx = [0x02, 0xc0];
+
+        let tx_hash = client.submit_raw_transaction(&raw_tx, 2).await.unwrap();
+        assert_eq!(tx_hash, H256::from_slice(&Keccak256::digest(raw_tx)));
+        assert!(client.unconfirmed_submissions.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_already_known_without_timeout_is_error() {
+        let app = Router::new().route(
+            "/",
+            post(|| async {
+                Json(serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "error": {"code": -32000, "message": "already known"},
+                    "id": 1
+                }))
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        let client = EthApiClient::new(format!("http://{}", addr)).unwrap();
+
+        // 没有超时记录：无法确认是本次提交，按错误返回
+        let err = client.submit_raw_transaction(&[0x02, 0xc0], 3).await.unwrap_err();
+        assert!(err.to_string().contains("already known"));
+    }
+}
@@ -0,0 +1,31 @@
+//! 自定义 RPC 方法处理器仓储内存实现
+
+use crate::service::repo::handler_repo::{CommandHandlerFn, HandlerRepository};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// 内存处理器仓储（方法名 → 处理器）
+#[derive(Clone, Default)]
+pub struct InMemoryHandlerRepository {
+    handlers: Arc<RwLock<HashMap<String, Arc<dyn CommandHandlerFn>>>>,
+}
+
+impl InMemoryHandlerRepository {
+    /// 创建空仓储
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl HandlerRepository for InMemoryHandlerRepository {
+    fn register(&self, method: &str, handler: Arc<dyn CommandHandlerFn>) {
+        self.handlers
+            .write()
+            .unwrap()
+            .insert(method.to_string(), handler);
+    }
+
+    fn get(&self, method: &str) -> Option<Arc<dyn CommandHandlerFn>> {
+        self.handlers.read().unwrap().get(method).cloned()
+    }
+}
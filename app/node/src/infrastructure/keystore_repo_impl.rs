@@ -0,0 +1,114 @@
+//! 密钥库内存实现
+//! 采用Erlang风格的无状态设计：服务与状态分离
+
+use crate::domain::tx_types::public_key_to_address;
+use crate::service::repo::keystore_repo::{KeyStore, KeyStoreError, RecoverableSignature};
+use async_trait::async_trait;
+use ethereum_types::{Address, H256, U256};
+use k256::ecdsa::SigningKey;
+use std::sync::{Arc, RwLock};
+
+/// 内存密钥库（私钥仅保存在进程内存中，重启即丢失）
+#[derive(Clone, Default)]
+pub struct InMemoryKeyStore {
+    /// (地址, 私钥)，保持导入顺序
+    keys: Arc<RwLock<Vec<(Address, SigningKey)>>>,
+}
+
+impl InMemoryKeyStore {
+    /// 创建空密钥库
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 导入 32 字节 secp256k1 私钥，返回对应地址
+    pub fn import_key(&self, secret: &[u8]) -> Result<Address, KeyStoreError> {
+        let key = SigningKey::from_slice(secret)
+            .map_err(|e| KeyStoreError::InvalidKey(e.to_string()))?;
+        Ok(self.insert(key))
+    }
+
+    /// 随机生成新账户，返回地址
+    pub fn generate(&self) -> Address {
+        self.insert(SigningKey::random(&mut rand::thread_rng()))
+    }
+
+    fn insert(&self, key: SigningKey) -> Address {
+        let address = public_key_to_address(key.verifying_key());
+        let mut keys = self.keys.write().unwrap();
+        if !keys.iter().any(|(a, _)| *a == address) {
+            keys.push((address, key));
+        }
+        address
+    }
+}
+
+#[async_trait]
+impl KeyStore for InMemoryKeyStore {
+    async fn accounts(&self) -> Result<Vec<Address>, KeyStoreError> {
+        Ok(self.keys.read().unwrap().iter().map(|(a, _)| *a).collect())
+    }
+
+    async fn has_account(&self, address: &Address) -> Result<bool, KeyStoreError> {
+        Ok(self.keys.read().unwrap().iter().any(|(a, _)| a == address))
+    }
+
+    async fn sign_hash(
+        &self,
+        address: &Address,
+        hash: &H256,
+    ) -> Result<RecoverableSignature, KeyStoreError> {
+        let keys = self.keys.read().unwrap();
+        let (_, key) = keys
+            .iter()
+            .find(|(a, _)| a == address)
+            .ok_or(KeyStoreError::UnknownAccount(*address))?;
+
+        let (signature, recovery_id) = key
+            .sign_prehash_recoverable(hash.as_bytes())
+            .map_err(|e| KeyStoreError::SigningFailed(e.to_string()))?;
+
+        let bytes = signature.to_bytes();
+        Ok(RecoverableSignature {
+            recovery_id: recovery_id.to_byte(),
+            r: U256::from_big_endian(&bytes[..32]),
+            s: U256::from_big_endian(&bytes[32..]),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_import_and_list_accounts() {
+        let keystore = InMemoryKeyStore::new();
+        let a = keystore.import_key(&[0x11u8; 32]).unwrap();
+        let b = keystore.generate();
+
+        assert_eq!(keystore.accounts().await.unwrap(), vec![a, b]);
+        assert!(keystore.has_account(&a).await.unwrap());
+        assert!(!keystore.has_account(&Address::zero()).await.unwrap());
+
+        // 重复导入不会产生重复账户
+        keystore.import_key(&[0x11u8; 32]).unwrap();
+        assert_eq!(keystore.accounts().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_import_invalid_key() {
+        let keystore = InMemoryKeyStore::new();
+        assert!(matches!(
+            keystore.import_key(&[0u8; 32]),
+            Err(KeyStoreError::InvalidKey(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sign_unknown_account() {
+        let keystore = InMemoryKeyStore::new();
+        let result = keystore.sign_hash(&Address::zero(), &H256::zero()).await;
+        assert_eq!(result, Err(KeyStoreError::UnknownAccount(Address::zero())));
+    }
+}
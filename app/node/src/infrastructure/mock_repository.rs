@@ -2,27 +2,58 @@
 //!
 //! 这是一个简单的内存实现，用于测试和开发
 
+use async_trait::async_trait;
 use ethereum_types::{Address, Bloom, H256, H64, U256, U64};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::{Arc, RwLock};
-use crate::domain::command_types::{Block, Transaction, TransactionReceipt};
+use crate::domain::command_types::{Block, BlockTransactions, Transaction, TransactionReceipt};
+use crate::service::transaction_validator::{AccountStateProvider, StateError};
+
+/// 单个账户的代码变更历史（生效区块号 -> 代码）
+type CodeHistory = BTreeMap<U64, Vec<u8>>;
 
 /// 模拟的内存以太坊仓储（支持 Clone 用于静态分发）
 #[derive(Clone)]
 pub struct MockEthereumRepository {
     pub(crate) blocks: Arc<RwLock<HashMap<U64, Block>>>,
+    /// 区块哈希 -> 区块号 二级索引，避免按哈希查询时线性扫描
+    pub(crate) block_hashes: Arc<RwLock<HashMap<H256, U64>>>,
     pub(crate) transactions: Arc<RwLock<HashMap<H256, Transaction>>>,
     pub(crate) receipts: Arc<RwLock<HashMap<H256, TransactionReceipt>>>,
     pub(crate) current_block_number: Arc<RwLock<U64>>,
+    /// 合约代码历史，用于按历史区块查询 eth_getCode
+    pub(crate) code_history: Arc<RwLock<HashMap<Address, CodeHistory>>>,
+    /// 账户余额（未设置的账户返回 `DEFAULT_BALANCE`）
+    pub(crate) balances: Arc<RwLock<HashMap<Address, U256>>>,
+    /// 被重组替换下来的非主链区块（哈希 -> 区块）
+    pub(crate) side_blocks: Arc<RwLock<HashMap<H256, Block>>>,
+    /// 账户已上链 nonce（未设置的账户为 0）
+    pub(crate) nonces: Arc<RwLock<HashMap<Address, u64>>>,
+    /// 分叉选择状态：safe / finalized 区块号（共识层尚未通知时为 None）
+    pub(crate) safe_block_number: Arc<RwLock<Option<U64>>>,
+    pub(crate) finalized_block_number: Arc<RwLock<Option<U64>>>,
 }
 
 impl MockEthereumRepository {
+    /// 未设置余额的账户的模拟余额（1 ETH）
+    pub const DEFAULT_BALANCE: u64 = 1_000_000_000_000_000_000;
+
+    /// 创世区块的 base fee（EIP-1559 INITIAL_BASE_FEE，1 Gwei）
+    pub const INITIAL_BASE_FEE: u64 = 1_000_000_000;
+
     pub fn new() -> Self {
         let repo = Self {
             blocks: Arc::new(RwLock::new(HashMap::new())),
+            block_hashes: Arc::new(RwLock::new(HashMap::new())),
             transactions: Arc::new(RwLock::new(HashMap::new())),
             receipts: Arc::new(RwLock::new(HashMap::new())),
             current_block_number: Arc::new(RwLock::new(U64::from(0))),
+            code_history: Arc::new(RwLock::new(HashMap::new())),
+            balances: Arc::new(RwLock::new(HashMap::new())),
+            side_blocks: Arc::new(RwLock::new(HashMap::new())),
+            nonces: Arc::new(RwLock::new(HashMap::new())),
+            safe_block_number: Arc::new(RwLock::new(None)),
+            finalized_block_number: Arc::new(RwLock::new(None)),
         };
 
         // 初始化创世区块
@@ -49,23 +80,133 @@ impl MockEthereumRepository {
             gas_limit: U256::from(8_000_000u64),
             gas_used: U256::zero(),
             timestamp: U256::from(0),
-            transactions: vec![],
+            transactions: BlockTransactions::default(),
             uncles: vec![],
+            base_fee_per_gas: Some(U256::from(Self::INITIAL_BASE_FEE)),
+            excess_blob_gas: None,
         };
 
-        self.blocks
-            .write()
-            .unwrap()
-            .insert(U64::zero(), genesis_block);
+        self.insert_block(genesis_block);
+    }
+
+    /// 写入区块并维护哈希索引；同一高度被替换时移除旧哈希
+    fn insert_block(&self, block: Block) {
+        let (hash, number) = (block.hash, block.number);
+        let mut blocks = self.blocks.write().unwrap();
+        let mut block_hashes = self.block_hashes.write().unwrap();
+        let mut side_blocks = self.side_blocks.write().unwrap();
+        self.reindex_transactions(blocks.get(&number), &block);
+        if let Some(old) = blocks.insert(number, block) {
+            // 同高度的旧区块被替换（重组），保留为非主链区块
+            block_hashes.remove(&old.hash);
+            if old.hash != hash {
+                side_blocks.insert(old.hash, old);
+            }
+        }
+        side_blocks.remove(&hash);
+        block_hashes.insert(hash, number);
+    }
+
+    /// 维护交易位置索引，使 eth_getTransactionByHash 反映当前主链
+    ///
+    /// 被替换区块中的交易先退回 pending 形式（区块字段为空），
+    /// 再按新区块的完整交易列表记录区块哈希、区块号和索引；
+    /// 交易若在其他区块重新打包，写入该区块时再次定位
+    fn reindex_transactions(&self, replaced: Option<&Block>, block: &Block) {
+        let mut transactions = self.transactions.write().unwrap();
+        if let Some(old) = replaced.filter(|old| old.hash != block.hash) {
+            for hash in old.transactions.hashes() {
+                let Some(tx) = transactions.get_mut(&hash) else {
+                    continue;
+                };
+                if tx.block_hash == Some(old.hash) {
+                    tx.block_hash = None;
+                    tx.block_number = None;
+                    tx.transaction_index = None;
+                }
+            }
+        }
+        if let BlockTransactions::Full(txs) = &block.transactions {
+            for (index, tx) in txs.iter().enumerate() {
+                let mut tx = tx.clone();
+                tx.block_hash = Some(block.hash);
+                tx.block_number = Some(block.number);
+                tx.transaction_index = Some(U64::from(index));
+                transactions.insert(tx.hash, tx);
+            }
+        }
     }
 
     /// 添加模拟区块（用于测试）
     pub fn add_block(&self, block: Block) {
         let number = block.number;
-        self.blocks.write().unwrap().insert(number, block);
+        self.insert_block(block);
         *self.current_block_number.write().unwrap() = number;
     }
 
+    /// 通过哈希索引查询区块
+    pub fn get_block_by_hash(&self, hash: &H256) -> Option<Block> {
+        let number = *self.block_hashes.read().unwrap().get(hash)?;
+        self.blocks.read().unwrap().get(&number).cloned()
+    }
+
+    /// 查询已被重组替换的非主链区块
+    pub fn get_side_block(&self, hash: &H256) -> Option<Block> {
+        self.side_blocks.read().unwrap().get(hash).cloned()
+    }
+
+    /// 设置合约代码（自 `block_number` 起生效；空代码表示自毁）
+    pub fn set_code(&self, address: Address, block_number: U64, code: Vec<u8>) {
+        self.code_history
+            .write()
+            .unwrap()
+            .entry(address)
+            .or_default()
+            .insert(block_number, code);
+    }
+
+    /// 查询指定区块时的合约代码（取不晚于该区块的最近一次变更）
+    pub fn code_at(&self, address: &Address, block_number: U64) -> Vec<u8> {
+        self.code_history
+            .read()
+            .unwrap()
+            .get(address)
+            .and_then(|history| history.range(..=block_number).next_back())
+            .map(|(_, code)| code.clone())
+            .unwrap_or_default()
+    }
+
+    /// 设置账户余额
+    pub fn set_balance(&self, address: Address, balance: U256) {
+        self.balances.write().unwrap().insert(address, balance);
+    }
+
+    /// 查询账户余额
+    pub fn balance_of(&self, address: &Address) -> U256 {
+        self.balances
+            .read()
+            .unwrap()
+            .get(address)
+            .copied()
+            .unwrap_or_else(|| U256::from(Self::DEFAULT_BALANCE))
+    }
+
+    /// 设置账户已上链 nonce
+    pub fn set_nonce(&self, address: Address, nonce: u64) {
+        self.nonces.write().unwrap().insert(address, nonce);
+    }
+
+    /// 查询账户已上链 nonce
+    pub fn nonce_of(&self, address: &Address) -> u64 {
+        self.nonces.read().unwrap().get(address).copied().unwrap_or(0)
+    }
+
+    /// 更新分叉选择状态（对应 engine_forkchoiceUpdated 中的 safe / finalized）
+    pub fn set_forkchoice(&self, safe: U64, finalized: U64) {
+        *self.safe_block_number.write().unwrap() = Some(safe);
+        *self.finalized_block_number.write().unwrap() = Some(finalized);
+    }
+
     /// 添加模拟交易（用于测试）
     pub fn add_transaction(&self, tx: Transaction) {
         self.transactions.write().unwrap().insert(tx.hash, tx);
@@ -85,3 +226,78 @@ impl Default for MockEthereumRepository {
         Self::new()
     }
 }
+
+/// 入池验证查询最新状态
+#[async_trait]
+impl AccountStateProvider for MockEthereumRepository {
+    async fn get_balance(&self, address: Address) -> Result<U256, StateError> {
+        Ok(self.balance_of(&address))
+    }
+
+    async fn get_nonce(&self, address: Address) -> Result<U64, StateError> {
+        Ok(U64::from(self.nonce_of(&address)))
+    }
+
+    async fn is_contract(&self, address: Address) -> Result<bool, StateError> {
+        let head = *self.current_block_number.read().unwrap();
+        Ok(!self.code_at(&address, head).is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block_at(repo: &MockEthereumRepository, number: u64, hash: H256) -> Block {
+        let mut block = repo.blocks.read().unwrap()[&U64::zero()].clone();
+        block.number = U64::from(number);
+        block.hash = hash;
+        block
+    }
+
+    #[test]
+    fn test_block_hash_index_with_many_blocks() {
+        let repo = MockEthereumRepository::new();
+        let hashes: Vec<H256> = (1..=10_000u64).map(H256::from_low_u64_be).collect();
+        for (i, hash) in hashes.iter().enumerate() {
+            repo.add_block(block_at(&repo, i as u64 + 1, *hash));
+        }
+
+        assert_eq!(repo.block_hashes.read().unwrap().len(), 10_001);
+        for (i, hash) in hashes.iter().enumerate() {
+            let block = repo.get_block_by_hash(hash).expect("indexed block");
+            assert_eq!(block.number, U64::from(i as u64 + 1));
+        }
+        // 创世区块同样被索引
+        assert_eq!(repo.get_block_by_hash(&H256::zero()).unwrap().number, U64::zero());
+        assert!(repo.get_block_by_hash(&H256::repeat_byte(0xff)).is_none());
+    }
+
+    #[test]
+    fn test_block_hash_index_drops_replaced_block() {
+        let repo = MockEthereumRepository::new();
+        let old_hash = H256::repeat_byte(0x01);
+        let new_hash = H256::repeat_byte(0x02);
+
+        repo.add_block(block_at(&repo, 1, old_hash));
+        repo.add_block(block_at(&repo, 1, new_hash));
+
+        assert!(repo.get_block_by_hash(&old_hash).is_none());
+        assert_eq!(repo.get_block_by_hash(&new_hash).unwrap().number, U64::one());
+    }
+
+    #[test]
+    fn test_code_history_by_block() {
+        let repo = MockEthereumRepository::new();
+        let contract = Address::from_low_u64_be(0xc0de);
+
+        repo.set_code(contract, U64::from(5), vec![0x60, 0x00]);
+        repo.set_code(contract, U64::from(8), vec![]);
+
+        assert!(repo.code_at(&contract, U64::from(4)).is_empty());
+        assert_eq!(repo.code_at(&contract, U64::from(5)), vec![0x60, 0x00]);
+        assert_eq!(repo.code_at(&contract, U64::from(7)), vec![0x60, 0x00]);
+        assert!(repo.code_at(&contract, U64::from(8)).is_empty());
+        assert!(repo.code_at(&Address::zero(), U64::from(5)).is_empty());
+    }
+}
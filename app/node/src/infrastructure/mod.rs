@@ -2,3 +2,12 @@ pub mod eth_api_client;
 pub mod json_rpc_trait;
 pub mod mock_repository;
 pub mod transaction_repo_impl;
+pub mod tx_pool_actor;
+pub mod keystore_repo_impl;
+pub mod handler_repo_impl;
+pub mod clock_impl;
+pub mod revm_executor;
+pub mod account_state_cache;
+pub mod node_config;
+pub mod node_handle;
+pub mod node_identity;
@@ -0,0 +1,287 @@
+//! 节点启动配置
+//!
+//! 加载顺序：内置默认值 → TOML 配置文件（可选）→ 环境变量覆盖。
+//! 默认值与此前 main.rs 中硬编码的行为一致，不提供配置文件时节点行为不变。
+//!
+//! 支持的环境变量：
+//! - `RUSTETH_RPC_HOST` / `RUSTETH_RPC_PORT`
+//! - `RUSTETH_CHAIN_ID`
+//! - `RUSTETH_BEACON_ENDPOINT`
+//! - `RUSTETH_DISCOVERY_ENABLED` / `RUSTETH_DISCOVERY_PORT`
+
+use crate::domain::rpc_config::{ConnectionOverflow, NamespaceConfig, DEFAULT_MAX_REQUESTS_PER_CONNECTION};
+use crate::infrastructure::transaction_repo_impl::TxPoolConfig;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// 配置加载错误
+#[derive(Debug, Error)]
+pub enum NodeConfigError {
+    /// 读取配置文件失败
+    #[error("读取配置文件 {path} 失败: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+
+    /// TOML 解析失败
+    #[error("配置文件解析失败: {0}")]
+    Parse(#[from] toml::de::Error),
+
+    /// 环境变量取值无效
+    #[error("环境变量 {name} 的值无效: {value}")]
+    InvalidEnv { name: String, value: String },
+}
+
+/// 节点配置
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NodeConfig {
+    /// JSON-RPC 服务
+    pub rpc: RpcConfig,
+    /// 链参数
+    pub chain: ChainConfig,
+    /// 信标节点
+    pub beacon: BeaconConfig,
+    /// 节点发现（预留：节点尚未接入发现协议）
+    pub discovery: DiscoveryConfig,
+    /// 交易池容量
+    pub tx_pool: TxPoolConfig,
+}
+
+/// JSON-RPC 服务配置
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RpcConfig {
+    pub host: String,
+    pub port: u16,
+    /// eth_getLogs 单次最多返回的日志条数
+    pub max_log_results: usize,
+    /// eth_callBundle 单次最多包含的调用数
+    pub max_bundle_size: usize,
+    /// 开放的 JSON-RPC 命名空间（默认全部开放）
+    pub namespaces: NamespaceConfig,
+    /// 单个连接的在途请求上限（0 表示不限制）
+    pub max_requests_per_connection: usize,
+    /// 连接在途请求达到上限后的处理方式（"reject" 返回 429 / "queue" 排队）
+    pub connection_overflow: ConnectionOverflow,
+}
+
+impl Default for RpcConfig {
+    fn default() -> Self {
+        Self {
+            host: "127.0.0.1".to_string(),
+            port: 8545,
+            max_log_results: 10_000,
+            max_bundle_size: 100,
+            namespaces: NamespaceConfig::default(),
+            max_requests_per_connection: DEFAULT_MAX_REQUESTS_PER_CONNECTION,
+            connection_overflow: ConnectionOverflow::default(),
+        }
+    }
+}
+
+/// 链参数配置
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChainConfig {
+    /// 链 ID（EIP-155）
+    pub chain_id: u64,
+}
+
+impl Default for ChainConfig {
+    fn default() -> Self {
+        Self { chain_id: 1 }
+    }
+}
+
+/// 信标节点配置
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BeaconConfig {
+    /// 信标节点 REST API 地址（None 表示不连接）
+    pub endpoint: Option<String>,
+}
+
+/// 节点发现配置
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DiscoveryConfig {
+    pub enabled: bool,
+    /// UDP 监听端口
+    pub port: u16,
+    /// 引导节点（ENR 或 enode URL）
+    pub bootnodes: Vec<String>,
+    /// 节点私钥文件（不存在时生成）；None 表示每次启动使用临时身份
+    pub node_key_path: Option<PathBuf>,
+}
+
+impl Default for DiscoveryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 30303,
+            bootnodes: Vec::new(),
+            node_key_path: None,
+        }
+    }
+}
+
+impl NodeConfig {
+    /// 解析 TOML 文本，缺省的字段取默认值
+    pub fn from_toml_str(s: &str) -> Result<Self, NodeConfigError> {
+        Ok(toml::from_str(s)?)
+    }
+
+    /// 加载配置：`path` 为 None 时使用默认值，随后应用进程环境变量覆盖
+    pub fn load(path: Option<&Path>) -> Result<Self, NodeConfigError> {
+        let mut config = match path {
+            Some(path) => {
+                let content = std::fs::read_to_string(path).map_err(|source| NodeConfigError::Io {
+                    path: path.display().to_string(),
+                    source,
+                })?;
+                Self::from_toml_str(&content)?
+            }
+            None => Self::default(),
+        };
+        config.apply_env_overrides(|name| std::env::var(name).ok())?;
+        Ok(config)
+    }
+
+    /// 按 `lookup` 返回的环境变量覆盖配置（便于测试注入）
+    pub fn apply_env_overrides(
+        &mut self,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<(), NodeConfigError> {
+        if let Some(host) = lookup("RUSTETH_RPC_HOST") {
+            self.rpc.host = host;
+        }
+        if let Some(port) = parse_env(&lookup, "RUSTETH_RPC_PORT")? {
+            self.rpc.port = port;
+        }
+        if let Some(chain_id) = parse_env(&lookup, "RUSTETH_CHAIN_ID")? {
+            self.chain.chain_id = chain_id;
+        }
+        if let Some(endpoint) = lookup("RUSTETH_BEACON_ENDPOINT") {
+            self.beacon.endpoint = Some(endpoint);
+        }
+        if let Some(enabled) = parse_env(&lookup, "RUSTETH_DISCOVERY_ENABLED")? {
+            self.discovery.enabled = enabled;
+        }
+        if let Some(port) = parse_env(&lookup, "RUSTETH_DISCOVERY_PORT")? {
+            self.discovery.port = port;
+        }
+        Ok(())
+    }
+}
+
+fn parse_env<T: FromStr>(
+    lookup: &impl Fn(&str) -> Option<String>,
+    name: &str,
+) -> Result<Option<T>, NodeConfigError> {
+    lookup(name)
+        .map(|value| {
+            value.parse().map_err(|_| NodeConfigError::InvalidEnv {
+                name: name.to_string(),
+                value,
+            })
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use crate::domain::rpc_config::Namespace;
+
+    const SAMPLE: &str = r#"
+        [rpc]
+        host = "0.0.0.0"
+        port = 9545
+        max_log_results = 500
+        max_bundle_size = 20
+        namespaces = ["eth", "net", "web3"]
+        max_requests_per_connection = 16
+        connection_overflow = "queue"
+
+        [chain]
+        chain_id = 11155111
+
+        [beacon]
+        endpoint = "http://localhost:5052"
+
+        [discovery]
+        enabled = true
+        port = 30304
+        bootnodes = ["enr:-abc"]
+        node_key_path = "/var/lib/rusteth/nodekey"
+
+        [tx_pool]
+        max_pending = 100
+        max_queued = 10
+        price_bump_percent = 125
+    "#;
+
+    #[test]
+    fn test_parse_sample_toml() {
+        let config = NodeConfig::from_toml_str(SAMPLE).unwrap();
+
+        assert_eq!(config.rpc.host, "0.0.0.0");
+        assert_eq!(config.rpc.port, 9545);
+        assert_eq!(config.rpc.max_log_results, 500);
+        assert_eq!(config.rpc.max_bundle_size, 20);
+        assert_eq!(
+            config.rpc.namespaces,
+            NamespaceConfig::only([Namespace::Eth, Namespace::Net, Namespace::Web3])
+        );
+        assert_eq!(config.rpc.max_requests_per_connection, 16);
+        assert_eq!(config.rpc.connection_overflow, ConnectionOverflow::Queue);
+        assert_eq!(config.chain.chain_id, 11155111);
+        assert_eq!(config.beacon.endpoint.as_deref(), Some("http://localhost:5052"));
+        assert!(config.discovery.enabled);
+        assert_eq!(config.discovery.port, 30304);
+        assert_eq!(config.discovery.bootnodes, vec!["enr:-abc".to_string()]);
+        assert_eq!(
+            config.discovery.node_key_path,
+            Some(PathBuf::from("/var/lib/rusteth/nodekey"))
+        );
+        assert_eq!(config.tx_pool.max_pending, 100);
+        assert_eq!(config.tx_pool.max_queued, 10);
+        assert_eq!(config.tx_pool.price_bump_percent, 125);
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let config = NodeConfig::from_toml_str("[rpc]\nport = 8546\n").unwrap();
+
+        assert_eq!(config.rpc.host, "127.0.0.1");
+        assert_eq!(config.rpc.port, 8546);
+        assert_eq!(config.chain, ChainConfig::default());
+        assert_eq!(config.tx_pool.max_pending, TxPoolConfig::default().max_pending);
+
+        // 拼写错误的字段应报错，而不是静默忽略
+        assert!(NodeConfig::from_toml_str("[rpc]\nprot = 1\n").is_err());
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let env: HashMap<&str, &str> = [("RUSTETH_RPC_PORT", "18545"), ("RUSTETH_CHAIN_ID", "5")].into();
+        let mut config = NodeConfig::from_toml_str(SAMPLE).unwrap();
+        config
+            .apply_env_overrides(|name| env.get(name).map(|v| v.to_string()))
+            .unwrap();
+
+        assert_eq!(config.rpc.port, 18545);
+        assert_eq!(config.rpc.host, "0.0.0.0");
+        assert_eq!(config.chain.chain_id, 5);
+
+        let err = config
+            .apply_env_overrides(|name| (name == "RUSTETH_RPC_PORT").then(|| "abc".to_string()))
+            .unwrap_err();
+        assert!(matches!(err, NodeConfigError::InvalidEnv { .. }));
+    }
+}
@@ -0,0 +1,129 @@
+//! 节点后台任务句柄 - 统一的关闭与清理
+//!
+//! 启动时把后台任务（HTTP 服务器等）登记到 `NodeHandle`，每个任务拿到一个
+//! `ShutdownSignal`。`shutdown()` 广播关闭信号，在宽限期内等待任务自行退出，
+//! 超时仍未退出的任务被强制 abort，保证关闭流程有确定的上限。
+//!
+//! 交易池 Actor 在所有 `TxPoolHandle` 被丢弃后自行退出，无需登记。
+
+use std::future::Future;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+/// 默认关闭宽限期
+pub const DEFAULT_SHUTDOWN_GRACE: Duration = Duration::from_secs(5);
+
+/// 关闭信号（每个后台任务持有一个）
+pub struct ShutdownSignal(broadcast::Receiver<()>);
+
+impl ShutdownSignal {
+    /// 等待关闭信号；`NodeHandle` 被丢弃时同样返回
+    pub async fn recv(mut self) {
+        let _ = self.0.recv().await;
+    }
+}
+
+/// 关闭结果
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ShutdownReport {
+    /// 宽限期内自行退出的任务
+    pub stopped: Vec<&'static str>,
+    /// 超过宽限期被强制终止的任务
+    pub aborted: Vec<&'static str>,
+}
+
+/// 节点后台任务句柄
+pub struct NodeHandle {
+    shutdown: broadcast::Sender<()>,
+    tasks: Vec<(&'static str, JoinHandle<()>)>,
+    grace: Duration,
+}
+
+impl NodeHandle {
+    /// 创建句柄，`grace` 为关闭时等待任务退出的总时长
+    pub fn new(grace: Duration) -> Self {
+        let (shutdown, _) = broadcast::channel(1);
+        Self {
+            shutdown,
+            tasks: Vec::new(),
+            grace,
+        }
+    }
+
+    /// 启动并登记后台任务
+    pub fn spawn<F, Fut>(&mut self, name: &'static str, task: F)
+    where
+        F: FnOnce(ShutdownSignal) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let signal = ShutdownSignal(self.shutdown.subscribe());
+        self.tasks.push((name, tokio::spawn(task(signal))));
+    }
+
+    /// 已登记的任务数
+    pub fn task_count(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// 广播关闭信号并等待所有任务退出，超过宽限期的任务被 abort
+    pub async fn shutdown(self) -> ShutdownReport {
+        let _ = self.shutdown.send(());
+        let deadline = Instant::now() + self.grace;
+
+        let mut report = ShutdownReport::default();
+        for (name, mut task) in self.tasks {
+            match tokio::time::timeout_at(deadline, &mut task).await {
+                Ok(result) => {
+                    if let Err(e) = result {
+                        tracing::warn!(task = name, error = %e, "后台任务异常退出");
+                    }
+                    report.stopped.push(name);
+                }
+                Err(_) => {
+                    task.abort();
+                    let _ = task.await;
+                    tracing::warn!(task = name, "后台任务未在宽限期内退出，已强制终止");
+                    report.aborted.push(name);
+                }
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_cooperative_tasks_stop() {
+        let mut node = NodeHandle::new(Duration::from_secs(1));
+        node.spawn("a", |signal| signal.recv());
+        node.spawn("b", |signal| async move {
+            signal.recv().await;
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        });
+
+        let report = node.shutdown().await;
+        assert_eq!(report.stopped, vec!["a", "b"]);
+        assert!(report.aborted.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_straggler_is_aborted_within_grace() {
+        let grace = Duration::from_millis(100);
+        let mut node = NodeHandle::new(grace);
+        node.spawn("cooperative", |signal| signal.recv());
+        // 忽略关闭信号的任务
+        node.spawn("straggler", |_signal| std::future::pending());
+
+        let started = std::time::Instant::now();
+        let report = node.shutdown().await;
+
+        assert!(started.elapsed() < grace * 5);
+        assert_eq!(report.stopped, vec!["cooperative"]);
+        assert_eq!(report.aborted, vec!["straggler"]);
+    }
+}
@@ -0,0 +1,178 @@
+//! 节点身份 - 持久化的节点私钥与 ENR
+//!
+//! 节点私钥以十六进制保存在 `DiscoveryConfig::node_key_path`（不存在时生成并写入），
+//! 重启后 node id 不变，对端积累的信誉得以保留。上次签发的 ENR 保存在同目录的
+//! `<node_key_path>.enr`：记录内容未变时沿用原序列号，变化时（如端口变更）序列号加一，
+//! 保证对端总能用新记录替换旧记录（EIP-778）。
+//! 未配置路径时使用每次启动随机生成的临时身份。
+
+use crate::infrastructure::node_config::DiscoveryConfig;
+use discv5::enr::{CombinedKey, Enr, EnrKey, NodeId};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// 节点身份加载错误
+#[derive(Debug, Error)]
+pub enum NodeIdentityError {
+    /// 读写密钥或 ENR 文件失败
+    #[error("读写 {path} 失败: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+
+    /// 密钥文件内容不是合法的 secp256k1 私钥
+    #[error("节点私钥无效: {0}")]
+    InvalidKey(String),
+
+    /// ENR 签发失败
+    #[error("ENR 构建失败: {0}")]
+    Enr(String),
+}
+
+/// 节点身份（私钥 + 当前 ENR）
+pub struct NodeIdentity {
+    key: CombinedKey,
+    enr: Enr<CombinedKey>,
+}
+
+impl NodeIdentity {
+    /// 按发现配置加载身份：配置了 `node_key_path` 时持久化，否则为临时身份
+    pub fn from_config(config: &DiscoveryConfig) -> Result<Self, NodeIdentityError> {
+        match &config.node_key_path {
+            Some(path) => Self::load_or_create(path, config.port),
+            None => Self::generate(config.port),
+        }
+    }
+
+    /// 生成临时身份（不落盘）
+    pub fn generate(udp_port: u16) -> Result<Self, NodeIdentityError> {
+        let key = CombinedKey::generate_secp256k1();
+        let enr = build_enr(&key, 1, udp_port)?;
+        Ok(Self { key, enr })
+    }
+
+    /// 从 `key_path` 加载私钥（不存在时生成并写入），并签发监听 `udp_port` 的 ENR
+    pub fn load_or_create(key_path: &Path, udp_port: u16) -> Result<Self, NodeIdentityError> {
+        let key = load_or_create_key(key_path)?;
+
+        let enr_path = enr_path(key_path);
+        let previous = fs::read_to_string(&enr_path)
+            .ok()
+            .and_then(|text| text.trim().parse::<Enr<CombinedKey>>().ok())
+            .filter(|enr| enr.public_key() == key.public());
+
+        let enr = match previous {
+            Some(previous) if previous.udp4() == Some(udp_port) => previous,
+            previous => {
+                let seq = previous.map_or(1, |enr| enr.seq() + 1);
+                let enr = build_enr(&key, seq, udp_port)?;
+                fs::write(&enr_path, enr.to_base64())
+                    .map_err(|source| io_error(&enr_path, source))?;
+                enr
+            }
+        };
+        Ok(Self { key, enr })
+    }
+
+    pub fn key(&self) -> &CombinedKey {
+        &self.key
+    }
+
+    pub fn enr(&self) -> &Enr<CombinedKey> {
+        &self.enr
+    }
+
+    pub fn node_id(&self) -> NodeId {
+        self.enr.node_id()
+    }
+}
+
+fn build_enr(
+    key: &CombinedKey,
+    seq: u64,
+    udp_port: u16,
+) -> Result<Enr<CombinedKey>, NodeIdentityError> {
+    Enr::builder()
+        .seq(seq)
+        .udp4(udp_port)
+        .build(key)
+        .map_err(|err| NodeIdentityError::Enr(err.to_string()))
+}
+
+fn load_or_create_key(path: &Path) -> Result<CombinedKey, NodeIdentityError> {
+    match fs::read_to_string(path) {
+        Ok(text) => {
+            let mut bytes = hex::decode(text.trim().trim_start_matches("0x"))
+                .map_err(|err| NodeIdentityError::InvalidKey(err.to_string()))?;
+            CombinedKey::secp256k1_from_bytes(&mut bytes)
+                .map_err(|err| NodeIdentityError::InvalidKey(err.to_string()))
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            let key = CombinedKey::generate_secp256k1();
+            write_private(path, hex::encode(key.encode()).as_bytes())?;
+            Ok(key)
+        }
+        Err(source) => Err(io_error(path, source)),
+    }
+}
+
+/// 写入私钥文件，Unix 下仅所有者可读写
+fn write_private(path: &Path, contents: &[u8]) -> Result<(), NodeIdentityError> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(path)
+        .and_then(|mut file| file.write_all(contents))
+        .map_err(|source| io_error(path, source))
+}
+
+fn enr_path(key_path: &Path) -> PathBuf {
+    let mut path = key_path.as_os_str().to_owned();
+    path.push(".enr");
+    PathBuf::from(path)
+}
+
+fn io_error(path: &Path, source: std::io::Error) -> NodeIdentityError {
+    NodeIdentityError::Io {
+        path: path.display().to_string(),
+        source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identity_is_stable_across_restarts() {
+        let dir = std::env::temp_dir().join(format!("rusteth-nodekey-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let key_path = dir.join("nodekey");
+
+        let first = NodeIdentity::load_or_create(&key_path, 30303).unwrap();
+        let second = NodeIdentity::load_or_create(&key_path, 30303).unwrap();
+        assert_eq!(first.node_id(), second.node_id());
+        // 记录未变，沿用原序列号
+        assert_eq!(second.enr().seq(), first.enr().seq());
+
+        // 端口变化：node id 不变，序列号递增
+        let third = NodeIdentity::load_or_create(&key_path, 30304).unwrap();
+        assert_eq!(third.node_id(), first.node_id());
+        assert_eq!(third.enr().seq(), first.enr().seq() + 1);
+        assert_eq!(third.enr().udp4(), Some(30304));
+
+        // 未持久化的身份每次不同
+        let ephemeral = NodeIdentity::generate(30303).unwrap();
+        assert_ne!(ephemeral.node_id(), first.node_id());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
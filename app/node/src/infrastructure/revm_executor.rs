@@ -0,0 +1,539 @@
+//! 基于 revm 的调用执行器
+//!
+//! 账户代码按区块从仓储读取（见 MockEthereumRepository::code_at），
+//! 存储槽暂按零值处理；调用者余额由执行环境注入。
+//! 硬分叉规则、固有 gas 与操作码 gas 覆盖取自执行环境中的 `GasSchedule`。
+//! 执行环境带截止时间时，由 `DeadlineInspector` 在执行中途中止超时的调用。
+
+use crate::domain::command_types::{CallRequest, StateOverride};
+use crate::infrastructure::mock_repository::MockEthereumRepository;
+use crate::service::call_executor_trait::{
+    CallEnv, CallExecutor, CallOutcome, EvmFork, GasSchedule,
+};
+use ethereum_types::{H160, H256, U256, U64};
+use revm::{
+    db::CacheDB,
+    handler::register::EvmHandler,
+    inspector_handle_register,
+    interpreter::{opcode, InstructionResult, Interpreter},
+    primitives::{
+        AccountInfo, Address, Bytecode, Bytes, Env, ExecutionResult, InvalidTransaction, SpecId,
+        TransactTo, B256, U256 as RU256,
+    },
+    Database, DatabaseCommit, DatabaseRef, Evm, EvmContext, Inspector,
+};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// 把 gas 转发给子调用的操作码，操作码 gas 覆盖对其不生效
+const FRAME_OPCODES: [u8; 6] = [
+    opcode::CALL,
+    opcode::CALLCODE,
+    opcode::DELEGATECALL,
+    opcode::STATICCALL,
+    opcode::CREATE,
+    opcode::CREATE2,
+];
+
+/// 每执行多少条指令读取一次时钟
+const DEADLINE_CHECK_INTERVAL: u64 = 1024;
+
+/// 执行截止时间检查
+///
+/// 到期后每条指令执行前都把当前帧置为异常终止：子调用被中止后，
+/// 父帧继续执行的下一条指令同样被中止，整个调用随之结束
+struct DeadlineInspector {
+    deadline: Option<Instant>,
+    steps: u64,
+    expired: bool,
+}
+
+impl DeadlineInspector {
+    fn new(deadline: Option<Instant>) -> Self {
+        Self {
+            deadline,
+            steps: 0,
+            expired: false,
+        }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for DeadlineInspector {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        if !self.expired {
+            let Some(deadline) = self.deadline else {
+                return;
+            };
+            self.steps += 1;
+            if !self.steps.is_multiple_of(DEADLINE_CHECK_INTERVAL) || Instant::now() < deadline {
+                return;
+            }
+            self.expired = true;
+        }
+        interp.instruction_result = InstructionResult::OutOfGas;
+    }
+}
+
+/// revm 调用执行器
+#[derive(Clone)]
+pub struct RevmCallExecutor {
+    repo: MockEthereumRepository,
+}
+
+impl RevmCallExecutor {
+    pub fn new(repo: MockEthereumRepository) -> Self {
+        Self { repo }
+    }
+
+    /// 指定区块上的空缓存层，执行中的状态修改只写入缓存
+    fn cache_db(&self, block_number: U64) -> CacheDB<RepositoryDb<'_>> {
+        CacheDB::new(RepositoryDb {
+            repo: &self.repo,
+            block_number,
+        })
+    }
+}
+
+/// 仓储在指定区块上的只读视图（revm 数据库适配器）
+#[derive(Clone)]
+struct RepositoryDb<'a> {
+    repo: &'a MockEthereumRepository,
+    block_number: U64,
+}
+
+impl DatabaseRef for RepositoryDb<'_> {
+    type Error = Infallible;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let code = self
+            .repo
+            .code_at(&H160::from_slice(address.as_slice()), self.block_number);
+        if code.is_empty() {
+            return Ok(None);
+        }
+        let bytecode = Bytecode::new_raw(Bytes::from(code));
+        Ok(Some(AccountInfo::new(RU256::ZERO, 1, bytecode.hash_slow(), bytecode)))
+    }
+
+    fn code_by_hash_ref(&self, _code_hash: B256) -> Result<Bytecode, Self::Error> {
+        // basic_ref 总是携带代码，revm 不会按哈希回查
+        Ok(Bytecode::default())
+    }
+
+    fn storage_ref(&self, _address: Address, _index: RU256) -> Result<RU256, Self::Error> {
+        Ok(RU256::ZERO)
+    }
+
+    fn block_hash_ref(&self, _number: u64) -> Result<B256, Self::Error> {
+        Ok(B256::ZERO)
+    }
+}
+
+/// 将状态覆盖写入本次执行的缓存层（不影响仓储）
+fn apply_state_override(db: &mut CacheDB<RepositoryDb<'_>>, state_override: &StateOverride) {
+    for (address, account) in state_override {
+        let address = to_address(*address);
+        let mut info = db.basic_ref(address).ok().flatten().unwrap_or_default();
+        if let Some(balance) = account.balance {
+            info.balance = to_u256(balance);
+        }
+        if let Some(nonce) = account.nonce {
+            info.nonce = nonce.as_u64();
+        }
+        if let Some(code) = &account.code {
+            let bytecode = Bytecode::new_raw(Bytes::from(code.clone()));
+            info.code_hash = bytecode.hash_slow();
+            info.code = Some(bytecode);
+        }
+        db.insert_account_info(address, info);
+
+        // state 整体替换账户存储；stateDiff 只覆盖指定槽位
+        let result = match (&account.state, &account.state_diff) {
+            (Some(state), _) => db.replace_account_storage(address, to_storage(state)),
+            (None, Some(diff)) => to_storage(diff)
+                .into_iter()
+                .try_for_each(|(slot, value)| db.insert_account_storage(address, slot, value)),
+            (None, None) => Ok(()),
+        };
+        let Ok(()) = result;
+    }
+}
+
+fn spec_id(fork: EvmFork) -> SpecId {
+    match fork {
+        EvmFork::Berlin => SpecId::BERLIN,
+        EvmFork::London => SpecId::LONDON,
+        EvmFork::Shanghai => SpecId::SHANGHAI,
+        EvmFork::Cancun => SpecId::CANCUN,
+    }
+}
+
+/// 将计费表中的覆盖项注册到 revm：替换固有 gas 计算，并包装被覆盖的操作码
+///
+/// 被覆盖的操作码照常执行，随后按实际消耗与目标 gas 的差值补扣或返还
+fn apply_gas_schedule<EXT, DB: Database>(
+    handler: &mut EvmHandler<'_, EXT, DB>,
+    schedule: &GasSchedule,
+) {
+    let intrinsic = schedule.intrinsic;
+    handler.validation.initial_tx_gas = Arc::new(move |env: &Env| {
+        // CallRequest 不携带访问列表
+        let gas = intrinsic.intrinsic_gas(&env.tx.data, env.tx.transact_to.is_create(), &[]);
+        if gas > env.tx.gas_limit {
+            return Err(InvalidTransaction::CallGasCostMoreThanGasLimit.into());
+        }
+        Ok(gas)
+    });
+
+    let mut table = handler.take_instruction_table();
+    let instructions = table.to_boxed();
+    for (&op, &cost) in &schedule.opcode_costs {
+        if FRAME_OPCODES.contains(&op) {
+            continue;
+        }
+        let original = std::mem::replace(&mut instructions[op as usize], Box::new(|_, _| {}));
+        instructions[op as usize] = Box::new(move |interpreter: &mut Interpreter, host| {
+            let before = interpreter.gas.remaining();
+            original(interpreter, host);
+            if interpreter.instruction_result.is_error() {
+                return;
+            }
+            let spent = before - interpreter.gas.remaining();
+            if cost < spent {
+                interpreter.gas.erase_cost(spent - cost);
+            } else if !interpreter.gas.record_cost(cost - spent) {
+                interpreter.instruction_result = InstructionResult::OutOfGas;
+            }
+        });
+    }
+    handler.set_instruction_table(table);
+}
+
+fn to_storage(slots: &HashMap<H256, H256>) -> revm::primitives::HashMap<RU256, RU256> {
+    slots
+        .iter()
+        .map(|(slot, value)| {
+            (
+                RU256::from_be_bytes(slot.to_fixed_bytes()),
+                RU256::from_be_bytes(value.to_fixed_bytes()),
+            )
+        })
+        .collect()
+}
+
+fn to_address(address: H160) -> Address {
+    Address::from_slice(address.as_bytes())
+}
+
+fn to_u256(value: U256) -> RU256 {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    RU256::from_be_bytes(bytes)
+}
+
+/// 在缓存层上执行单个调用；`commit` 为 true 时把状态修改写回缓存层
+fn transact(
+    db: &mut CacheDB<RepositoryDb<'_>>,
+    call: &CallRequest,
+    gas_limit: u64,
+    env: &CallEnv,
+    commit: bool,
+) -> CallOutcome {
+    let caller = to_address(call.from.unwrap_or_default());
+
+    // 模拟执行不收取 base fee（与 geth eth_call/eth_estimateGas 一致）
+    let (gas_price, priority_fee) = if env.gas_free {
+        (U256::zero(), None)
+    } else {
+        (
+            call.max_fee_per_gas.or(call.gas_price).unwrap_or_default(),
+            call.max_priority_fee_per_gas,
+        )
+    };
+    let mut evm = Evm::builder()
+        .with_db(&mut *db)
+        .with_external_context(DeadlineInspector::new(env.deadline))
+        .with_spec_id(spec_id(env.gas_schedule.fork))
+        .modify_cfg_env(|cfg| cfg.disable_balance_check = env.gas_free)
+        .modify_block_env(|block| {
+            block.number = RU256::from(env.block_number.as_u64());
+            block.gas_limit = RU256::from(env.block_gas_limit.max(gas_limit));
+            block.basefee = RU256::ZERO;
+        })
+        .modify_tx_env(|tx| {
+            tx.caller = caller;
+            tx.transact_to = match call.to {
+                Some(to) => TransactTo::Call(to_address(to)),
+                None => TransactTo::Create,
+            };
+            tx.value = to_u256(call.value.unwrap_or_default());
+            tx.data = Bytes::from(call.data.clone().unwrap_or_default());
+            tx.gas_limit = gas_limit;
+            tx.gas_price = to_u256(gas_price);
+            tx.gas_priority_fee = priority_fee.map(to_u256);
+            tx.nonce = None;
+        })
+        .append_handler_register(inspector_handle_register)
+        .append_handler_register_box(Box::new(|handler| {
+            apply_gas_schedule(handler, &env.gas_schedule)
+        }))
+        .build();
+
+    let result = evm.transact();
+    let timed_out = evm.context.external.expired;
+    drop(evm);
+
+    // 被中止的执行结果不可信，也不写回缓存层
+    if timed_out {
+        return CallOutcome::TimedOut;
+    }
+    match result {
+        Ok(result) => {
+            if commit {
+                db.commit(result.state);
+            }
+            match result.result {
+                ExecutionResult::Success { gas_used, output, .. } => CallOutcome::Success {
+                    gas_used,
+                    output: output.into_data().to_vec(),
+                },
+                ExecutionResult::Revert { gas_used, output } => CallOutcome::Revert {
+                    gas_used,
+                    output: output.to_vec(),
+                },
+                ExecutionResult::Halt { gas_used, reason } => CallOutcome::Halt {
+                    gas_used,
+                    reason: format!("{:?}", reason),
+                },
+            }
+        }
+        Err(err) => CallOutcome::Invalid(err.to_string()),
+    }
+}
+
+impl CallExecutor for RevmCallExecutor {
+    fn execute(&self, call: &CallRequest, gas_limit: u64, env: &CallEnv) -> CallOutcome {
+        let caller = to_address(call.from.unwrap_or_default());
+        let mut db = self.cache_db(env.block_number);
+        let mut caller_info = db.basic_ref(caller).ok().flatten().unwrap_or_default();
+        caller_info.balance = to_u256(env.caller_balance);
+        db.insert_account_info(caller, caller_info);
+        apply_state_override(&mut db, &env.state_override);
+
+        transact(&mut db, call, gas_limit, env, false)
+    }
+
+    fn execute_bundle(
+        &self,
+        calls: &[(CallRequest, u64)],
+        env: &CallEnv,
+        isolated: bool,
+    ) -> Vec<CallOutcome> {
+        // 状态快照只构建一次；隔离模式下每个调用在快照的副本上执行
+        let mut db = self.cache_db(env.block_number);
+        apply_state_override(&mut db, &env.state_override);
+
+        calls
+            .iter()
+            .map(|(call, gas_limit)| {
+                if isolated {
+                    transact(&mut db.clone(), call, *gas_limit, env, false)
+                } else {
+                    transact(&mut db, call, *gas_limit, env, true)
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::command_types::AccountOverride;
+
+    fn env() -> CallEnv {
+        CallEnv {
+            block_number: U64::zero(),
+            block_gas_limit: 30_000_000,
+            caller_balance: U256::from(1_000_000_000_000_000_000u64),
+            state_override: Default::default(),
+            gas_free: false,
+            gas_schedule: GasSchedule::default(),
+            deadline: None,
+        }
+    }
+
+    fn call_to(to: H160, data: Vec<u8>) -> CallRequest {
+        CallRequest {
+            from: Some(H160::from_low_u64_be(1)),
+            to: Some(to),
+            gas: None,
+            gas_price: None,
+            value: None,
+            data: Some(data),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+        }
+    }
+
+    #[test]
+    fn test_execute_returns_contract_output() {
+        let repo = MockEthereumRepository::new();
+        let contract = H160::from_low_u64_be(0xc0de);
+        // PUSH1 42 PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+        repo.set_code(
+            contract,
+            U64::zero(),
+            vec![0x60, 0x2a, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3],
+        );
+
+        let outcome = RevmCallExecutor::new(repo).execute(&call_to(contract, vec![]), 100_000, &env());
+        match outcome {
+            CallOutcome::Success { gas_used, output } => {
+                assert!(gas_used > 21_000);
+                assert_eq!(output[31], 42);
+            }
+            other => panic!("unexpected outcome: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_execute_with_custom_gas_schedule() {
+        let repo = MockEthereumRepository::new();
+        let contract = H160::from_low_u64_be(0xc0de);
+        // PUSH1 42 PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+        repo.set_code(
+            contract,
+            U64::zero(),
+            vec![0x60, 0x2a, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3],
+        );
+        let executor = RevmCallExecutor::new(repo);
+        let gas_used = |env: &CallEnv| {
+            match executor.execute(&call_to(contract, vec![]), 100_000, env) {
+                CallOutcome::Success { gas_used, .. } => gas_used,
+                other => panic!("unexpected outcome: {:?}", other),
+            }
+        };
+
+        // MSTORE 主网计费：3 + 首个字的内存扩展 3
+        let mut schedule = GasSchedule::default().with_opcode_cost(opcode::MSTORE, 1_006);
+        schedule.intrinsic.tx_gas = 30_000;
+        let custom = CallEnv {
+            gas_schedule: schedule,
+            ..env()
+        };
+
+        assert_eq!(gas_used(&custom), gas_used(&env()) + 1_000 + 9_000);
+
+        // 固有 gas 按自定义计费表校验
+        let outcome = executor.execute(&call_to(contract, vec![]), 25_000, &custom);
+        assert!(matches!(outcome, CallOutcome::Invalid(_)));
+    }
+
+    #[test]
+    fn test_execute_bundle_stateful_and_isolated() {
+        let repo = MockEthereumRepository::new();
+        let counter = H160::from_low_u64_be(0xc0c0);
+        // 槽 0 加一并返回新值：
+        // PUSH1 0 SLOAD PUSH1 1 ADD DUP1 PUSH1 0 SSTORE PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+        repo.set_code(
+            counter,
+            U64::zero(),
+            vec![
+                0x60, 0x00, 0x54, 0x60, 0x01, 0x01, 0x80, 0x60, 0x00, 0x55, 0x60, 0x00, 0x52,
+                0x60, 0x20, 0x60, 0x00, 0xf3,
+            ],
+        );
+        let executor = RevmCallExecutor::new(repo);
+        let calls = vec![(call_to(counter, vec![]), 100_000); 2];
+        let outputs = |isolated| -> Vec<u8> {
+            executor
+                .execute_bundle(&calls, &env(), isolated)
+                .into_iter()
+                .map(|outcome| match outcome {
+                    CallOutcome::Success { output, .. } => output[31],
+                    other => panic!("unexpected outcome: {:?}", other),
+                })
+                .collect()
+        };
+
+        // 第二个调用读到第一个调用写入的值
+        assert_eq!(outputs(false), vec![1, 2]);
+        // 每个调用都从原始状态开始
+        assert_eq!(outputs(true), vec![1, 1]);
+    }
+
+    #[test]
+    fn test_execute_stops_at_deadline() {
+        let repo = MockEthereumRepository::new();
+        let contract = H160::from_low_u64_be(0x100f);
+        // 死循环：JUMPDEST PUSH1 0 JUMP
+        repo.set_code(contract, U64::zero(), vec![0x5b, 0x60, 0x00, 0x56]);
+        let executor = RevmCallExecutor::new(repo);
+        let call = call_to(contract, vec![]);
+
+        // 无截止时间：耗尽 gas 后异常终止
+        let outcome = executor.execute(&call, 1_000_000, &env());
+        assert!(matches!(outcome, CallOutcome::Halt { .. }), "{:?}", outcome);
+
+        // 截止时间已过：执行中途被中止，不会跑完 30M gas
+        let expired = CallEnv {
+            deadline: Some(Instant::now()),
+            ..env()
+        };
+        let started = Instant::now();
+        assert_eq!(executor.execute(&call, 30_000_000, &expired), CallOutcome::TimedOut);
+        assert!(started.elapsed() < std::time::Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_execute_below_intrinsic_gas_is_invalid() {
+        let repo = MockEthereumRepository::new();
+        let outcome = RevmCallExecutor::new(repo).execute(
+            &call_to(H160::from_low_u64_be(0x1234), vec![]),
+            20_000,
+            &env(),
+        );
+        assert!(matches!(outcome, CallOutcome::Invalid(_)));
+    }
+
+    #[test]
+    fn test_execute_revert() {
+        let repo = MockEthereumRepository::new();
+        let contract = H160::from_low_u64_be(0xdead);
+        // PUSH1 0 PUSH1 0 REVERT
+        repo.set_code(contract, U64::zero(), vec![0x60, 0x00, 0x60, 0x00, 0xfd]);
+
+        let outcome = RevmCallExecutor::new(repo).execute(&call_to(contract, vec![]), 100_000, &env());
+        assert!(matches!(outcome, CallOutcome::Revert { .. }));
+    }
+
+    #[test]
+    fn test_execute_with_state_override() {
+        let repo = MockEthereumRepository::new();
+        let contract = H160::from_low_u64_be(0xbeef);
+        let slot = H256::zero();
+        // PUSH1 0 SLOAD PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+        let code = vec![0x60, 0x00, 0x54, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3];
+
+        let mut env = env();
+        env.state_override.insert(
+            contract,
+            AccountOverride {
+                code: Some(code),
+                state_diff: Some(HashMap::from([(slot, H256::from_low_u64_be(7))])),
+                ..Default::default()
+            },
+        );
+
+        let outcome = RevmCallExecutor::new(repo.clone()).execute(&call_to(contract, vec![]), 100_000, &env);
+        match outcome {
+            CallOutcome::Success { output, .. } => assert_eq!(output[31], 7),
+            other => panic!("unexpected outcome: {:?}", other),
+        }
+        // 覆盖只作用于本次执行
+        assert!(repo.code_at(&contract, U64::zero()).is_empty());
+    }
+}
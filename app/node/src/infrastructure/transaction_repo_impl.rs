@@ -1,15 +1,24 @@
-/// 交易池内存实现
-/// 采用Erlang风格的无状态设计：服务与状态分离
+//! 交易池内存实现
+//! 采用Erlang风格的无状态设计：服务与状态分离
 
+use crate::domain::block_types::Block;
 use crate::domain::tx_types::DynamicFeeTx;
-use crate::service::repo::transaction_repo::{TxPool, TxPoolError, TxPoolStats};
+use crate::infrastructure::clock_impl::SystemClock;
+use crate::service::clock::Clock;
+use crate::service::repo::transaction_repo::{PendingTx, TxPool, TxPoolError, TxPoolStats};
+use crate::service::transaction_validator::AccountStateProvider;
 use async_trait::async_trait;
+use serde::Deserialize;
 use ethereum_types::{Address, H256, U256};
 use std::collections::{BTreeMap, HashMap};
 use std::sync::{Arc, RwLock};
+use std::time::Instant;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
 
 /// 交易池配置
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct TxPoolConfig {
     /// 最大交易数
     pub max_pending: usize,
@@ -29,23 +38,43 @@ impl Default for TxPoolConfig {
     }
 }
 
+/// 新链头上的账户状态（交易池重新验证用）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountState {
+    pub nonce: u64,
+    pub balance: U256,
+}
+
 /// 交易池状态（独立的数据结构）
 /// 遵循Erlang风格：状态与行为分离
-struct TxPoolState {
+///
+/// 所有操作都是作用于状态的同步函数，由持有者决定并发方式：
+/// TxPoolImpl 通过 RwLock 共享，TxPoolActor 在单一任务中独占。
+pub(crate) struct TxPoolState {
     /// 所有交易映射 hash -> (tx, sender)
     transactions: HashMap<H256, (DynamicFeeTx, Address)>,
     /// Pending交易：按sender分组，每个sender的交易按nonce排序
     pending: HashMap<Address, BTreeMap<u64, H256>>,
     /// Queued交易：按sender分组
     queued: HashMap<Address, BTreeMap<u64, H256>>,
+    /// 入池时间 hash -> arrival（相对 epoch 的纳秒数，严格递增）
+    arrivals: HashMap<H256, u64>,
+    /// 入池时间的基准时刻
+    epoch: Instant,
+    /// 最近一笔交易的入池时间
+    last_arrival: u64,
 }
 
 impl TxPoolState {
-    fn new() -> Self {
+    /// 创建空状态，`epoch` 为入池时间的基准时刻
+    pub(crate) fn new(epoch: Instant) -> Self {
         Self {
             transactions: HashMap::new(),
             pending: HashMap::new(),
             queued: HashMap::new(),
+            arrivals: HashMap::new(),
+            epoch,
+            last_arrival: 0,
         }
     }
 
@@ -60,58 +89,30 @@ impl TxPoolState {
     fn queued_count(&self) -> usize {
         self.queued.values().map(|txs| txs.len()).sum()
     }
-}
-
-/// 交易池实现（无状态服务）
-/// 所有状态存储在Arc<RwLock<TxPoolState>>中
-#[derive(Clone)]
-pub struct TxPoolImpl {
-    config: TxPoolConfig,
-    state: Arc<RwLock<TxPoolState>>,
-}
-
-impl TxPoolImpl {
-    pub fn new(config: TxPoolConfig) -> Self {
-        Self {
-            config,
-            state: Arc::new(RwLock::new(TxPoolState::new())),
-        }
-    }
-
-    /// 计算交易哈希
-    ///
-    /// 使用 DynamicFeeTx::hash() 方法计算标准的 EIP-1559 交易哈希
-    /// hash = keccak256(0x02 || rlp([...]))
-    fn compute_tx_hash(&self, tx: &DynamicFeeTx) -> H256 {
-        tx.hash()
-    }
 
     /// 检查是否需要价格提升（替换交易）
-    fn needs_price_bump(&self, old_tx: &DynamicFeeTx, new_tx: &DynamicFeeTx) -> bool {
+    fn needs_price_bump(config: &TxPoolConfig, old_tx: &DynamicFeeTx, new_tx: &DynamicFeeTx) -> bool {
         let old_price = old_tx.max_fee_per_gas;
-        let required_price = old_price * U256::from(self.config.price_bump_percent) / U256::from(100);
+        let required_price = old_price * U256::from(config.price_bump_percent) / U256::from(100);
         new_tx.max_fee_per_gas >= required_price
     }
-}
 
-impl Default for TxPoolImpl {
-    fn default() -> Self {
-        Self::new(TxPoolConfig::default())
-    }
-}
-
-#[async_trait]
-impl TxPool for TxPoolImpl {
-    async fn add(&self, tx: DynamicFeeTx, sender: Address) -> Result<H256, TxPoolError> {
-        let tx_hash = self.compute_tx_hash(&tx);
+    pub(crate) fn add(
+        &mut self,
+        config: &TxPoolConfig,
+        tx: DynamicFeeTx,
+        sender: Address,
+        now: Instant,
+    ) -> Result<H256, TxPoolError> {
+        // 使用 DynamicFeeTx::hash() 计算标准的 EIP-1559 交易哈希
+        // hash = keccak256(0x02 || rlp([...]))
+        let tx_hash = tx.hash();
         let nonce = tx.nonce.as_u64();
 
-        let mut state = self.state.write().unwrap();
-
         // 检查是否已存在
-        if let Some((existing_tx, _)) = state.transactions.get(&tx_hash) {
+        if let Some((existing_tx, _)) = self.transactions.get(&tx_hash) {
             // 如果是替换交易，检查价格提升
-            if !self.needs_price_bump(existing_tx, &tx) {
+            if !Self::needs_price_bump(config, existing_tx, &tx) {
                 return Err(TxPoolError::ReplacementUnderpriced {
                     current: format!("{}", existing_tx.max_fee_per_gas),
                     required: format!("{}", tx.max_fee_per_gas),
@@ -121,120 +122,379 @@ impl TxPool for TxPoolImpl {
         }
 
         // 检查容量
-        if state.total_count() >= self.config.max_pending + self.config.max_queued {
+        if self.total_count() >= config.max_pending + config.max_queued {
             return Err(TxPoolError::PoolFull {
-                current: state.total_count(),
-                max: self.config.max_pending + self.config.max_queued,
+                current: self.total_count(),
+                max: config.max_pending + config.max_queued,
             });
         }
 
         // 存储交易
-        state.transactions.insert(tx_hash, (tx.clone(), sender));
+        self.transactions.insert(tx_hash, (tx, sender));
+        self.record_arrival(tx_hash, now);
 
         // 决定放入pending还是queued
         // 简化逻辑：先都放pending，实际应该检查nonce连续性
-        let sender_pending = state.pending.entry(sender).or_insert_with(BTreeMap::new);
+        let sender_pending = self.pending.entry(sender).or_default();
         sender_pending.insert(nonce, tx_hash);
 
         Ok(tx_hash)
     }
 
-    async fn get(&self, hash: &H256) -> Result<Option<DynamicFeeTx>, TxPoolError> {
-        let state = self.state.read().unwrap();
-        Ok(state.transactions.get(hash).map(|(tx, _)| tx.clone()))
+    pub(crate) fn get(&self, hash: &H256) -> Option<DynamicFeeTx> {
+        self.transactions.get(hash).map(|(tx, _)| tx.clone())
     }
 
-    async fn get_pending_by_sender(&self, sender: Address) -> Result<Vec<DynamicFeeTx>, TxPoolError> {
-        let state = self.state.read().unwrap();
-
+    pub(crate) fn get_pending_by_sender(&self, sender: Address) -> Vec<DynamicFeeTx> {
         let mut result = Vec::new();
-        if let Some(sender_txs) = state.pending.get(&sender) {
+        if let Some(sender_txs) = self.pending.get(&sender) {
             for hash in sender_txs.values() {
-                if let Some((tx, _)) = state.transactions.get(hash) {
+                if let Some((tx, _)) = self.transactions.get(hash) {
                     result.push(tx.clone());
                 }
             }
         }
-
-        Ok(result)
+        result
     }
 
-    async fn get_pending(&self, max_count: usize, base_fee: Option<u64>) -> Result<Vec<DynamicFeeTx>, TxPoolError> {
-        let state = self.state.read().unwrap();
+    pub(crate) fn get_pending(&self, max_count: usize, base_fee: Option<u64>) -> Vec<DynamicFeeTx> {
+        self.get_pending_candidates(max_count, base_fee)
+            .into_iter()
+            .map(|pending| pending.tx)
+            .collect()
+    }
 
+    /// 可打包交易及其入池序号（按max_fee_per_gas降序）
+    pub(crate) fn get_pending_candidates(&self, max_count: usize, base_fee: Option<u64>) -> Vec<PendingTx> {
         let mut all_pending = Vec::new();
 
         // 收集所有pending交易
-        for sender_txs in state.pending.values() {
+        for sender_txs in self.pending.values() {
             for hash in sender_txs.values() {
-                if let Some((tx, _)) = state.transactions.get(hash) {
+                if let Some((tx, _)) = self.transactions.get(hash) {
                     // 如果设置了base_fee，过滤掉max_fee_per_gas太低的交易
                     if let Some(base) = base_fee {
                         if tx.max_fee_per_gas < U256::from(base) {
                             continue;
                         }
                     }
-                    all_pending.push(tx.clone());
+                    all_pending.push(PendingTx {
+                        tx: tx.clone(),
+                        arrival: self.arrivals.get(hash).copied().unwrap_or_default(),
+                    });
                 }
             }
         }
 
         // 按max_fee_per_gas降序排序（矿工收益最大化）
-        all_pending.sort_by(|a, b| b.max_fee_per_gas.cmp(&a.max_fee_per_gas));
+        all_pending.sort_by_key(|pending| std::cmp::Reverse(pending.tx.max_fee_per_gas));
 
         // 限制数量
         all_pending.truncate(max_count);
 
-        Ok(all_pending)
+        all_pending
     }
 
-    async fn remove(&self, hash: &H256) -> Result<(), TxPoolError> {
-        let mut state = self.state.write().unwrap();
+    /// 记录入池时间（替换交易视为重新入池）
+    ///
+    /// 同一时刻入池的交易顺延 1 纳秒，保证入池时间严格递增、FIFO 顺序确定
+    fn record_arrival(&mut self, tx_hash: H256, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.epoch).as_nanos();
+        let arrival = u64::try_from(elapsed)
+            .unwrap_or(u64::MAX)
+            .max(self.last_arrival + 1);
+        self.last_arrival = arrival;
+        self.arrivals.insert(tx_hash, arrival);
+    }
 
-        if let Some((tx, sender)) = state.transactions.remove(hash) {
+    pub(crate) fn remove(&mut self, hash: &H256) {
+        if let Some((tx, sender)) = self.transactions.remove(hash) {
             let nonce = tx.nonce.as_u64();
+            self.arrivals.remove(hash);
 
             // 从pending移除
-            if let Some(sender_pending) = state.pending.get_mut(&sender) {
+            if let Some(sender_pending) = self.pending.get_mut(&sender) {
                 sender_pending.remove(&nonce);
                 if sender_pending.is_empty() {
-                    state.pending.remove(&sender);
+                    self.pending.remove(&sender);
                 }
             }
 
             // 从queued移除
-            if let Some(sender_queued) = state.queued.get_mut(&sender) {
+            if let Some(sender_queued) = self.queued.get_mut(&sender) {
                 sender_queued.remove(&nonce);
                 if sender_queued.is_empty() {
-                    state.queued.remove(&sender);
+                    self.queued.remove(&sender);
                 }
             }
         }
+    }
+
+    pub(crate) fn stats(&self, config: &TxPoolConfig) -> TxPoolStats {
+        TxPoolStats {
+            pending: self.pending_count(),
+            queued: self.queued_count(),
+            capacity: config.max_pending + config.max_queued,
+        }
+    }
+
+    /// 链重组后重新评估交易池
+    ///
+    /// - 被回滚区块中的交易重新放回池中（同 sender/nonce 已有池内交易时保留池内版本）
+    /// - 按新链头的账户 nonce 重新划分：从账户 nonce 起连续的交易为 pending，其余为 queued
+    /// - nonce 低于账户 nonce 的交易已在新链上执行过，直接丢弃
+    ///
+    /// 不在 `new_head_nonces` 中的账户不重新划分，其回滚交易按 `add` 的规则放入 pending。
+    pub(crate) fn on_reorg(
+        &mut self,
+        reverted_txs: Vec<(DynamicFeeTx, Address)>,
+        new_head_nonces: &HashMap<Address, u64>,
+        now: Instant,
+    ) {
+        for (tx, sender) in reverted_txs {
+            let tx_hash = tx.hash();
+            let nonce = tx.nonce.as_u64();
+            let occupied = self.pending.get(&sender).is_some_and(|txs| txs.contains_key(&nonce))
+                || self.queued.get(&sender).is_some_and(|txs| txs.contains_key(&nonce));
+            if occupied || self.transactions.contains_key(&tx_hash) {
+                continue;
+            }
+
+            self.transactions.insert(tx_hash, (tx, sender));
+            self.record_arrival(tx_hash, now);
+            self.pending.entry(sender).or_default().insert(nonce, tx_hash);
+        }
+
+        for (sender, account_nonce) in new_head_nonces {
+            self.reclassify(*sender, *account_nonce, None);
+        }
+    }
+
+    /// 导入新区块后重新验证交易池
+    ///
+    /// - 区块中已打包的交易按哈希移除
+    /// - 按新链头的账户 nonce 丢弃 nonce 过低的交易并重新划分 pending/queued
+    /// - 余额按 nonce 顺序扣除各笔交易的 `max_cost`，余额不足的交易降级为 queued，
+    ///   其后的同 sender 交易因 nonce 不连续一并降级
+    ///
+    /// 不在 `account_states` 中的账户只移除已打包交易，不重新划分。
+    pub(crate) fn on_new_head(&mut self, block: &Block, account_states: &HashMap<Address, AccountState>) {
+        for tx in &block.transactions {
+            self.remove(&tx.hash());
+        }
+
+        for (sender, state) in account_states {
+            self.reclassify(*sender, state.nonce, Some(state.balance));
+        }
+    }
+
+    /// 按账户 nonce 重新划分单个 sender 的 pending/queued，丢弃 nonce 过低的交易
+    ///
+    /// 给出 `balance` 时，按 nonce 顺序累计扣除前序交易的花费，
+    /// 剩余余额不足以支付的交易及其后续交易划入 queued
+    fn reclassify(&mut self, sender: Address, account_nonce: u64, balance: Option<U256>) {
+        let mut slots = self.pending.remove(&sender).unwrap_or_default();
+        slots.extend(self.queued.remove(&sender).unwrap_or_default());
+
+        let mut pending = BTreeMap::new();
+        let mut queued = BTreeMap::new();
+        let mut next_nonce = account_nonce;
+        let mut remaining = balance;
+        for (nonce, tx_hash) in slots {
+            if nonce < account_nonce {
+                self.transactions.remove(&tx_hash);
+                self.arrivals.remove(&tx_hash);
+            } else if nonce == next_nonce && self.charge(&tx_hash, &mut remaining) {
+                pending.insert(nonce, tx_hash);
+                next_nonce += 1;
+            } else {
+                queued.insert(nonce, tx_hash);
+            }
+        }
+
+        if !pending.is_empty() {
+            self.pending.insert(sender, pending);
+        }
+        if !queued.is_empty() {
+            self.queued.insert(sender, queued);
+        }
+    }
+
+    /// 从剩余余额中扣除交易的最大花费，不足时返回 false 且不扣除（未给出余额时视为足够）
+    fn charge(&self, tx_hash: &H256, remaining: &mut Option<U256>) -> bool {
+        match (remaining.as_mut(), self.transactions.get(tx_hash)) {
+            (Some(balance), Some((tx, _))) => match balance.checked_sub(tx.max_cost()) {
+                Some(rest) => {
+                    *balance = rest;
+                    true
+                }
+                None => false,
+            },
+            _ => true,
+        }
+    }
+
+    /// 池中所有 sender（pending 与 queued）
+    pub(crate) fn senders(&self) -> Vec<Address> {
+        let mut senders: Vec<Address> = self.pending.keys().chain(self.queued.keys()).copied().collect();
+        senders.sort();
+        senders.dedup();
+        senders
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.transactions.clear();
+        self.pending.clear();
+        self.queued.clear();
+        self.arrivals.clear();
+    }
+}
+
+/// 交易池实现（无状态服务）
+/// 所有状态存储在Arc<RwLock<TxPoolState>>中，入池时间取自注入的时钟
+#[derive(Clone)]
+pub struct TxPoolImpl<C: Clock = SystemClock> {
+    config: TxPoolConfig,
+    clock: C,
+    state: Arc<RwLock<TxPoolState>>,
+}
+
+impl TxPoolImpl {
+    pub fn new(config: TxPoolConfig) -> Self {
+        Self::with_clock(config, SystemClock)
+    }
+}
+
+impl<C: Clock> TxPoolImpl<C> {
+    /// 使用指定时钟创建交易池
+    pub fn with_clock(config: TxPoolConfig, clock: C) -> Self {
+        let state = TxPoolState::new(clock.now());
+        Self {
+            config,
+            clock,
+            state: Arc::new(RwLock::new(state)),
+        }
+    }
+
+    /// 处理链重组：回滚交易重新入池，并按新链头的账户 nonce 重新划分 pending/queued
+    ///
+    /// # 参数
+    /// - `reverted_txs`: 被回滚区块中的交易及其 sender
+    /// - `new_head_nonces`: 新链头上受影响账户的 nonce
+    pub fn on_reorg(
+        &self,
+        reverted_txs: Vec<(DynamicFeeTx, Address)>,
+        new_head_nonces: &HashMap<Address, u64>,
+    ) {
+        self.state
+            .write()
+            .unwrap()
+            .on_reorg(reverted_txs, new_head_nonces, self.clock.now());
+    }
+
+    /// 导入新区块后重新验证：移除已打包交易，丢弃 nonce 过低的交易，余额不足的交易降级为 queued
+    ///
+    /// # 参数
+    /// - `block`: 新导入的链头区块
+    /// - `account_states`: 新链头上受影响账户的 nonce 与余额
+    pub fn on_new_head(&self, block: &Block, account_states: &HashMap<Address, AccountState>) {
+        self.state.write().unwrap().on_new_head(block, account_states);
+    }
+}
+
+impl<C: Clock + Clone + 'static> TxPoolImpl<C> {
+    /// 订阅新链头（如 `BlockChain::subscribe_new_heads`），每个新链头从 `accounts`
+    /// 查询池中各 sender 的 nonce 与余额后重新验证（见 `on_new_head`）
+    ///
+    /// 查询失败的 sender 本轮不重新划分；订阅落后丢失的区块中已打包的交易
+    /// 由后续链头的 nonce 检查清除；发送端关闭后任务退出
+    pub fn follow_new_heads<S>(
+        &self,
+        mut heads: broadcast::Receiver<Block>,
+        accounts: Arc<S>,
+    ) -> JoinHandle<()>
+    where
+        S: AccountStateProvider + ?Sized + 'static,
+    {
+        let pool = self.clone();
+        tokio::spawn(async move {
+            loop {
+                let block = match heads.recv().await {
+                    Ok(block) => block,
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                let senders = pool.state.read().unwrap().senders();
+                let mut account_states = HashMap::new();
+                for sender in senders {
+                    let (Ok(nonce), Ok(balance)) = (
+                        accounts.get_nonce(sender).await,
+                        accounts.get_balance(sender).await,
+                    ) else {
+                        continue;
+                    };
+                    account_states.insert(
+                        sender,
+                        AccountState {
+                            nonce: nonce.as_u64(),
+                            balance,
+                        },
+                    );
+                }
+                pool.on_new_head(&block, &account_states);
+            }
+        })
+    }
+}
+
+impl Default for TxPoolImpl {
+    fn default() -> Self {
+        Self::new(TxPoolConfig::default())
+    }
+}
+
+#[async_trait]
+impl<C: Clock> TxPool for TxPoolImpl<C> {
+    async fn add(&self, tx: DynamicFeeTx, sender: Address) -> Result<H256, TxPoolError> {
+        let now = self.clock.now();
+        self.state.write().unwrap().add(&self.config, tx, sender, now)
+    }
+
+    async fn get(&self, hash: &H256) -> Result<Option<DynamicFeeTx>, TxPoolError> {
+        Ok(self.state.read().unwrap().get(hash))
+    }
+
+    async fn get_pending_by_sender(&self, sender: Address) -> Result<Vec<DynamicFeeTx>, TxPoolError> {
+        Ok(self.state.read().unwrap().get_pending_by_sender(sender))
+    }
+
+    async fn get_pending(&self, max_count: usize, base_fee: Option<u64>) -> Result<Vec<DynamicFeeTx>, TxPoolError> {
+        Ok(self.state.read().unwrap().get_pending(max_count, base_fee))
+    }
+
+    async fn get_pending_candidates(&self, max_count: usize, base_fee: Option<u64>) -> Result<Vec<PendingTx>, TxPoolError> {
+        Ok(self.state.read().unwrap().get_pending_candidates(max_count, base_fee))
+    }
 
+    async fn remove(&self, hash: &H256) -> Result<(), TxPoolError> {
+        self.state.write().unwrap().remove(hash);
         Ok(())
     }
 
     async fn remove_batch(&self, hashes: &[H256]) -> Result<(), TxPoolError> {
+        let mut state = self.state.write().unwrap();
         for hash in hashes {
-            self.remove(hash).await?;
+            state.remove(hash);
         }
         Ok(())
     }
 
     async fn stats(&self) -> Result<TxPoolStats, TxPoolError> {
-        let state = self.state.read().unwrap();
-        Ok(TxPoolStats {
-            pending: state.pending_count(),
-            queued: state.queued_count(),
-            capacity: self.config.max_pending + self.config.max_queued,
-        })
+        Ok(self.state.read().unwrap().stats(&self.config))
     }
 
     async fn clear(&self) -> Result<(), TxPoolError> {
-        let mut state = self.state.write().unwrap();
-        state.transactions.clear();
-        state.pending.clear();
-        state.queued.clear();
+        self.state.write().unwrap().clear();
         Ok(())
     }
 }
@@ -242,7 +502,9 @@ impl TxPool for TxPoolImpl {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::tx_types::AccessListItem;
+    use crate::service::repo::block_repo::tests::make_block;
+    use crate::infrastructure::clock_impl::MockClock;
+    use std::time::Duration;
 
     fn create_test_tx(nonce: u64, max_fee: u64) -> DynamicFeeTx {
         DynamicFeeTx {
@@ -317,5 +579,178 @@ mod tests {
         assert_eq!(stats.pending, 2);
         assert_eq!(stats.queued, 0);
     }
-}
 
+    #[tokio::test]
+    async fn test_arrival_time_comes_from_clock() {
+        let clock = MockClock::default();
+        let pool = TxPoolImpl::with_clock(TxPoolConfig::default(), clock.clone());
+        let sender = Address::from_low_u64_be(0x5678);
+
+        clock.advance(Duration::from_secs(5));
+        pool.add(create_test_tx(0, 50_000_000_000), sender).await.unwrap();
+        clock.advance(Duration::from_secs(2));
+        pool.add(create_test_tx(1, 50_000_000_000), sender).await.unwrap();
+        // 时钟未前进：仍严格递增
+        pool.add(create_test_tx(2, 50_000_000_000), sender).await.unwrap();
+
+        let mut arrivals: Vec<u64> = pool
+            .get_pending_candidates(10, None)
+            .await
+            .unwrap()
+            .iter()
+            .map(|p| p.arrival)
+            .collect();
+        arrivals.sort_unstable();
+        assert_eq!(arrivals, vec![5_000_000_000, 7_000_000_000, 7_000_000_001]);
+    }
+
+    #[tokio::test]
+    async fn test_reorg_rewinds_nonce() {
+        let pool = TxPoolImpl::default();
+        let sender = Address::from_low_u64_be(0x5678);
+
+        // 旧链上 nonce 0..=2 已打包，池中剩 nonce 3、5
+        let tx3 = pool.add(create_test_tx(3, 50_000_000_000), sender).await.unwrap();
+        let tx5 = pool.add(create_test_tx(5, 50_000_000_000), sender).await.unwrap();
+
+        // 重组：新链只包含 nonce 0，nonce 1、2 被回滚
+        let reverted = vec![
+            (create_test_tx(1, 50_000_000_000), sender),
+            (create_test_tx(2, 50_000_000_000), sender),
+        ];
+        pool.on_reorg(reverted, &HashMap::from([(sender, 1)]));
+
+        let pending: Vec<u64> = pool
+            .get_pending_by_sender(sender)
+            .await
+            .unwrap()
+            .iter()
+            .map(|tx| tx.nonce.as_u64())
+            .collect();
+        assert_eq!(pending, vec![1, 2, 3]);
+        assert!(pool.get(&tx3).await.unwrap().is_some());
+        // nonce 4 缺失，nonce 5 不可执行
+        assert!(pool.get(&tx5).await.unwrap().is_some());
+        let stats = pool.stats().await.unwrap();
+        assert_eq!((stats.pending, stats.queued), (3, 1));
+
+        // 新链继续前进到 nonce 3：nonce 1、2 变为过期并被丢弃
+        pool.on_reorg(vec![], &HashMap::from([(sender, 3)]));
+        let pending: Vec<u64> = pool
+            .get_pending_by_sender(sender)
+            .await
+            .unwrap()
+            .iter()
+            .map(|tx| tx.nonce.as_u64())
+            .collect();
+        assert_eq!(pending, vec![3]);
+        assert!(pool.get(&create_test_tx(1, 50_000_000_000).hash()).await.unwrap().is_none());
+        assert_eq!(pool.stats().await.unwrap().queued, 1);
+    }
+
+    #[tokio::test]
+    async fn test_new_head_prunes_mined_and_demotes_unaffordable() {
+        let pool = TxPoolImpl::default();
+        let miner_sender = Address::from_low_u64_be(0x5678);
+        let poor_sender = Address::from_low_u64_be(0x9abc);
+
+        let mined = create_test_tx(0, 50_000_000_000);
+        let mined_hash = pool.add(mined.clone(), miner_sender).await.unwrap();
+        let next = pool.add(create_test_tx(1, 50_000_000_000), miner_sender).await.unwrap();
+        // 每笔最大花费约 1 ETH + gas
+        let poor_0 = pool.add(create_test_tx(0, 40_000_000_000), poor_sender).await.unwrap();
+        let poor_1 = pool.add(create_test_tx(1, 40_000_000_000), poor_sender).await.unwrap();
+        assert_eq!(pool.stats().await.unwrap().pending, 4);
+
+        // 新区块打包了 miner_sender 的 nonce 0；poor_sender 余额降到 0.5 ETH
+        let mut block = make_block(1, H256::zero());
+        block.transactions = vec![mined];
+        let eth = U256::from(1_000_000_000_000_000_000u64);
+        let account_states = HashMap::from([
+            (miner_sender, AccountState { nonce: 1, balance: eth * 10 }),
+            (poor_sender, AccountState { nonce: 0, balance: eth / 2 }),
+        ]);
+        pool.on_new_head(&block, &account_states);
+
+        // 已打包交易被移除，同 sender 的下一笔仍可打包
+        assert!(pool.get(&mined_hash).await.unwrap().is_none());
+        let pending: Vec<H256> = pool
+            .get_pending(10, None)
+            .await
+            .unwrap()
+            .iter()
+            .map(|tx| tx.hash())
+            .collect();
+        assert_eq!(pending, vec![next]);
+
+        // 余额不足的交易仍在池中，但降级为 queued
+        assert!(pool.get(&poor_0).await.unwrap().is_some());
+        assert!(pool.get(&poor_1).await.unwrap().is_some());
+        let stats = pool.stats().await.unwrap();
+        assert_eq!((stats.pending, stats.queued), (1, 2));
+    }
+
+    #[tokio::test]
+    async fn test_new_head_charges_balance_across_nonces() {
+        let pool = TxPoolImpl::default();
+        let sender = Address::from_low_u64_be(0x5678);
+        // 每笔最大花费 1 ETH + 21000 * 40 Gwei
+        let first = pool.add(create_test_tx(0, 40_000_000_000), sender).await.unwrap();
+        let second = pool.add(create_test_tx(1, 40_000_000_000), sender).await.unwrap();
+
+        // 1.5 ETH 单独够付任一笔，但付完第一笔后不够付第二笔
+        let eth = U256::from(1_000_000_000_000_000_000u64);
+        let account_states =
+            HashMap::from([(sender, AccountState { nonce: 0, balance: eth * 3 / 2 })]);
+        pool.on_new_head(&make_block(1, H256::zero()), &account_states);
+
+        let pending: Vec<H256> = pool
+            .get_pending_by_sender(sender)
+            .await
+            .unwrap()
+            .iter()
+            .map(|tx| tx.hash())
+            .collect();
+        assert_eq!(pending, vec![first]);
+        assert!(pool.get(&second).await.unwrap().is_some());
+        let stats = pool.stats().await.unwrap();
+        assert_eq!((stats.pending, stats.queued), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_follow_new_heads_revalidates() {
+        use crate::infrastructure::mock_repository::MockEthereumRepository;
+
+        let pool = TxPoolImpl::default();
+        let accounts = Arc::new(MockEthereumRepository::new());
+        let sender = Address::from_low_u64_be(0x5678);
+        let mined = create_test_tx(0, 50_000_000_000);
+        let mined_hash = pool.add(mined.clone(), sender).await.unwrap();
+        let stale = pool.add(create_test_tx(1, 50_000_000_000), sender).await.unwrap();
+        let next = pool.add(create_test_tx(2, 50_000_000_000), sender).await.unwrap();
+
+        let (heads, receiver) = broadcast::channel(4);
+        let follower = pool.follow_new_heads(receiver, accounts.clone());
+
+        // 新链头上 sender 已用到 nonce 2（nonce 1 在其他路径上链）
+        let eth = U256::from(1_000_000_000_000_000_000u64);
+        accounts.set_nonce(sender, 2);
+        accounts.set_balance(sender, eth * 10);
+        let mut block = make_block(1, H256::zero());
+        block.transactions = vec![mined];
+        heads.send(block).unwrap();
+        drop(heads);
+        follower.await.unwrap();
+
+        assert!(pool.get(&mined_hash).await.unwrap().is_none());
+        assert!(pool.get(&stale).await.unwrap().is_none());
+        let pending: Vec<H256> = pool
+            .get_pending_by_sender(sender)
+            .await
+            .unwrap()
+            .iter()
+            .map(|tx| tx.hash())
+            .collect();
+        assert_eq!(pending, vec![next]);
+    }
+}
@@ -0,0 +1,286 @@
+//! 交易池 Actor 实现
+//! Erlang风格通信：状态由单一任务独占，外部只通过消息访问
+//!
+//! - 有界 mpsc 命令通道：提交过快时发送方等待（背压），而非争抢锁
+//! - 每条命令携带 oneshot 回复通道
+//! - 命令按到达顺序串行处理，结果确定
+
+use crate::domain::tx_types::DynamicFeeTx;
+use crate::infrastructure::clock_impl::SystemClock;
+use crate::infrastructure::transaction_repo_impl::{TxPoolConfig, TxPoolState};
+use crate::service::clock::Clock;
+use crate::service::repo::transaction_repo::{PendingTx, TxPool, TxPoolError, TxPoolStats};
+use async_trait::async_trait;
+use ethereum_types::{Address, H256};
+use tokio::sync::{mpsc, oneshot};
+
+/// 默认命令通道容量
+pub const DEFAULT_MAILBOX_CAPACITY: usize = 1024;
+
+/// 交易池命令（Actor 消息）
+enum TxPoolCommand {
+    Add {
+        tx: Box<DynamicFeeTx>,
+        sender: Address,
+        reply: oneshot::Sender<Result<H256, TxPoolError>>,
+    },
+    Get {
+        hash: H256,
+        reply: oneshot::Sender<Option<DynamicFeeTx>>,
+    },
+    GetPendingBySender {
+        sender: Address,
+        reply: oneshot::Sender<Vec<DynamicFeeTx>>,
+    },
+    GetPending {
+        max_count: usize,
+        base_fee: Option<u64>,
+        reply: oneshot::Sender<Vec<PendingTx>>,
+    },
+    Remove {
+        hashes: Vec<H256>,
+        reply: oneshot::Sender<()>,
+    },
+    Stats {
+        reply: oneshot::Sender<TxPoolStats>,
+    },
+    Clear {
+        reply: oneshot::Sender<()>,
+    },
+}
+
+/// 交易池 Actor：在独立任务中持有 TxPoolState
+pub struct TxPoolActor<C: Clock = SystemClock> {
+    config: TxPoolConfig,
+    clock: C,
+    state: TxPoolState,
+    mailbox: mpsc::Receiver<TxPoolCommand>,
+}
+
+impl TxPoolActor {
+    /// 启动 Actor 并返回句柄（需在 tokio 运行时内调用）
+    ///
+    /// 所有句柄被丢弃后，Actor 任务自动退出。
+    pub fn spawn(config: TxPoolConfig, mailbox_capacity: usize) -> TxPoolHandle {
+        TxPoolActor::spawn_with_clock(config, mailbox_capacity, SystemClock)
+    }
+}
+
+impl<C: Clock + 'static> TxPoolActor<C> {
+    /// 使用指定时钟启动 Actor，入池时间取自该时钟
+    pub fn spawn_with_clock(
+        config: TxPoolConfig,
+        mailbox_capacity: usize,
+        clock: C,
+    ) -> TxPoolHandle {
+        let (sender, mailbox) = mpsc::channel(mailbox_capacity);
+        let actor = Self {
+            config,
+            state: TxPoolState::new(clock.now()),
+            clock,
+            mailbox,
+        };
+        tokio::spawn(actor.run());
+        TxPoolHandle { sender }
+    }
+
+    async fn run(mut self) {
+        while let Some(command) = self.mailbox.recv().await {
+            self.handle(command);
+        }
+    }
+
+    /// 处理单条命令；调用方已放弃等待时忽略回复失败
+    fn handle(&mut self, command: TxPoolCommand) {
+        match command {
+            TxPoolCommand::Add { tx, sender, reply } => {
+                let now = self.clock.now();
+                let _ = reply.send(self.state.add(&self.config, *tx, sender, now));
+            }
+            TxPoolCommand::Get { hash, reply } => {
+                let _ = reply.send(self.state.get(&hash));
+            }
+            TxPoolCommand::GetPendingBySender { sender, reply } => {
+                let _ = reply.send(self.state.get_pending_by_sender(sender));
+            }
+            TxPoolCommand::GetPending { max_count, base_fee, reply } => {
+                let _ = reply.send(self.state.get_pending_candidates(max_count, base_fee));
+            }
+            TxPoolCommand::Remove { hashes, reply } => {
+                for hash in &hashes {
+                    self.state.remove(hash);
+                }
+                let _ = reply.send(());
+            }
+            TxPoolCommand::Stats { reply } => {
+                let _ = reply.send(self.state.stats(&self.config));
+            }
+            TxPoolCommand::Clear { reply } => {
+                self.state.clear();
+                let _ = reply.send(());
+            }
+        }
+    }
+}
+
+/// 交易池句柄：通过消息访问 TxPoolActor
+#[derive(Clone)]
+pub struct TxPoolHandle {
+    sender: mpsc::Sender<TxPoolCommand>,
+}
+
+impl TxPoolHandle {
+    /// 发送命令并等待回复
+    async fn request<T>(
+        &self,
+        command: impl FnOnce(oneshot::Sender<T>) -> TxPoolCommand,
+    ) -> Result<T, TxPoolError> {
+        let (reply, response) = oneshot::channel();
+        self.sender
+            .send(command(reply))
+            .await
+            .map_err(|_| actor_stopped())?;
+        response.await.map_err(|_| actor_stopped())
+    }
+}
+
+fn actor_stopped() -> TxPoolError {
+    TxPoolError::Other("tx pool actor stopped".to_string())
+}
+
+#[async_trait]
+impl TxPool for TxPoolHandle {
+    async fn add(&self, tx: DynamicFeeTx, sender: Address) -> Result<H256, TxPoolError> {
+        let tx = Box::new(tx);
+        self.request(|reply| TxPoolCommand::Add { tx, sender, reply })
+            .await?
+    }
+
+    async fn get(&self, hash: &H256) -> Result<Option<DynamicFeeTx>, TxPoolError> {
+        let hash = *hash;
+        self.request(|reply| TxPoolCommand::Get { hash, reply }).await
+    }
+
+    async fn get_pending_by_sender(&self, sender: Address) -> Result<Vec<DynamicFeeTx>, TxPoolError> {
+        self.request(|reply| TxPoolCommand::GetPendingBySender { sender, reply })
+            .await
+    }
+
+    async fn get_pending(&self, max_count: usize, base_fee: Option<u64>) -> Result<Vec<DynamicFeeTx>, TxPoolError> {
+        let pending = self.get_pending_candidates(max_count, base_fee).await?;
+        Ok(pending.into_iter().map(|pending| pending.tx).collect())
+    }
+
+    async fn get_pending_candidates(&self, max_count: usize, base_fee: Option<u64>) -> Result<Vec<PendingTx>, TxPoolError> {
+        self.request(|reply| TxPoolCommand::GetPending { max_count, base_fee, reply })
+            .await
+    }
+
+    async fn remove(&self, hash: &H256) -> Result<(), TxPoolError> {
+        self.remove_batch(std::slice::from_ref(hash)).await
+    }
+
+    async fn remove_batch(&self, hashes: &[H256]) -> Result<(), TxPoolError> {
+        let hashes = hashes.to_vec();
+        self.request(|reply| TxPoolCommand::Remove { hashes, reply })
+            .await
+    }
+
+    async fn stats(&self) -> Result<TxPoolStats, TxPoolError> {
+        self.request(|reply| TxPoolCommand::Stats { reply }).await
+    }
+
+    async fn clear(&self) -> Result<(), TxPoolError> {
+        self.request(|reply| TxPoolCommand::Clear { reply }).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethereum_types::{U256, U64};
+    use std::collections::HashSet;
+
+    fn create_test_tx(nonce: u64, max_fee: u64) -> DynamicFeeTx {
+        DynamicFeeTx {
+            chain_id: U64::from(1),
+            nonce: U64::from(nonce),
+            max_priority_fee_per_gas: U256::from(1_000_000_000u64),
+            max_fee_per_gas: U256::from(max_fee),
+            gas_limit: U64::from(21000),
+            to: Some(Address::from_low_u64_be(0x1234)),
+            value: U256::from(1_000_000_000_000_000_000u64),
+            data: vec![],
+            access_list: vec![],
+            v: U64::from(0),
+            r: U256::from(1),
+            s: U256::from(1),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_add_get_remove_via_handle() {
+        let pool = TxPoolActor::spawn(TxPoolConfig::default(), DEFAULT_MAILBOX_CAPACITY);
+        let sender = Address::from_low_u64_be(0x5678);
+
+        let hash = pool.add(create_test_tx(0, 50_000_000_000), sender).await.unwrap();
+        assert_eq!(pool.get(&hash).await.unwrap().unwrap().nonce, U64::zero());
+        assert_eq!(pool.get_pending_by_sender(sender).await.unwrap().len(), 1);
+
+        pool.remove(&hash).await.unwrap();
+        assert!(pool.get(&hash).await.unwrap().is_none());
+        assert_eq!(pool.stats().await.unwrap().pending, 0);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_adds_are_not_lost() {
+        // 通道容量远小于提交量，验证背压下不丢交易
+        let pool = TxPoolActor::spawn(TxPoolConfig::default(), 8);
+
+        let mut tasks = Vec::new();
+        for sender_id in 0..16u64 {
+            let pool = pool.clone();
+            tasks.push(tokio::spawn(async move {
+                let sender = Address::from_low_u64_be(0x1000 + sender_id);
+                let mut hashes = Vec::new();
+                for nonce in 0..50 {
+                    let tx = create_test_tx(nonce, 1_000_000_000 * (sender_id + 1));
+                    hashes.push(pool.add(tx, sender).await.unwrap());
+                }
+                hashes
+            }));
+        }
+
+        let mut hashes = HashSet::new();
+        for task in tasks {
+            hashes.extend(task.await.unwrap());
+        }
+        assert_eq!(hashes.len(), 16 * 50);
+
+        let stats = pool.stats().await.unwrap();
+        assert_eq!(stats.pending, 16 * 50);
+        let pending = pool.get_pending(usize::MAX, None).await.unwrap();
+        assert_eq!(pending.len(), 16 * 50);
+        assert!(pending
+            .windows(2)
+            .all(|pair| pair[0].max_fee_per_gas >= pair[1].max_fee_per_gas));
+    }
+
+    #[tokio::test]
+    async fn test_pool_full_reported_through_handle() {
+        let config = TxPoolConfig {
+            max_pending: 1,
+            max_queued: 0,
+            ..TxPoolConfig::default()
+        };
+        let pool = TxPoolActor::spawn(config, DEFAULT_MAILBOX_CAPACITY);
+        let sender = Address::from_low_u64_be(0x5678);
+
+        pool.add(create_test_tx(0, 50_000_000_000), sender).await.unwrap();
+        let err = pool.add(create_test_tx(1, 50_000_000_000), sender).await.unwrap_err();
+        assert_eq!(err, TxPoolError::PoolFull { current: 1, max: 1 });
+
+        pool.clear().await.unwrap();
+        assert_eq!(pool.stats().await.unwrap().pending, 0);
+    }
+}
@@ -1,8 +1,12 @@
 use node::service::command_dispatcher::CommandDispatcher;
 use node::inbound::json_rpc::EthJsonRpcHandler;
-use node::inbound::server::run_server;
+use node::domain::rpc_config::ServerConfig;
+use node::inbound::server::serve_until;
 use node::infrastructure::mock_repository::MockEthereumRepository;
+use node::infrastructure::node_config::NodeConfig;
+use node::infrastructure::node_handle::{NodeHandle, DEFAULT_SHUTDOWN_GRACE};
 use node::service::ethereum_service_impl::EthereumServiceImpl;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
@@ -17,6 +21,16 @@ async fn main() -> anyhow::Result<()> {
         .with(tracing_subscriber::fmt::layer())
         .init();
 
+    // 配置文件路径：命令行第一个参数，或环境变量 RUSTETH_CONFIG；均未提供时使用默认配置
+    let config_path = std::env::args()
+        .nth(1)
+        .or_else(|| std::env::var("RUSTETH_CONFIG").ok())
+        .map(PathBuf::from);
+    let config = NodeConfig::load(config_path.as_deref())?;
+    if let Some(path) = &config_path {
+        println!("⚙️  配置文件: {}", path.display());
+    }
+
     println!("🏗️  构建 Clean Architecture 依赖链...\n");
 
     // 基础设施层 - 创建数据仓储
@@ -25,7 +39,13 @@ async fn main() -> anyhow::Result<()> {
 
     // 服务层 - 创建业务服务
     println!("🔧 [Service] EthereumServiceImpl");
-    let service = Arc::new(EthereumServiceImpl::new(repo));
+    let service = Arc::new(
+        EthereumServiceImpl::new(repo)
+            .with_chain_id(config.chain.chain_id)
+            .with_tx_pool_config(config.tx_pool)
+            .with_max_log_results(config.rpc.max_log_results)
+            .with_max_bundle_size(config.rpc.max_bundle_size),
+    );
 
     // 领域层 - 创建命令分发器
     println!("🚀 [Domain] CommandDispatcher");
@@ -33,11 +53,12 @@ async fn main() -> anyhow::Result<()> {
 
     // 接口层 - 创建 JSON-RPC 处理器
     println!("🌐 [Interface] EthJsonRpcHandler");
-    let rpc_handler = EthJsonRpcHandler::new(dispatcher);
+    let rpc_handler =
+        EthJsonRpcHandler::new(dispatcher).with_namespaces(config.rpc.namespaces.clone());
 
     // 启动 HTTP 服务器
-    let host = "127.0.0.1";
-    let port = 8545;
+    let host = config.rpc.host.as_str();
+    let port = config.rpc.port;
 
     println!("\n✅ 依赖注入完成！\n");
     println!("🚀 RustEth 节点启动中...");
@@ -53,7 +74,27 @@ async fn main() -> anyhow::Result<()> {
     println!("   ✓ CQRS 命令查询分离");
     println!("   ✓ 极简设计，无过度抽象");
 
-    run_server(host, port, rpc_handler).await?;
+    let server_config = ServerConfig {
+        max_requests_per_connection: Some(config.rpc.max_requests_per_connection).filter(|&max| max > 0),
+        connection_overflow: config.rpc.connection_overflow,
+        ..ServerConfig::default()
+    };
+
+    // 后台任务登记到 NodeHandle，Ctrl-C 时统一关闭
+    let listener = tokio::net::TcpListener::bind((host, port)).await?;
+    let mut node = NodeHandle::new(DEFAULT_SHUTDOWN_GRACE);
+    node.spawn("json-rpc", |signal| async move {
+        if let Err(e) = serve_until(listener, rpc_handler, server_config, signal.recv()).await {
+            tracing::error!(error = %e, "JSON-RPC 服务器异常退出");
+        }
+    });
+
+    tokio::signal::ctrl_c().await?;
+    println!("\n🛑 收到退出信号，正在关闭...");
+    let report = node.shutdown().await;
+    if !report.aborted.is_empty() {
+        println!("⚠️  强制终止的任务: {:?}", report.aborted);
+    }
 
     Ok(())
 }
@@ -1,16 +1,19 @@
-/// 区块生产和接收服务 - 集成用例实现
-///
-/// 场景1: 矿工/验证者构建新区块
-/// 场景2: 验证者接收并验证区块
-///
-/// 参考 geth/miner/worker.go 和 geth/eth/handler.go
-
-use crate::domain::block_types::{Block, BlockValidationError, BuildEnvironment};
+//! 区块生产和接收服务 - 集成用例实现
+//!
+//! 场景1: 矿工/验证者构建新区块
+//! 场景2: 验证者接收并验证区块
+//!
+//! 参考 geth/miner/worker.go 和 geth/eth/handler.go
+
+use crate::domain::block_types::{Block, BlockValidationError, BuildEnvironment, BuiltBlock};
 use crate::domain::receipt_types::TransactionReceipt;
+use crate::infrastructure::clock_impl::SystemClock;
+use crate::service::build_block_impl::{BaseFeeCalculator, BaseFeeParams, GasLimitCalculator};
 use crate::service::build_block_trait::{BlockBuilder, BlockChain};
+use crate::service::clock::Clock;
 use crate::service::repo::block_repo::BlockRepositoryError;
 use async_trait::async_trait;
-use ethereum_types::U64;
+use ethereum_types::{Address, Bloom, H256, U64};
 use std::sync::Arc;
 
 /// 区块生产错误
@@ -26,6 +29,8 @@ pub enum BlockProductionError {
     ValidationFailed(BlockValidationError),
     /// 仓储错误
     RepositoryError(String),
+    /// 对端数量不足，暂不出块（避免孤立节点自行分叉）
+    NotReady { peers: usize, min_peers: usize },
 }
 
 impl std::fmt::Display for BlockProductionError {
@@ -36,6 +41,11 @@ impl std::fmt::Display for BlockProductionError {
             Self::BroadcastFailed(msg) => write!(f, "Broadcast failed: {}", msg),
             Self::ValidationFailed(e) => write!(f, "Validation failed: {}", e),
             Self::RepositoryError(msg) => write!(f, "Repository error: {}", msg),
+            Self::NotReady { peers, min_peers } => write!(
+                f,
+                "Not ready: {} peers discovered, at least {} required",
+                peers, min_peers
+            ),
         }
     }
 }
@@ -54,6 +64,75 @@ impl From<BlockRepositoryError> for BlockProductionError {
     }
 }
 
+/// 区块预校验的检查项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationCheck {
+    /// 区块基本规则：`BlockBuilder::validate_block` 的检查
+    /// （PoS 区块头固定字段、gas used 不超过 gas limit）
+    BlockRules,
+    /// 父区块在本地规范链上
+    Parent,
+    /// base fee 与父区块推算值一致（EIP-1559）
+    BaseFee,
+    /// gas limit 相对父区块的调整幅度
+    GasLimit,
+    /// 收据与区块头一致（数量、累计 gas、logs bloom）
+    ///
+    /// MPT 尚未实现，交易根/收据根/状态根暂不校验
+    Receipts,
+}
+
+/// 单项检查结果
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckStatus {
+    Passed,
+    Failed(BlockValidationError),
+    /// 前置条件不满足（如父区块缺失）而未执行
+    Skipped(String),
+}
+
+/// 区块预校验报告（不持久化区块）
+#[derive(Debug, Clone)]
+pub struct ValidationReport {
+    pub block_number: U64,
+    pub block_hash: H256,
+    /// 按执行顺序排列的检查结果
+    pub checks: Vec<(ValidationCheck, CheckStatus)>,
+}
+
+impl ValidationReport {
+    /// 所有检查均通过
+    pub fn is_valid(&self) -> bool {
+        self.checks
+            .iter()
+            .all(|(_, status)| *status == CheckStatus::Passed)
+    }
+
+    /// 查询某项检查的结果
+    pub fn status(&self, check: ValidationCheck) -> Option<&CheckStatus> {
+        self.checks
+            .iter()
+            .find(|(c, _)| *c == check)
+            .map(|(_, status)| status)
+    }
+
+    /// 未通过的检查项
+    pub fn failures(&self) -> impl Iterator<Item = &(ValidationCheck, CheckStatus)> {
+        self.checks
+            .iter()
+            .filter(|(_, status)| matches!(status, CheckStatus::Failed(_)))
+    }
+}
+
+impl From<Result<(), BlockValidationError>> for CheckStatus {
+    fn from(result: Result<(), BlockValidationError>) -> Self {
+        match result {
+            Ok(()) => Self::Passed,
+            Err(e) => Self::Failed(e),
+        }
+    }
+}
+
 /// 区块广播接口（P2P网络）
 ///
 /// 用于将新构建的区块广播给其他节点
@@ -81,6 +160,20 @@ impl BlockBroadcaster for MockBroadcaster {
     }
 }
 
+/// 对端信息来源（节点发现）
+///
+/// 用于出块前确认节点已接入网络
+pub trait PeerInfo: Send + Sync {
+    /// 当前已发现的可用对端数量
+    fn peer_count(&self) -> usize;
+}
+
+/// 出块就绪门槛：已发现对端达到 `min_peers` 才允许出块
+struct PeerGate {
+    peers: Arc<dyn PeerInfo>,
+    min_peers: usize,
+}
+
 /// 区块生产服务
 ///
 /// 场景1: 矿工/验证者构建新区块
@@ -98,6 +191,10 @@ pub struct BlockProductionService {
     blockchain: Arc<dyn BlockChain>,
     /// 区块广播器
     broadcaster: Arc<dyn BlockBroadcaster>,
+    /// 出块就绪门槛（None 表示不检查对端数量，如单机开发链）
+    peer_gate: Option<PeerGate>,
+    /// 出块时间戳来源
+    clock: Arc<dyn Clock>,
 }
 
 impl BlockProductionService {
@@ -111,12 +208,42 @@ impl BlockProductionService {
             builder,
             blockchain,
             broadcaster,
+            peer_gate: None,
+            clock: Arc::new(SystemClock),
         }
     }
 
+    /// 使用指定时钟生成出块时间戳（默认系统时钟）
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// 要求至少 `min_peers` 个已发现对端才出块，否则返回 `NotReady`
+    pub fn with_peer_gate(mut self, peers: Arc<dyn PeerInfo>, min_peers: usize) -> Self {
+        self.peer_gate = Some(PeerGate { peers, min_peers });
+        self
+    }
+
+    /// 检查对端数量是否达到出块门槛
+    fn ensure_ready(&self) -> Result<(), BlockProductionError> {
+        let Some(gate) = &self.peer_gate else {
+            return Ok(());
+        };
+        let peers = gate.peers.peer_count();
+        if peers < gate.min_peers {
+            return Err(BlockProductionError::NotReady {
+                peers,
+                min_peers: gate.min_peers,
+            });
+        }
+        Ok(())
+    }
+
     /// 场景1: 生产新区块
     ///
     /// 完整流程：
+    /// 0. 配置了对端门槛时，对端不足直接返回 `NotReady`
     /// 1. 调用 BlockBuilder::build_block 构建区块
     /// 2. 获取交易收据（从构建过程中）
     /// 3. 持久化到本地区块链
@@ -127,19 +254,23 @@ impl BlockProductionService {
         &self,
         env: BuildEnvironment,
     ) -> Result<Block, BlockProductionError> {
+        self.ensure_ready()?;
+
         // Step 1: 构建区块
         tracing::info!(
             parent_number = %env.parent_number,
             "开始构建新区块"
         );
 
-        let block = self.builder.build_block(env).await
+        let BuiltBlock { block, summary } = self.builder.build_block(env).await
             .map_err(BlockProductionError::BuildFailed)?;
 
         tracing::info!(
             block_number = %block.number(),
             block_hash = %block.hash(),
-            tx_count = block.transactions.len(),
+            tx_count = summary.tx_count,
+            priority_fees = %summary.total_priority_fees,
+            base_fee_burned = %summary.base_fee_burned,
             "区块构建成功"
         );
 
@@ -183,6 +314,37 @@ impl BlockProductionService {
         Ok(block)
     }
 
+    /// 以当前链头为父区块、指定时间戳的构建环境
+    pub async fn environment_at(
+        &self,
+        timestamp: U64,
+        fee_recipient: Address,
+    ) -> Result<BuildEnvironment, BlockProductionError> {
+        let head = self.current_block().await?;
+        BuildEnvironment::builder()
+            .parent(&head)
+            .timestamp(timestamp)
+            .fee_recipient(fee_recipient)
+            .build()
+            .map_err(|e| BlockProductionError::BuildFailed(BlockValidationError::Other(e.to_string())))
+    }
+
+    /// 以当前链头为父区块、时钟当前时间为时间戳的构建环境
+    ///
+    /// 时钟落后于父区块时取父区块时间戳 + 1，保证时间戳严格递增
+    /// （参考 geth/miner/worker.go:commitWork）
+    pub async fn environment_now(
+        &self,
+        fee_recipient: Address,
+    ) -> Result<BuildEnvironment, BlockProductionError> {
+        let head = self.current_block().await?;
+        let timestamp = self
+            .clock
+            .unix_time()
+            .max(head.header.timestamp.as_u64() + 1);
+        self.environment_at(U64::from(timestamp), fee_recipient).await
+    }
+
     /// 获取当前链头
     pub async fn current_block(&self) -> Result<Block, BlockProductionError> {
         self.blockchain.current_block().await.map_err(Into::into)
@@ -209,6 +371,8 @@ pub struct BlockReceptionService {
     validator: Arc<dyn BlockBuilder>,
     /// 区块链管理器
     blockchain: Arc<dyn BlockChain>,
+    /// base fee 调整参数（须与出块方一致）
+    base_fee_params: BaseFeeParams,
 }
 
 impl BlockReceptionService {
@@ -220,9 +384,16 @@ impl BlockReceptionService {
         Self {
             validator,
             blockchain,
+            base_fee_params: BaseFeeParams::default(),
         }
     }
 
+    /// 设置 base fee 调整参数（默认主网参数）
+    pub fn with_base_fee_params(mut self, params: BaseFeeParams) -> Self {
+        self.base_fee_params = params;
+        self
+    }
+
     /// 场景2: 接收并处理区块
     ///
     /// 完整流程：
@@ -312,6 +483,119 @@ impl BlockReceptionService {
         Ok(())
     }
 
+    /// 只校验不导入：执行全部检查并返回逐项报告，不调用 `write_block_and_set_head`
+    ///
+    /// 用于批量导入不可信节点的区块前排查问题。单项失败不会中断后续检查；
+    /// 只有读取本地链失败时返回错误。
+    pub async fn validate_only(
+        &self,
+        block: &Block,
+        receipts: &[TransactionReceipt],
+    ) -> Result<ValidationReport, BlockProductionError> {
+        let header = &block.header;
+        let mut checks = vec![(
+            ValidationCheck::BlockRules,
+            self.validator.validate_block(block).await.into(),
+        )];
+
+        // 父区块：规范链上前一高度的区块，且哈希与 parent_hash 一致
+        let parent = if block.number().is_zero() {
+            None
+        } else {
+            self.blockchain
+                .get_blocks_from(block.number() - 1, 1)
+                .await?
+                .into_iter()
+                .find(|parent| parent.hash() == header.parent_hash)
+        };
+        let parent_status = match &parent {
+            Some(_) => CheckStatus::Passed,
+            None if block.number().is_zero() => CheckStatus::Passed,
+            None => CheckStatus::Failed(BlockValidationError::Other(format!(
+                "Parent block not found: {}",
+                header.parent_hash
+            ))),
+        };
+        checks.push((ValidationCheck::Parent, parent_status));
+
+        match &parent {
+            Some(parent) => {
+                let parent_base_fee = parent
+                    .base_fee()
+                    .unwrap_or_else(|| BaseFeeCalculator::initial_base_fee().as_u256());
+                let expected = BaseFeeCalculator::calculate_base_fee_with_params(
+                    &self.base_fee_params,
+                    parent.gas_used().as_u64(),
+                    parent.gas_limit().as_u64(),
+                    parent_base_fee,
+                );
+                let actual = block.base_fee().unwrap_or_default();
+                let base_fee = if actual == expected {
+                    CheckStatus::Passed
+                } else {
+                    CheckStatus::Failed(BlockValidationError::InvalidBaseFee { expected, actual })
+                };
+                checks.push((ValidationCheck::BaseFee, base_fee));
+                checks.push((
+                    ValidationCheck::GasLimit,
+                    GasLimitCalculator::validate_gas_limit(
+                        parent.gas_limit().as_u64(),
+                        block.gas_limit().as_u64(),
+                    )
+                    .into(),
+                ));
+            }
+            None => {
+                let reason = "父区块不可用".to_string();
+                checks.push((ValidationCheck::BaseFee, CheckStatus::Skipped(reason.clone())));
+                checks.push((ValidationCheck::GasLimit, CheckStatus::Skipped(reason)));
+            }
+        }
+
+        checks.push((ValidationCheck::Receipts, Self::check_receipts(block, receipts).into()));
+
+        Ok(ValidationReport {
+            block_number: block.number(),
+            block_hash: block.hash(),
+            checks,
+        })
+    }
+
+    /// 收据与区块头一致性：数量、最后一条收据的累计 gas、logs bloom 并集
+    fn check_receipts(
+        block: &Block,
+        receipts: &[TransactionReceipt],
+    ) -> Result<(), BlockValidationError> {
+        if receipts.len() != block.transactions.len() {
+            return Err(BlockValidationError::Other(format!(
+                "Receipt count mismatch: {} transactions, {} receipts",
+                block.transactions.len(),
+                receipts.len()
+            )));
+        }
+
+        let cumulative = receipts
+            .last()
+            .map(|receipt| receipt.cumulative_gas_used)
+            .unwrap_or_default();
+        if cumulative != block.gas_used() {
+            return Err(BlockValidationError::Other(format!(
+                "Receipt gas mismatch: header {}, receipts {}",
+                block.gas_used(),
+                cumulative
+            )));
+        }
+
+        let mut bloom = Bloom::zero();
+        for receipt in receipts {
+            bloom.accrue_bloom(&receipt.logs_bloom);
+        }
+        if bloom != block.header.logs_bloom {
+            return Err(BlockValidationError::Other("Logs bloom mismatch".to_string()));
+        }
+        Ok(())
+    }
+
     /// 验证父区块存在
     async fn verify_parent_exists(&self, block: &Block) -> Result<bool, BlockProductionError> {
         // 如果是创世区块，不需要父区块
@@ -356,11 +640,23 @@ impl BlockReceptionService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::infrastructure::clock_impl::MockClock;
     use crate::infrastructure::transaction_repo_impl::{TxPoolConfig, TxPoolImpl};
     use crate::service::build_block_impl::BuildBlockService;
     use crate::service::repo::block_repo::InMemoryBlockRepository;
     use crate::service::blockchain_impl::BlockChainImpl;
-    use ethereum_types::{Address, H256};
+    use ethereum_types::H256;
+    use std::time::Duration;
+
+    /// 创世区块之上、父区块半满的构建环境
+    fn test_env() -> BuildEnvironment {
+        BuildEnvironment::builder()
+            .parent_gas_used(U64::from(15_000_000))
+            .timestamp(U64::from(1234567890))
+            .prev_randao(H256::repeat_byte(0x42))
+            .build()
+            .unwrap()
+    }
 
     /// 场景1测试: 矿工构建新区块
     #[tokio::test]
@@ -384,18 +680,7 @@ mod tests {
         );
 
         // 构建环境
-        let env = BuildEnvironment {
-            parent_hash: H256::zero(),
-            parent_number: U64::zero(),
-            parent_gas_used: U64::from(15_000_000),
-            parent_gas_limit: U64::from(30_000_000),
-            parent_base_fee: ethereum_types::U256::from(1_000_000_000u64),
-            timestamp: U64::from(1234567890),
-            fee_recipient: Address::zero(),
-            prev_randao: H256::random(),
-            withdrawals: vec![],
-            parent_beacon_block_root: None,
-        };
+        let env = test_env();
 
         // 执行：生产区块
         // 注意：这个测试会失败，因为 BlockChain 的方法还没实现
@@ -416,6 +701,72 @@ mod tests {
         }
     }
 
+    /// 固定对端数量的发现源
+    struct FixedPeers(usize);
+
+    impl PeerInfo for FixedPeers {
+        fn peer_count(&self) -> usize {
+            self.0
+        }
+    }
+
+    #[tokio::test]
+    async fn test_produce_block_waits_for_min_peers() {
+        let production_service = |peers: usize| {
+            let tx_pool = Arc::new(TxPoolImpl::new(TxPoolConfig::default()));
+            let builder = Arc::new(BuildBlockService::new(tx_pool, Some(30_000_000)));
+            let repository = Arc::new(InMemoryBlockRepository::new());
+            let blockchain = Arc::new(BlockChainImpl::new(repository));
+            BlockProductionService::new(builder, blockchain, Arc::new(MockBroadcaster))
+                .with_peer_gate(Arc::new(FixedPeers(peers)), 3)
+        };
+
+        // 低于门槛：拒绝出块
+        let below = production_service(2);
+        match below.produce_block(test_env()).await {
+            Err(BlockProductionError::NotReady { peers, min_peers }) => {
+                assert_eq!((peers, min_peers), (2, 3));
+            }
+            other => panic!("对端不足应拒绝出块: {:?}", other.map(|b| b.number())),
+        }
+
+        // 达到及超过门槛：正常出块
+        for peers in [3, 10] {
+            let ready = production_service(peers);
+            let block = ready.produce_block(test_env()).await.unwrap();
+            assert_eq!(block.number(), U64::one());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_environment_now_uses_clock() {
+        let tx_pool = Arc::new(TxPoolImpl::new(TxPoolConfig::default()));
+        let builder = Arc::new(BuildBlockService::new(tx_pool, Some(30_000_000)));
+        let (_, blockchain, _) = genesis_chain_and_child().await;
+        let genesis = blockchain.current_block().await.unwrap();
+        let genesis_time = genesis.header.timestamp.as_u64();
+
+        let clock = MockClock::new(genesis_time + 100);
+        let production = BlockProductionService::new(builder, blockchain, Arc::new(MockBroadcaster))
+            .with_clock(Arc::new(clock.clone()));
+        let recipient = Address::repeat_byte(0x11);
+
+        let env = production.environment_now(recipient).await.unwrap();
+        assert_eq!(env.timestamp, U64::from(genesis_time + 100));
+        assert_eq!(env.parent_hash, genesis.hash());
+        assert_eq!(env.fee_recipient, recipient);
+
+        clock.advance(Duration::from_secs(12));
+        let env = production.environment_now(recipient).await.unwrap();
+        assert_eq!(env.timestamp, U64::from(genesis_time + 112));
+
+        // 时钟落后于父区块：取父区块时间戳 + 1
+        let behind = MockClock::new(genesis_time.saturating_sub(10));
+        let production = production.with_clock(Arc::new(behind));
+        let env = production.environment_now(recipient).await.unwrap();
+        assert_eq!(env.timestamp, U64::from(genesis_time + 1));
+    }
+
     /// 场景2测试: 验证者接收区块
     #[tokio::test]
     async fn test_scenario2_receive_block() {
@@ -431,20 +782,9 @@ mod tests {
 
         // 先构建一个区块
         let builder = BuildBlockService::new(tx_pool, Some(30_000_000));
-        let env = BuildEnvironment {
-            parent_hash: H256::zero(),
-            parent_number: U64::zero(),
-            parent_gas_used: U64::from(15_000_000),
-            parent_gas_limit: U64::from(30_000_000),
-            parent_base_fee: ethereum_types::U256::from(1_000_000_000u64),
-            timestamp: U64::from(1234567890),
-            fee_recipient: Address::zero(),
-            prev_randao: H256::random(),
-            withdrawals: vec![],
-            parent_beacon_block_root: None,
-        };
+        let env = test_env();
 
-        let block = builder.build_block(env).await.unwrap();
+        let block = builder.build_block(env).await.unwrap().block;
         let receipts = Vec::new();
 
         // 执行：接收区块
@@ -469,21 +809,10 @@ mod tests {
         let tx_pool = Arc::new(TxPoolImpl::new(TxPoolConfig::default()));
         let builder = BuildBlockService::new(tx_pool, Some(30_000_000));
 
-        let env = BuildEnvironment {
-            parent_hash: H256::zero(),
-            parent_number: U64::zero(),
-            parent_gas_used: U64::from(15_000_000),
-            parent_gas_limit: U64::from(30_000_000),
-            parent_base_fee: ethereum_types::U256::from(1_000_000_000u64),
-            timestamp: U64::from(1234567890),
-            fee_recipient: Address::zero(),
-            prev_randao: H256::random(),
-            withdrawals: vec![],
-            parent_beacon_block_root: None,
-        };
+        let env = test_env();
 
         // 执行：只构建，不持久化
-        let block = builder.build_block(env).await.unwrap();
+        let block = builder.build_block(env).await.unwrap().block;
 
         // 验证：构建逻辑
         assert_eq!(block.number(), U64::one());
@@ -524,18 +853,7 @@ mod tests {
         let receiver = BlockReceptionService::new(validator_b, blockchain_b);
 
         // 1. 节点A生产区块
-        let env = BuildEnvironment {
-            parent_hash: H256::zero(),
-            parent_number: U64::zero(),
-            parent_gas_used: U64::from(15_000_000),
-            parent_gas_limit: U64::from(30_000_000),
-            parent_base_fee: ethereum_types::U256::from(1_000_000_000u64),
-            timestamp: U64::from(1234567890),
-            fee_recipient: Address::zero(),
-            prev_randao: H256::random(),
-            withdrawals: vec![],
-            parent_beacon_block_root: None,
-        };
+        let env = test_env();
 
         // 注意：这会失败因为持久化未实现，但展示了完整流程
         let block_result = producer.produce_block(env).await;
@@ -560,4 +878,107 @@ mod tests {
             }
         }
     }
+
+    /// 创世区块在链上、以其为父区块构建的合法区块 #1
+    async fn genesis_chain_and_child() -> (BlockReceptionService, Arc<dyn BlockChain>, Block) {
+        use crate::domain::block_types::BlockHeader;
+        use ethereum_types::U256;
+
+        let genesis = Block {
+            header: BlockHeader {
+                parent_hash: H256::zero(),
+                ommers_hash: BlockHeader::empty_ommers_hash(),
+                fee_recipient: Address::zero(),
+                state_root: H256::zero(),
+                transactions_root: H256::zero(),
+                receipts_root: H256::zero(),
+                logs_bloom: Bloom::zero(),
+                difficulty: U256::zero(),
+                number: U64::zero(),
+                gas_limit: U64::from(30_000_000),
+                gas_used: U64::from(20_000_000),
+                timestamp: U64::from(1_700_000_000),
+                extra_data: vec![],
+                mix_hash: H256::zero(),
+                nonce: 0,
+                base_fee_per_gas: Some(U256::from(1_000_000_000u64)),
+                withdrawals_root: None,
+                blob_gas_used: None,
+                excess_blob_gas: None,
+                parent_beacon_block_root: None,
+            },
+            transactions: vec![],
+            withdrawals: vec![],
+        };
+
+        let tx_pool = Arc::new(TxPoolImpl::new(TxPoolConfig::default()));
+        let builder = Arc::new(BuildBlockService::new(tx_pool, Some(30_000_000)));
+        let repository = Arc::new(InMemoryBlockRepository::new());
+        let blockchain = Arc::new(
+            BlockChainImpl::new_with_genesis(repository, genesis.clone())
+                .await
+                .unwrap(),
+        ) as Arc<dyn BlockChain>;
+
+        let env = BuildEnvironment::builder()
+            .parent(&genesis)
+            .timestamp(U64::from(1_700_000_012))
+            .prev_randao(H256::repeat_byte(0x42))
+            .build()
+            .unwrap();
+        let block = builder.build_block(env).await.unwrap().block;
+
+        let service = BlockReceptionService::new(builder, blockchain.clone());
+        (service, blockchain, block)
+    }
+
+    #[tokio::test]
+    async fn test_validate_only_valid_block() {
+        let (service, blockchain, block) = genesis_chain_and_child().await;
+
+        let report = service.validate_only(&block, &[]).await.unwrap();
+
+        assert!(report.is_valid(), "{:?}", report.checks);
+        assert_eq!(report.checks.len(), 5);
+        assert_eq!(report.block_number, U64::one());
+        // 只校验，不写入链
+        assert_eq!(blockchain.current_block_number().await.unwrap(), U64::zero());
+    }
+
+    #[tokio::test]
+    async fn test_validate_only_flags_wrong_base_fee() {
+        let (service, _, mut block) = genesis_chain_and_child().await;
+        let expected = block.base_fee().unwrap();
+        block.header.base_fee_per_gas = Some(expected + 1);
+
+        let report = service.validate_only(&block, &[]).await.unwrap();
+
+        assert!(!report.is_valid());
+        assert_eq!(
+            report.status(ValidationCheck::BaseFee),
+            Some(&CheckStatus::Failed(BlockValidationError::InvalidBaseFee {
+                expected,
+                actual: expected + 1,
+            }))
+        );
+        let failed: Vec<_> = report.failures().map(|(check, _)| *check).collect();
+        assert_eq!(failed, vec![ValidationCheck::BaseFee]);
+    }
+
+    #[tokio::test]
+    async fn test_validate_only_reports_block_validator_failure() {
+        let (service, _, mut block) = genesis_chain_and_child().await;
+        block.header.gas_used = block.header.gas_limit + 1;
+
+        let report = service.validate_only(&block, &[]).await.unwrap();
+
+        // 与 receive_block 使用同一个区块验证器
+        assert_eq!(
+            report.status(ValidationCheck::BlockRules),
+            Some(&CheckStatus::Failed(BlockValidationError::GasLimitExceeded {
+                limit: block.gas_limit().as_u64(),
+                used: block.gas_used().as_u64(),
+            }))
+        );
+    }
 }
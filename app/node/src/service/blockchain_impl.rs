@@ -1,10 +1,15 @@
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use async_trait::async_trait;
-use ethereum_types::U64;
+use ethereum_types::{H256, U256, U64};
 use crate::domain::block_types::{Block, BlockValidationError};
 use crate::domain::receipt_types::TransactionReceipt;
 use crate::service::build_block_trait::BlockChain;
+use crate::service::fork_choice::ForkChoiceStore;
 use crate::service::repo::block_repo::{BlockRepository, BlockRepositoryError};
+use tokio::sync::broadcast;
+
+/// 新链头通知的缓冲容量（订阅者落后超过此数量会收到 Lagged）
+const NEW_HEADS_CAPACITY: usize = 64;
 
 /// 区块链实现（管理链状态）
 ///
@@ -12,34 +17,74 @@ use crate::service::repo::block_repo::{BlockRepository, BlockRepositoryError};
 pub struct BlockChainImpl {
     /// 底层区块存储
     repository: Arc<dyn BlockRepository>,
+    /// 当前链头区块号（对应 geth 的 currentBlock 指针）
+    head: RwLock<Option<U64>>,
+    /// 链头更新锁：串行化所有链头写入（对应 geth 的 chainmu），
+    /// 持有期间完成“比较 - 写规范映射 - 更新链头”，避免并发写入使链头回退
+    head_update: tokio::sync::Mutex<()>,
+    /// 新链头通知（对应 geth 的 chainHeadFeed）
+    new_heads: broadcast::Sender<Block>,
+    /// 分叉选择权重（仅在持有链头锁时读写）
+    fork_choice: RwLock<ForkChoiceStore>,
     // TODO: 添加区块验证器（来自 BuildBlockService）
     // validator: Arc<dyn BlockBuilder>,
 }
 
 impl BlockChainImpl {
     pub fn new(repository: Arc<dyn BlockRepository>) -> Self {
-        Self { repository }
+        Self {
+            repository,
+            head: RwLock::new(None),
+            head_update: tokio::sync::Mutex::new(()),
+            new_heads: broadcast::channel(NEW_HEADS_CAPACITY).0,
+            fork_choice: RwLock::new(ForkChoiceStore::new()),
+        }
     }
 
     /// 创建带创世区块的链
     pub async fn new_with_genesis(
         repository: Arc<dyn BlockRepository>,
-        _genesis: Block,
+        genesis: Block,
     ) -> Result<Self, BlockRepositoryError> {
-        // TODO: 初始化创世区块
-        Ok(Self { repository })
+        let hash = genesis.hash();
+        if repository.get_block_by_hash(&hash).await?.is_none() {
+            repository
+                .save_block(&genesis, &[], genesis.header.difficulty)
+                .await?;
+        }
+        repository.set_canonical_hash(genesis.number(), hash).await?;
+
+        Ok(Self {
+            repository,
+            head: RwLock::new(Some(genesis.number())),
+            head_update: tokio::sync::Mutex::new(()),
+            new_heads: broadcast::channel(NEW_HEADS_CAPACITY).0,
+            fork_choice: RwLock::new(ForkChoiceStore::new()),
+        })
     }
+
+    fn head_number(&self) -> Option<U64> {
+        *self.head.read().unwrap()
+    }
+}
+
+fn storage_error(err: BlockRepositoryError) -> BlockValidationError {
+    BlockValidationError::Other(err.to_string())
 }
 
 #[async_trait]
 impl BlockChain for BlockChainImpl {
     async fn current_block(&self) -> Result<Block, BlockRepositoryError> {
-        // TODO: 从缓存或数据库获取当前区块
-        todo!("实现获取当前区块")
+        let number = self.current_block_number().await?;
+        self.repository
+            .get_block_by_number(number)
+            .await?
+            .ok_or(BlockRepositoryError::BlockNumberNotFound { number })
     }
 
     async fn current_block_number(&self) -> Result<U64, BlockRepositoryError> {
-        todo!("实现获取当前区块号")
+        self.head_number()
+            .ok_or(BlockRepositoryError::BlockNumberNotFound { number: U64::zero() })
     }
 
     async fn genesis(&self) -> Result<Block, BlockRepositoryError> {
@@ -52,45 +97,145 @@ impl BlockChain for BlockChainImpl {
 
     async fn insert_block(
         &self,
-        _block: Block,
-        _receipts: Vec<TransactionReceipt>,
+        block: Block,
+        receipts: Vec<TransactionReceipt>,
     ) -> Result<(), BlockValidationError> {
-        // TODO: 实现区块插入逻辑
-        // 1. 验证区块
-        // 2. 检查父区块
-        // 3. 执行交易
-        // 4. 持久化
-        todo!("实现区块插入")
+        // 区块规则与父区块校验由调用方（BuildBlockService / BlockReceptionService）完成，
+        // 这里只负责计算总难度并持久化
+        let parent_td = self
+            .repository
+            .get_total_difficulty(&block.header.parent_hash)
+            .await
+            .map_err(storage_error)?
+            .unwrap_or_default();
+        let total_difficulty = parent_td + block.header.difficulty;
+
+        self.repository
+            .save_block(&block, &receipts, total_difficulty)
+            .await
+            .map_err(storage_error)
     }
 
     async fn write_block_and_set_head(
         &self,
-        _block: Block,
-        _receipts: Vec<TransactionReceipt>,
+        block: Block,
+        receipts: Vec<TransactionReceipt>,
     ) -> Result<(), BlockValidationError> {
-        // TODO: 实现写入并设置链头
-        // 1. insert_block
-        // 2. set_canonical_hash
-        // 3. 更新内存缓存
-        todo!("实现写入并设置链头")
+        let number = block.number();
+        let hash = block.hash();
+
+        // 链头只前进：同高度或更低的区块被拒绝，同高度并发写入按加锁顺序先到先得
+        let _guard = self.head_update.lock().await;
+        if let Some(head) = self.head_number().filter(|head| number <= *head) {
+            return Err(BlockValidationError::NotAboveHead { number, head });
+        }
+
+        self.insert_block(block.clone(), receipts).await?;
+        self.repository
+            .set_canonical_hash(number, hash)
+            .await
+            .map_err(storage_error)?;
+        *self.head.write().unwrap() = Some(number);
+
+        // 仍持有链头锁，保证通知顺序与链头推进顺序一致；没有订阅者时发送失败可忽略
+        let _ = self.new_heads.send(block);
+        Ok(())
     }
 
-    async fn set_head(&self, _number: U64) -> Result<(), BlockRepositoryError> {
-        // TODO: 设置链头到指定高度
-        todo!("实现设置链头")
+    async fn set_head(&self, number: U64) -> Result<(), BlockRepositoryError> {
+        // 显式重组路径：允许回退，但同样与其他链头写入串行
+        let _guard = self.head_update.lock().await;
+        if self.repository.get_canonical_hash(number).await?.is_none() {
+            return Err(BlockRepositoryError::BlockNumberNotFound { number });
+        }
+
+        // 删除新链头之上的规范链映射
+        if let Some(head) = self.head_number() {
+            let mut n = head;
+            while n > number {
+                self.repository.delete_canonical_hash(n).await?;
+                n -= U64::one();
+            }
+        }
+        *self.head.write().unwrap() = Some(number);
+        Ok(())
+    }
+
+    async fn set_head_with_weight(
+        &self,
+        hash: H256,
+        fork_choice_weight: U256,
+    ) -> Result<bool, BlockRepositoryError> {
+        let _guard = self.head_update.lock().await;
+        let block = self
+            .repository
+            .get_block_by_hash(&hash)
+            .await?
+            .ok_or(BlockRepositoryError::BlockNotFound { hash })?;
+        self.fork_choice.write().unwrap().set_weight(hash, fork_choice_weight);
+
+        let current = match self.head_number() {
+            Some(head) => self.repository.get_canonical_hash(head).await?,
+            None => None,
+        };
+        if let Some(current) = current {
+            if current == hash || !self.fork_choice.read().unwrap().is_preferred(&hash, &current) {
+                return Ok(false);
+            }
+        }
+
+        // 先沿父区块回溯到与现有规范链的汇合点，收集整条分支；
+        // 回溯中途失败（父区块缺失）时规范链保持不变
+        let number = block.number();
+        let mut branch = Vec::new();
+        let mut cursor = block.clone();
+        loop {
+            let n = cursor.number();
+            if self.repository.get_canonical_hash(n).await? == Some(cursor.hash()) {
+                break;
+            }
+            branch.push((n, cursor.hash()));
+            if n.is_zero() {
+                break;
+            }
+            let parent = cursor.header.parent_hash;
+            cursor = self
+                .repository
+                .get_block_by_hash(&parent)
+                .await?
+                .ok_or(BlockRepositoryError::BlockNotFound { hash: parent })?;
+        }
+
+        // 一次性改写分支上的规范映射（旧链头高于新链头时删除多出的映射），再更新链头
+        self.repository.reorg_canonical_chain(&branch, number).await?;
+        *self.head.write().unwrap() = Some(number);
+
+        let _ = self.new_heads.send(block);
+        Ok(true)
     }
 
     async fn reset(&self) -> Result<(), BlockRepositoryError> {
-        // TODO: 重置链到创世区块
-        todo!("实现链重置")
+        self.set_head(U64::zero()).await
+    }
+
+    fn subscribe_new_heads(&self) -> broadcast::Receiver<Block> {
+        self.new_heads.subscribe()
     }
 
     async fn get_blocks_from(
         &self,
-        _start: U64,
-        _count: usize,
+        start: U64,
+        count: usize,
     ) -> Result<Vec<Block>, BlockRepositoryError> {
-        // TODO: 批量获取区块
-        todo!("实现批量获取区块")
+        let mut blocks = Vec::with_capacity(count);
+        let mut number = start;
+        while blocks.len() < count {
+            match self.repository.get_block_by_number(number).await? {
+                Some(block) => blocks.push(block),
+                None => break,
+            }
+            number += U64::one();
+        }
+        Ok(blocks)
     }
 }
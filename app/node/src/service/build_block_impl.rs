@@ -1,42 +1,133 @@
-/// BuildBlockService 实现 - 区块构建服务
-///
-/// 职责：
-/// 1. 编排区块构建流程
-/// 2. 集成交易池、状态执行器
-/// 3. 计算区块头字段
-/// 4. 验证区块合法性
-///
-/// 参考: geth/miner/worker.go
+//! BuildBlockService 实现 - 区块构建服务
+//!
+//! 职责：
+//! 1. 编排区块构建流程
+//! 2. 集成交易池、状态执行器
+//! 3. 计算区块头字段
+//! 4. 验证区块合法性
+//!
+//! 参考: geth/miner/worker.go
 
 use crate::domain::block_types::{
-    Block, BlockHeader, BlockValidationError, BuildEnvironment, Withdrawal,
+    Block, BlockBuildSummary, BlockHeader, BlockValidationError, BuildEnvironment, BuiltBlock,
+    Withdrawal,
 };
 use crate::domain::receipt_types::TransactionReceipt;
 use crate::domain::tx_types::DynamicFeeTx;
+use crate::domain::units::{Gwei, Wei};
 use crate::service::build_block_trait::BlockBuilder;
-use crate::service::repo::transaction_repo::TxPool;
+use crate::service::repo::transaction_repo::{PendingTx, TxPool};
 use async_trait::async_trait;
-use ethereum_types::{Bloom, H256, U256, U64};
+use ethereum_types::{Address, Bloom, H256, U256, U64};
+use std::fmt;
 use std::sync::Arc;
 
+/// Base fee 调整参数（EIP-1559）
+///
+/// 主网为弹性乘数 2、最大变化分母 8；部分 L2 / 测试网使用不同取值。
+/// 两个参数都是除数，只能通过 `new` 校验后创建
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BaseFeeParams {
+    /// 弹性乘数：gas_target = gas_limit / elasticity_multiplier
+    elasticity_multiplier: u64,
+    /// 最大变化分母：单个区块 base fee 最多变化 1 / max_change_denominator
+    max_change_denominator: u64,
+}
+
+impl BaseFeeParams {
+    /// 以太坊主网参数
+    pub const MAINNET: Self = Self {
+        elasticity_multiplier: 2,
+        max_change_denominator: 8,
+    };
+
+    /// 创建调整参数，拒绝为零的取值
+    pub fn new(
+        elasticity_multiplier: u64,
+        max_change_denominator: u64,
+    ) -> Result<Self, BaseFeeParamsError> {
+        if elasticity_multiplier == 0 {
+            return Err(BaseFeeParamsError::ZeroElasticityMultiplier);
+        }
+        if max_change_denominator == 0 {
+            return Err(BaseFeeParamsError::ZeroMaxChangeDenominator);
+        }
+        Ok(Self {
+            elasticity_multiplier,
+            max_change_denominator,
+        })
+    }
+
+    /// 弹性乘数
+    pub fn elasticity_multiplier(&self) -> u64 {
+        self.elasticity_multiplier
+    }
+
+    /// 最大变化分母
+    pub fn max_change_denominator(&self) -> u64 {
+        self.max_change_denominator
+    }
+}
+
+/// base fee 调整参数无效
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaseFeeParamsError {
+    /// 弹性乘数为零
+    ZeroElasticityMultiplier,
+    /// 最大变化分母为零
+    ZeroMaxChangeDenominator,
+}
+
+impl fmt::Display for BaseFeeParamsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ZeroElasticityMultiplier => {
+                write!(f, "Base fee elasticity multiplier must be greater than zero")
+            }
+            Self::ZeroMaxChangeDenominator => {
+                write!(f, "Base fee max change denominator must be greater than zero")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BaseFeeParamsError {}
+
+impl Default for BaseFeeParams {
+    fn default() -> Self {
+        Self::MAINNET
+    }
+}
+
 /// Base Fee计算器 (EIP-1559)
 ///
 /// 参考: https://eips.ethereum.org/EIPS/eip-1559
 ///
-/// Base fee动态调整算法：
-/// - 目标使用率: 50% (gas_target = gas_limit / 2)
-/// - 使用率 > 50%: base fee上涨
-/// - 使用率 < 50%: base fee下降
-/// - 最大变化率: 12.5% (1/8)
+/// Base fee动态调整算法（括号内为主网参数，见 `BaseFeeParams`）：
+/// - 目标使用率: 1 / 弹性乘数 (gas_target = gas_limit / elasticity_multiplier，主网为 gas_limit / 2)
+/// - 使用率高于目标: base fee上涨
+/// - 使用率低于目标: base fee下降
+/// - 最大变化率: 1 / 最大变化分母 (12.5%)
 pub struct BaseFeeCalculator;
 
 impl BaseFeeCalculator {
-    /// EIP-1559常量
-    const BASE_FEE_MAX_CHANGE_DENOMINATOR: u64 = 8; // 最大变化率 12.5%
-    const ELASTICITY_MULTIPLIER: u64 = 2; // 弹性乘数
-    const INITIAL_BASE_FEE: u64 = 1_000_000_000; // 1 Gwei
+    const INITIAL_BASE_FEE: Gwei = Gwei::new(1);
+
+    /// 按主网参数计算下一个区块的base fee
+    pub fn calculate_base_fee(
+        parent_gas_used: u64,
+        parent_gas_limit: u64,
+        parent_base_fee: U256,
+    ) -> U256 {
+        Self::calculate_base_fee_with_params(
+            &BaseFeeParams::MAINNET,
+            parent_gas_used,
+            parent_gas_limit,
+            parent_base_fee,
+        )
+    }
 
-    /// 计算下一个区块的base fee
+    /// 按指定参数计算下一个区块的base fee
     ///
     /// 算法 (EIP-1559):
     /// ```python
@@ -55,12 +146,13 @@ impl BaseFeeCalculator {
     /// else:
     ///     return parent_base_fee
     /// ```
-    pub fn calculate_base_fee(
+    pub fn calculate_base_fee_with_params(
+        params: &BaseFeeParams,
         parent_gas_used: u64,
         parent_gas_limit: u64,
         parent_base_fee: U256,
     ) -> U256 {
-        let gas_target = parent_gas_limit / Self::ELASTICITY_MULTIPLIER;
+        let gas_target = parent_gas_limit / params.elasticity_multiplier;
 
         if parent_gas_used == gas_target {
             // 使用量正好等于目标，base fee不变
@@ -73,7 +165,7 @@ impl BaseFeeCalculator {
             let base_fee_delta = std::cmp::max(
                 (parent_base_fee * U256::from(gas_used_delta))
                     / U256::from(gas_target)
-                    / U256::from(Self::BASE_FEE_MAX_CHANGE_DENOMINATOR),
+                    / U256::from(params.max_change_denominator),
                 U256::one(),
             );
             parent_base_fee + base_fee_delta
@@ -82,7 +174,7 @@ impl BaseFeeCalculator {
             let gas_used_delta = gas_target - parent_gas_used;
             let base_fee_delta = (parent_base_fee * U256::from(gas_used_delta))
                 / U256::from(gas_target)
-                / U256::from(Self::BASE_FEE_MAX_CHANGE_DENOMINATOR);
+                / U256::from(params.max_change_denominator);
 
             // Base fee最小为0
             parent_base_fee.saturating_sub(base_fee_delta)
@@ -90,8 +182,65 @@ impl BaseFeeCalculator {
     }
 
     /// 获取初始base fee (创世区块)
-    pub fn initial_base_fee() -> U256 {
-        U256::from(Self::INITIAL_BASE_FEE)
+    pub fn initial_base_fee() -> Wei {
+        Self::INITIAL_BASE_FEE
+            .to_wei()
+            .expect("initial base fee fits in U256")
+    }
+}
+
+/// Blob Base Fee计算器 (EIP-4844)
+///
+/// 参考: https://eips.ethereum.org/EIPS/eip-4844
+///
+/// blob base fee 由区块头的 excess_blob_gas 决定：
+/// `fake_exponential(MIN_BLOB_BASE_FEE, excess_blob_gas, BLOB_BASE_FEE_UPDATE_FRACTION)`
+pub struct BlobBaseFeeCalculator;
+
+impl BlobBaseFeeCalculator {
+    /// 最低 blob base fee (1 wei)
+    pub const MIN_BLOB_BASE_FEE: u64 = 1;
+    /// 更新分母 (Cancun)
+    pub const BLOB_BASE_FEE_UPDATE_FRACTION: u64 = 3_338_477;
+
+    /// 按 excess_blob_gas 计算 blob base fee
+    pub fn blob_base_fee(excess_blob_gas: U64) -> U256 {
+        Self::fake_exponential(
+            U256::from(Self::MIN_BLOB_BASE_FEE),
+            U256::from(excess_blob_gas.as_u64()),
+            U256::from(Self::BLOB_BASE_FEE_UPDATE_FRACTION),
+        )
+    }
+
+    /// 整数近似 `factor * e ** (numerator / denominator)`（泰勒展开）
+    ///
+    /// ```python
+    /// i = 1
+    /// output = 0
+    /// numerator_accum = factor * denominator
+    /// while numerator_accum > 0:
+    ///     output += numerator_accum
+    ///     numerator_accum = (numerator_accum * numerator) // (denominator * i)
+    ///     i += 1
+    /// return output // denominator
+    /// ```
+    ///
+    /// 中间值超出 U256 时结果同样超出，饱和为 `U256::MAX`
+    pub fn fake_exponential(factor: U256, numerator: U256, denominator: U256) -> U256 {
+        Self::checked_fake_exponential(factor, numerator, denominator).unwrap_or(U256::MAX)
+    }
+
+    fn checked_fake_exponential(factor: U256, numerator: U256, denominator: U256) -> Option<U256> {
+        let mut i = U256::one();
+        let mut output = U256::zero();
+        let mut numerator_accum = factor.checked_mul(denominator)?;
+        while !numerator_accum.is_zero() {
+            output = output.checked_add(numerator_accum)?;
+            numerator_accum =
+                numerator_accum.checked_mul(numerator)? / denominator.checked_mul(i)?;
+            i += U256::one();
+        }
+        Some(output / denominator)
     }
 }
 
@@ -188,6 +337,60 @@ impl GasLimitCalculator {
     }
 }
 
+/// 排序键：越大越优先
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct OrderKey(U256);
+
+impl OrderKey {
+    /// 数值越大越优先（如小费）
+    pub fn descending(value: U256) -> Self {
+        Self(value)
+    }
+
+    /// 数值越小越优先（如入池序号）
+    pub fn ascending(value: U256) -> Self {
+        Self(!value)
+    }
+}
+
+/// 交易排序策略
+///
+/// 出块时按 `rank` 降序选择交易，排序键相同时按交易哈希升序决胜
+pub trait OrderingStrategy: Send + Sync {
+    /// 计算交易在当前 base fee 下的排序键
+    fn rank(&self, tx: &PendingTx, base_fee: U256) -> OrderKey;
+}
+
+/// 按 effective priority fee 排序（默认，出块收益最大化）
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PriorityFeeOrdering;
+
+impl OrderingStrategy for PriorityFeeOrdering {
+    fn rank(&self, tx: &PendingTx, base_fee: U256) -> OrderKey {
+        OrderKey::descending(TransactionSelector::effective_priority_fee(&tx.tx, &base_fee))
+    }
+}
+
+/// 按入池先后排序（先到先得）
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FifoOrdering;
+
+impl OrderingStrategy for FifoOrdering {
+    fn rank(&self, tx: &PendingTx, _base_fee: U256) -> OrderKey {
+        OrderKey::ascending(U256::from(tx.arrival))
+    }
+}
+
+/// 按出价 max_fee_per_gas 排序（与 EIP-1559 之前的 gasPrice 排序一致）
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GasPriceOrdering;
+
+impl OrderingStrategy for GasPriceOrdering {
+    fn rank(&self, tx: &PendingTx, _base_fee: U256) -> OrderKey {
+        OrderKey::descending(tx.tx.max_fee_per_gas)
+    }
+}
+
 /// 交易选择器
 ///
 /// 负责从交易池选择最优交易组合 (装箱问题)
@@ -200,13 +403,14 @@ impl TransactionSelector {
     ///
     /// 策略参考 geth/miner/worker.go:
     /// 1. 过滤: max_fee_per_gas >= base_fee
-    /// 2. 排序: 按 effective_priority_fee 降序
+    /// 2. 排序: 按 effective_priority_fee 降序，相同时按交易哈希升序
     /// 3. 装箱: 累计gas不超过gas_limit
     ///
     /// 注意:
     /// - effective_priority_fee = min(max_priority_fee, max_fee - base_fee)
     /// - 优先选择给矿工小费高的交易
     /// - 区块填充到95%停止 (留一些缓冲空间)
+    /// - 小费相同时以交易哈希决胜，结果与候选交易的输入顺序无关，出块可复现
     ///
     /// 参数:
     /// - candidates: 候选交易列表
@@ -218,6 +422,27 @@ impl TransactionSelector {
         candidates: Vec<DynamicFeeTx>,
         gas_limit: u64,
         base_fee: U256,
+    ) -> Vec<DynamicFeeTx> {
+        let candidates = candidates
+            .into_iter()
+            .enumerate()
+            .map(|(arrival, tx)| PendingTx {
+                tx,
+                arrival: arrival as u64,
+            })
+            .collect();
+        Self::select_with_strategy(candidates, gas_limit, base_fee, &PriorityFeeOrdering)
+    }
+
+    /// 按指定排序策略选择交易
+    ///
+    /// 过滤与装箱规则同 `select_transactions`，排序改为按 `strategy.rank` 降序、
+    /// 相同时按交易哈希升序
+    pub fn select_with_strategy<S: OrderingStrategy + ?Sized>(
+        candidates: Vec<PendingTx>,
+        gas_limit: u64,
+        base_fee: U256,
+        strategy: &S,
     ) -> Vec<DynamicFeeTx> {
         let mut selected = Vec::new();
         let mut total_gas: u64 = 0;
@@ -225,18 +450,19 @@ impl TransactionSelector {
         // Step 1: 过滤低价交易
         let mut valid_txs: Vec<_> = candidates
             .into_iter()
-            .filter(|tx| tx.max_fee_per_gas >= base_fee)
+            .filter(|pending| pending.tx.max_fee_per_gas >= base_fee)
             .collect();
 
-        // Step 2: 按effective priority fee降序排序
-        valid_txs.sort_by(|a, b| {
-            let a_priority = Self::effective_priority_fee(a, &base_fee);
-            let b_priority = Self::effective_priority_fee(b, &base_fee);
-            b_priority.cmp(&a_priority) // 降序
+        // Step 2: 按排序键降序排序，相同时按哈希升序
+        valid_txs.sort_by_cached_key(|pending| {
+            (
+                std::cmp::Reverse(strategy.rank(pending, base_fee)),
+                pending.tx.hash(),
+            )
         });
 
         // Step 3: 贪心装箱
-        for tx in valid_txs {
+        for PendingTx { tx, .. } in valid_txs {
             let tx_gas = tx.gas_limit.as_u64();
 
             // 检查是否还有空间
@@ -256,22 +482,29 @@ impl TransactionSelector {
 
     /// 计算effective priority fee (矿工实际收益)
     ///
-    /// effective_priority_fee = min(max_priority_fee, max_fee - base_fee)
+    /// effective_priority_fee = effective_gas_price - base_fee
+    ///                        = min(max_priority_fee, max_fee - base_fee)
     ///
     /// 这是矿工/验证者实际能获得的小费金额
     fn effective_priority_fee(tx: &DynamicFeeTx, base_fee: &U256) -> U256 {
-        let max_priority = tx.max_priority_fee_per_gas;
-        let max_fee_minus_base = tx.max_fee_per_gas.saturating_sub(*base_fee);
-
-        // min(max_priority_fee, max_fee - base_fee)
-        if max_priority < max_fee_minus_base {
-            max_priority
-        } else {
-            max_fee_minus_base
-        }
+        tx.effective_gas_price(*base_fee).saturating_sub(*base_fee)
     }
 }
 
+/// 最终 fee recipient 仍为零地址时的处理方式
+///
+/// 零地址收款会使出块收益永久丢失，生产环境应配置为 `Warn` 或 `Reject`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ZeroFeeRecipientPolicy {
+    /// 允许（开发/测试链）
+    #[default]
+    Allow,
+    /// 记录警告后继续出块
+    Warn,
+    /// 拒绝出块
+    Reject,
+}
+
 /// 区块构建服务实现
 ///
 /// 设计原则：
@@ -283,6 +516,14 @@ pub struct BuildBlockService {
     tx_pool: Arc<dyn TxPool>,
     /// 期望的gas limit（矿工配置，None则自动调整）
     desired_gas_limit: Option<u64>,
+    /// 默认 fee recipient（构建环境给出零地址时使用）
+    default_fee_recipient: Option<Address>,
+    /// 零地址 fee recipient 的处理方式
+    zero_fee_recipient_policy: ZeroFeeRecipientPolicy,
+    /// base fee 调整参数
+    base_fee_params: BaseFeeParams,
+    /// 交易排序策略
+    ordering: Arc<dyn OrderingStrategy>,
 }
 
 impl BuildBlockService {
@@ -295,12 +536,66 @@ impl BuildBlockService {
         Self {
             tx_pool,
             desired_gas_limit,
+            default_fee_recipient: None,
+            zero_fee_recipient_policy: ZeroFeeRecipientPolicy::default(),
+            base_fee_params: BaseFeeParams::default(),
+            ordering: Arc::new(PriorityFeeOrdering),
+        }
+    }
+
+    /// 设置 base fee 调整参数（默认主网参数）
+    pub fn with_base_fee_params(mut self, params: BaseFeeParams) -> Self {
+        self.base_fee_params = params;
+        self
+    }
+
+    /// 设置默认 fee recipient（代多个验证者出块时，未指定收款地址的构建使用此地址）
+    pub fn with_default_fee_recipient(mut self, fee_recipient: Address) -> Self {
+        self.default_fee_recipient = Some(fee_recipient);
+        self
+    }
+
+    /// 设置零地址 fee recipient 的处理方式
+    pub fn with_zero_fee_recipient_policy(mut self, policy: ZeroFeeRecipientPolicy) -> Self {
+        self.zero_fee_recipient_policy = policy;
+        self
+    }
+
+    /// 设置交易排序策略（默认按 effective priority fee）
+    pub fn with_ordering_strategy<S: OrderingStrategy + 'static>(mut self, strategy: S) -> Self {
+        self.ordering = Arc::new(strategy);
+        self
+    }
+
+    /// 确定出块收益地址：构建环境给出的非零地址优先，其次为默认地址
+    fn resolve_fee_recipient(&self, env: &BuildEnvironment) -> Result<Address, BlockValidationError> {
+        let fee_recipient = if env.fee_recipient.is_zero() {
+            self.default_fee_recipient.unwrap_or_default()
+        } else {
+            env.fee_recipient
+        };
+
+        if fee_recipient.is_zero() {
+            match self.zero_fee_recipient_policy {
+                ZeroFeeRecipientPolicy::Allow => {}
+                ZeroFeeRecipientPolicy::Warn => {
+                    tracing::warn!(
+                        number = %(env.parent_number + 1),
+                        "fee recipient 为零地址，出块收益将丢失"
+                    );
+                }
+                ZeroFeeRecipientPolicy::Reject => {
+                    return Err(BlockValidationError::ZeroFeeRecipient)
+                }
+            }
         }
+        Ok(fee_recipient)
     }
 
     /// 计算新区块的base fee
     fn calculate_base_fee(&self, env: &BuildEnvironment) -> U256 {
-        BaseFeeCalculator::calculate_base_fee(
+        BaseFeeCalculator::calculate_base_fee_with_params(
+            &self.base_fee_params,
             env.parent_gas_used.as_u64(),
             env.parent_gas_limit.as_u64(),
             env.parent_base_fee,
@@ -320,10 +615,11 @@ impl BuildBlockService {
     async fn get_candidate_transactions(
         &self,
         base_fee: U256,
-    ) -> Result<Vec<DynamicFeeTx>, BlockValidationError> {
-        // 从交易池获取最多1000笔交易（足够填满一个区块）
+    ) -> Result<Vec<PendingTx>, BlockValidationError> {
+        // 取全部可打包交易，由排序策略决定先后、区块 gas limit 决定数量；
+        // 在交易池里预先按 max_fee 截断会让 FIFO 等策略看不到出价较低的交易
         self.tx_pool
-            .get_pending(1000, Some(base_fee.as_u64()))
+            .get_pending_candidates(usize::MAX, Some(base_fee.as_u64()))
             .await
             .map_err(|e| {
                 BlockValidationError::Other(format!("Failed to get pending transactions: {}", e))
@@ -332,20 +628,25 @@ impl BuildBlockService {
 
     /// 选择并执行交易
     ///
-    /// 返回: (选中的交易, 总gas使用量, 收据列表)
+    /// 返回: (选中的交易, 收益摘要, 收据列表)
     async fn select_and_execute_transactions(
         &self,
-        candidates: Vec<DynamicFeeTx>,
+        candidates: Vec<PendingTx>,
         gas_limit: u64,
         base_fee: U256,
-    ) -> Result<(Vec<DynamicFeeTx>, u64, Vec<TransactionReceipt>), BlockValidationError> {
-        // Step 1: 使用贪心算法选择交易
-        let selected_txs =
-            TransactionSelector::select_transactions(candidates, gas_limit, base_fee);
+    ) -> Result<(Vec<DynamicFeeTx>, BlockBuildSummary, Vec<TransactionReceipt>), BlockValidationError> {
+        // Step 1: 按排序策略贪心选择交易
+        let selected_txs = TransactionSelector::select_with_strategy(
+            candidates,
+            gas_limit,
+            base_fee,
+            self.ordering.as_ref(),
+        );
 
         // Step 2: 执行交易并累计gas使用量
         let mut receipts = Vec::new();
         let mut total_gas_used: u64 = 0;
+        let mut total_priority_fees = U256::zero();
         let mut executed_txs = Vec::new();
 
         for tx in selected_txs {
@@ -364,17 +665,27 @@ impl BuildBlockService {
                 receipts.len() as u64,
                 total_gas_used + gas_used,
                 gas_used,
+                tx.effective_gas_price(base_fee),
+                DynamicFeeTx::TRANSACTION_TYPE,
                 true, // 假设交易成功
                 Bloom::zero(),
                 vec![],
             );
 
             total_gas_used += gas_used;
+            total_priority_fees += TransactionSelector::effective_priority_fee(&tx, &base_fee)
+                * U256::from(gas_used);
             receipts.push(receipt);
             executed_txs.push(tx);
         }
 
-        Ok((executed_txs, total_gas_used, receipts))
+        let summary = BlockBuildSummary {
+            total_priority_fees,
+            total_gas_used,
+            tx_count: executed_txs.len(),
+            base_fee_burned: base_fee * U256::from(total_gas_used),
+        };
+        Ok((executed_txs, summary, receipts))
     }
 
     /// 计算交易根 (Merkle-Patricia Trie)
@@ -425,6 +736,7 @@ impl BuildBlockService {
     }
 
     /// 构建区块头
+    #[allow(clippy::too_many_arguments)]
     fn build_header(
         &self,
         env: &BuildEnvironment,
@@ -474,8 +786,13 @@ impl BlockBuilder for BuildBlockService {
     /// 5. 计算Merkle根 (transactions_root, receipts_root, state_root)
     /// 6. 计算logs bloom过滤器
     /// 7. 组装区块头
-    /// 8. 返回完整区块
-    async fn build_block(&self, env: BuildEnvironment) -> Result<Block, BlockValidationError> {
+    /// 8. 返回完整区块及收益摘要
+    async fn build_block(&self, env: BuildEnvironment) -> Result<BuiltBlock, BlockValidationError> {
+        let env = BuildEnvironment {
+            fee_recipient: self.resolve_fee_recipient(&env)?,
+            ..env
+        };
+
         // Step 1: 计算base fee
         let base_fee = self.calculate_base_fee(&env);
 
@@ -489,7 +806,7 @@ impl BlockBuilder for BuildBlockService {
         let candidates = self.get_candidate_transactions(base_fee).await?;
 
         // Step 4: 选择并执行交易
-        let (transactions, gas_used, receipts) = self
+        let (transactions, summary, receipts) = self
             .select_and_execute_transactions(candidates, gas_limit, base_fee)
             .await?;
 
@@ -509,7 +826,7 @@ impl BlockBuilder for BuildBlockService {
             &env,
             base_fee,
             gas_limit,
-            gas_used,
+            summary.total_gas_used,
             transactions_root,
             receipts_root,
             state_root,
@@ -524,7 +841,7 @@ impl BlockBuilder for BuildBlockService {
             withdrawals: env.withdrawals.clone(),
         };
 
-        Ok(block)
+        Ok(BuiltBlock { block, summary })
     }
 
     /// 验证区块 (PoS规则)
@@ -593,13 +910,13 @@ mod tests {
             parent_base_fee: U256::from(1_000_000_000u64),
             timestamp: U64::from(1234567890),
             fee_recipient: Address::zero(),
-            prev_randao: H256::random(),
+            prev_randao: H256::repeat_byte(0x42),
             withdrawals: vec![],
             parent_beacon_block_root: None,
         };
 
         // 构建区块
-        let block = builder.build_block(env).await.unwrap();
+        let block = builder.build_block(env).await.unwrap().block;
 
         // 验证
         assert_eq!(block.number(), U64::one());
@@ -612,6 +929,44 @@ mod tests {
         // block_repo.save(block);
     }
 
+    #[tokio::test]
+    async fn test_fee_recipient_falls_back_to_default() {
+        let tx_pool = Arc::new(TxPoolImpl::new(TxPoolConfig::default()));
+        let default_recipient = Address::repeat_byte(0xfe);
+        let builder = BuildBlockService::new(tx_pool.clone(), None)
+            .with_default_fee_recipient(default_recipient)
+            .with_zero_fee_recipient_policy(ZeroFeeRecipientPolicy::Reject);
+        let env = |fee_recipient| BuildEnvironment {
+            parent_hash: H256::zero(),
+            parent_number: U64::zero(),
+            parent_gas_used: U64::from(15_000_000),
+            parent_gas_limit: U64::from(30_000_000),
+            parent_base_fee: U256::from(1_000_000_000u64),
+            timestamp: U64::from(1234567890),
+            fee_recipient,
+            prev_randao: H256::zero(),
+            withdrawals: vec![],
+            parent_beacon_block_root: None,
+        };
+
+        // 零地址回退到默认地址
+        let block = builder.build_block(env(Address::zero())).await.unwrap().block;
+        assert_eq!(block.header.fee_recipient, default_recipient);
+
+        // 构建环境给出的非零地址优先
+        let proposer = Address::repeat_byte(0x01);
+        let block = builder.build_block(env(proposer)).await.unwrap().block;
+        assert_eq!(block.header.fee_recipient, proposer);
+
+        // 未配置默认地址时按策略拒绝
+        let strict = BuildBlockService::new(tx_pool, None)
+            .with_zero_fee_recipient_policy(ZeroFeeRecipientPolicy::Reject);
+        assert_eq!(
+            strict.build_block(env(Address::zero())).await.unwrap_err(),
+            BlockValidationError::ZeroFeeRecipient
+        );
+    }
+
     #[tokio::test]
     async fn test_base_fee_calculation() {
         let tx_pool = Arc::new(TxPoolImpl::new(TxPoolConfig::default()));
@@ -679,13 +1034,13 @@ mod tests {
             parent_base_fee: U256::from(1_000_000_000u64),
             timestamp: U64::from(1234567890),
             fee_recipient: Address::zero(),
-            prev_randao: H256::random(),
+            prev_randao: H256::repeat_byte(0x42),
             withdrawals: vec![],
             parent_beacon_block_root: None,
         };
 
         // 构建区块
-        let block = builder.build_block(env).await.unwrap();
+        let block = builder.build_block(env).await.unwrap().block;
 
         // 验证区块
         assert!(builder.validate_block(&block).await.is_ok());
@@ -741,10 +1096,116 @@ mod tests {
         assert_eq!(new_base_fee, parent_base_fee);
     }
 
+    #[test]
+    fn test_fake_exponential() {
+        // EIP-4844 参考实现的测试向量: (factor, numerator, denominator, expected)
+        let cases: [(u64, u64, u64, u64); 8] = [
+            (1, 0, 1, 1),
+            (38493, 0, 1000, 38493),
+            (0, 1234, 2345, 0),
+            (1, 2, 1, 6),
+            (1, 4, 2, 6),
+            (1, 3, 1, 16),
+            (1, 6, 2, 18),
+            (10, 8, 2, 542),
+        ];
+        for (factor, numerator, denominator, expected) in cases {
+            assert_eq!(
+                BlobBaseFeeCalculator::fake_exponential(
+                    U256::from(factor),
+                    U256::from(numerator),
+                    U256::from(denominator)
+                ),
+                U256::from(expected),
+                "fake_exponential({}, {}, {})",
+                factor,
+                numerator,
+                denominator
+            );
+        }
+    }
+
+    #[test]
+    fn test_blob_base_fee() {
+        // (excess_blob_gas, blob base fee)，与 geth eip4844 测试一致
+        let cases: [(u64, u64); 4] = [(0, 1), (2_314_057, 1), (2_314_058, 2), (10 * 1024 * 1024, 23)];
+        for (excess_blob_gas, expected) in cases {
+            assert_eq!(
+                BlobBaseFeeCalculator::blob_base_fee(U64::from(excess_blob_gas)),
+                U256::from(expected),
+                "excess_blob_gas = {}",
+                excess_blob_gas
+            );
+        }
+
+        // 极端 excess_blob_gas：结果超出 U256，饱和而不是溢出 panic
+        assert_eq!(BlobBaseFeeCalculator::blob_base_fee(U64::MAX), U256::MAX);
+        assert_eq!(
+            BlobBaseFeeCalculator::fake_exponential(U256::MAX, U256::one(), U256::from(2)),
+            U256::MAX
+        );
+    }
+
+    #[test]
+    fn test_base_fee_elasticity_shifts_target() {
+        let parent_gas_limit = 30_000_000;
+        let parent_base_fee = U256::from(1_000_000_000u64);
+        let params = BaseFeeParams::new(4, 8).unwrap();
+        let next = |params: &BaseFeeParams, gas_used| {
+            BaseFeeCalculator::calculate_base_fee_with_params(
+                params,
+                gas_used,
+                parent_gas_limit,
+                parent_base_fee,
+            )
+        };
+
+        // 弹性乘数 4：目标为 25%，此时 base fee 不变，而 50% 使用率已触发上涨
+        assert_eq!(next(&params, parent_gas_limit / 4), parent_base_fee);
+        assert!(next(&params, parent_gas_limit / 2) > parent_base_fee);
+        assert_eq!(next(&BaseFeeParams::MAINNET, parent_gas_limit / 2), parent_base_fee);
+    }
+
+    #[test]
+    fn test_base_fee_params_reject_zero() {
+        assert_eq!(
+            BaseFeeParams::new(0, 8),
+            Err(BaseFeeParamsError::ZeroElasticityMultiplier)
+        );
+        assert_eq!(
+            BaseFeeParams::new(2, 0),
+            Err(BaseFeeParamsError::ZeroMaxChangeDenominator)
+        );
+        assert_eq!(BaseFeeParams::new(2, 8), Ok(BaseFeeParams::MAINNET));
+    }
+
+    #[test]
+    fn test_base_fee_denominator_bounds_change() {
+        let parent_gas_limit = 30_000_000;
+        let parent_base_fee = U256::from(1_000_000_000u64);
+        let params = BaseFeeParams::new(2, 50).unwrap();
+
+        // 满块时达到最大涨幅：主网 1/8，分母 50 时 1/50
+        let mainnet = BaseFeeCalculator::calculate_base_fee(
+            parent_gas_limit,
+            parent_gas_limit,
+            parent_base_fee,
+        );
+        let custom = BaseFeeCalculator::calculate_base_fee_with_params(
+            &params,
+            parent_gas_limit,
+            parent_gas_limit,
+            parent_base_fee,
+        );
+        assert_eq!(mainnet, parent_base_fee + parent_base_fee / 8);
+        assert_eq!(custom, parent_base_fee + parent_base_fee / 50);
+    }
+
     #[test]
     fn test_initial_base_fee() {
         let initial = BaseFeeCalculator::initial_base_fee();
-        assert_eq!(initial, U256::from(1_000_000_000u64));
+        assert_eq!(initial, Wei::new(1_000_000_000));
+        assert_eq!(initial.to_string(), "1 Gwei");
     }
 
     // ========== GasLimitCalculator 单元测试 ==========
@@ -811,4 +1272,278 @@ mod tests {
             tx1.max_priority_fee_per_gas
         );
     }
+
+    #[tokio::test]
+    async fn test_build_summary_accounts_fees() {
+        let tx_pool = Arc::new(TxPoolImpl::new(TxPoolConfig::default()));
+        let sender = Address::from_low_u64_be(0x5678);
+        let gwei = |n: u64| U256::from(n * 1_000_000_000);
+
+        // base fee 1 Gwei：tx_a 小费 2 Gwei；tx_b 受 max_fee 限制，小费 4 - 1 = 3 Gwei
+        let tx_a = DynamicFeeTx {
+            chain_id: U64::one(),
+            nonce: U64::zero(),
+            max_priority_fee_per_gas: gwei(2),
+            max_fee_per_gas: gwei(10),
+            gas_limit: U64::from(21000),
+            to: Some(Address::zero()),
+            value: U256::zero(),
+            data: vec![],
+            access_list: vec![],
+            v: U64::zero(),
+            r: U256::one(),
+            s: U256::one(),
+        };
+        let tx_b = DynamicFeeTx {
+            nonce: U64::one(),
+            max_priority_fee_per_gas: gwei(5),
+            max_fee_per_gas: gwei(4),
+            gas_limit: U64::from(50000),
+            ..tx_a.clone()
+        };
+        tx_pool.add(tx_a, sender).await.unwrap();
+        tx_pool.add(tx_b, sender).await.unwrap();
+
+        let builder = BuildBlockService::new(tx_pool, Some(30_000_000));
+        let env = BuildEnvironment {
+            parent_hash: H256::zero(),
+            parent_number: U64::zero(),
+            parent_gas_used: U64::from(15_000_000), // 50%，base fee 不变
+            parent_gas_limit: U64::from(30_000_000),
+            parent_base_fee: gwei(1),
+            timestamp: U64::from(1234567890),
+            fee_recipient: Address::zero(),
+            prev_randao: H256::zero(),
+            withdrawals: vec![],
+            parent_beacon_block_root: None,
+        };
+
+        let BuiltBlock { block, summary } = builder.build_block(env).await.unwrap();
+
+        assert_eq!(summary.tx_count, 2);
+        assert_eq!(summary.total_gas_used, 71_000);
+        assert_eq!(block.gas_used(), U64::from(71_000));
+        assert_eq!(
+            summary.total_priority_fees,
+            gwei(2) * U256::from(21_000) + gwei(3) * U256::from(50_000)
+        );
+        assert_eq!(summary.base_fee_burned, gwei(1) * U256::from(71_000));
+    }
+
+    #[tokio::test]
+    async fn test_receipts_record_effective_gas_price() {
+        let tx_pool = Arc::new(TxPoolImpl::new(TxPoolConfig::default()));
+        let builder = BuildBlockService::new(tx_pool, Some(30_000_000));
+        let gwei = |n: u64| U256::from(n * 1_000_000_000);
+
+        // base fee 3 Gwei：tip_bound 支付 3 + 2 = 5 Gwei；cap_bound 受 max_fee 限制支付 4 Gwei
+        let tip_bound = DynamicFeeTx {
+            chain_id: U64::one(),
+            nonce: U64::zero(),
+            max_priority_fee_per_gas: gwei(2),
+            max_fee_per_gas: gwei(10),
+            gas_limit: U64::from(21000),
+            to: Some(Address::zero()),
+            value: U256::zero(),
+            data: vec![],
+            access_list: vec![],
+            v: U64::zero(),
+            r: U256::one(),
+            s: U256::one(),
+        };
+        let cap_bound = DynamicFeeTx {
+            nonce: U64::one(),
+            max_priority_fee_per_gas: gwei(4),
+            max_fee_per_gas: gwei(4),
+            ..tip_bound.clone()
+        };
+
+        let (txs, _, receipts) = builder
+            .select_and_execute_transactions(
+                vec![
+                    PendingTx { tx: tip_bound, arrival: 0 },
+                    PendingTx { tx: cap_bound, arrival: 1 },
+                ],
+                30_000_000,
+                gwei(3),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(txs.len(), 2);
+        for (tx, receipt) in txs.iter().zip(&receipts) {
+            let expected = if tx.nonce.is_zero() { gwei(5) } else { gwei(4) };
+            assert_eq!(receipt.effective_gas_price, expected);
+            assert_eq!(receipt.transaction_type, U64::from(2));
+        }
+    }
+
+    #[test]
+    fn test_equal_fees_select_in_hash_order() {
+        let base_fee = U256::from(1_000_000_000u64);
+        let txs: Vec<DynamicFeeTx> = (0..4u64)
+            .map(|nonce| DynamicFeeTx {
+                chain_id: U64::one(),
+                nonce: U64::from(nonce),
+                max_priority_fee_per_gas: U256::from(2_000_000_000u64),
+                max_fee_per_gas: U256::from(3_000_000_000u64),
+                gas_limit: U64::from(21000),
+                to: Some(Address::zero()),
+                value: U256::zero(),
+                data: vec![],
+                access_list: vec![],
+                v: U64::zero(),
+                r: U256::one(),
+                s: U256::one(),
+            })
+            .collect();
+
+        let mut expected: Vec<H256> = txs.iter().map(|tx| tx.hash()).collect();
+        expected.sort();
+
+        let mut reversed = txs.clone();
+        reversed.reverse();
+        for candidates in [txs, reversed] {
+            let selected = TransactionSelector::select_transactions(candidates, 30_000_000, base_fee);
+            let hashes: Vec<H256> = selected.iter().map(|tx| tx.hash()).collect();
+            assert_eq!(hashes, expected);
+        }
+    }
+
+    #[test]
+    fn test_ordering_strategies() {
+        let gwei = |n: u64| U256::from(n) * U256::exp10(9);
+        let base_fee = gwei(10);
+        let tx = |nonce: u64, max_priority: u64, max_fee: u64| DynamicFeeTx {
+            chain_id: U64::one(),
+            nonce: U64::from(nonce),
+            max_priority_fee_per_gas: gwei(max_priority),
+            max_fee_per_gas: gwei(max_fee),
+            gas_limit: U64::from(21000),
+            to: Some(Address::zero()),
+            value: U256::zero(),
+            data: vec![],
+            access_list: vec![],
+            v: U64::zero(),
+            r: U256::one(),
+            s: U256::one(),
+        };
+
+        // early: 最早入池、小费最低；rich: 出价最高、小费居中；tipper: 小费最高、出价最低
+        let early = PendingTx { tx: tx(0, 1, 50), arrival: 0 };
+        let rich = PendingTx { tx: tx(1, 3, 100), arrival: 1 };
+        let tipper = PendingTx { tx: tx(2, 5, 20), arrival: 2 };
+        // 出价低于 base fee，任何策略下都被过滤
+        let underpriced = PendingTx { tx: tx(3, 9, 9), arrival: 3 };
+        let candidates = vec![
+            underpriced.clone(),
+            tipper.clone(),
+            rich.clone(),
+            early.clone(),
+        ];
+
+        let order = |strategy: &dyn OrderingStrategy| -> Vec<U64> {
+            TransactionSelector::select_with_strategy(
+                candidates.clone(),
+                30_000_000,
+                base_fee,
+                strategy,
+            )
+            .iter()
+            .map(|tx| tx.nonce)
+            .collect()
+        };
+        let nonces = |txs: [&PendingTx; 3]| -> Vec<U64> { txs.iter().map(|p| p.tx.nonce).collect() };
+
+        assert_eq!(order(&PriorityFeeOrdering), nonces([&tipper, &rich, &early]));
+        assert_eq!(order(&FifoOrdering), nonces([&early, &rich, &tipper]));
+        assert_eq!(order(&GasPriceOrdering), nonces([&rich, &early, &tipper]));
+    }
+
+    #[tokio::test]
+    async fn test_build_block_uses_configured_ordering() {
+        let tx_pool = Arc::new(TxPoolImpl::new(TxPoolConfig::default()));
+        let builder = BuildBlockService::new(tx_pool.clone(), Some(30_000_000))
+            .with_ordering_strategy(FifoOrdering);
+
+        // 小费递减入池：FIFO 下按入池顺序打包，而非按小费
+        for (i, priority) in [1u64, 2, 3].into_iter().enumerate() {
+            let tx = DynamicFeeTx {
+                chain_id: U64::one(),
+                nonce: U64::zero(),
+                max_priority_fee_per_gas: U256::from(priority * 1_000_000_000),
+                max_fee_per_gas: U256::from(10_000_000_000u64),
+                gas_limit: U64::from(21000),
+                to: Some(Address::zero()),
+                value: U256::zero(),
+                data: vec![],
+                access_list: vec![],
+                v: U64::zero(),
+                r: U256::one(),
+                s: U256::one(),
+            };
+            tx_pool
+                .add(tx, Address::from_low_u64_be(i as u64 + 1))
+                .await
+                .unwrap();
+        }
+
+        let base_fee = U256::from(1_000_000_000u64);
+        let candidates = builder.get_candidate_transactions(base_fee).await.unwrap();
+        let (txs, _, _) = builder
+            .select_and_execute_transactions(candidates, 30_000_000, base_fee)
+            .await
+            .unwrap();
+        let priorities: Vec<U256> = txs.iter().map(|tx| tx.max_priority_fee_per_gas).collect();
+        assert_eq!(
+            priorities,
+            vec![
+                U256::from(1_000_000_000u64),
+                U256::from(2_000_000_000u64),
+                U256::from(3_000_000_000u64),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ordering_sees_all_pending_candidates() {
+        let tx_pool = Arc::new(TxPoolImpl::new(TxPoolConfig::default()));
+        let builder = BuildBlockService::new(tx_pool.clone(), Some(30_000_000))
+            .with_ordering_strategy(FifoOrdering);
+        let gwei = |n: u64| U256::from(n * 1_000_000_000);
+        // value 区分交易哈希
+        let tx = |max_fee: U256, value: u64| DynamicFeeTx {
+            chain_id: U64::one(),
+            nonce: U64::zero(),
+            max_priority_fee_per_gas: gwei(1),
+            max_fee_per_gas: max_fee,
+            gas_limit: U64::from(21000),
+            to: Some(Address::zero()),
+            value: U256::from(value),
+            data: vec![],
+            access_list: vec![],
+            v: U64::zero(),
+            r: U256::one(),
+            s: U256::one(),
+        };
+
+        // 最早入池的交易出价最低，其后 1000 笔出价更高的交易
+        let early = tx(gwei(2), 0);
+        tx_pool.add(early.clone(), Address::from_low_u64_be(1)).await.unwrap();
+        for i in 0..1000u64 {
+            tx_pool
+                .add(tx(gwei(10), i + 1), Address::from_low_u64_be(i + 2))
+                .await
+                .unwrap();
+        }
+
+        let base_fee = gwei(1);
+        let candidates = builder.get_candidate_transactions(base_fee).await.unwrap();
+        assert_eq!(candidates.len(), 1001);
+        let (txs, _, _) = builder
+            .select_and_execute_transactions(candidates, 30_000_000, base_fee)
+            .await
+            .unwrap();
+        assert_eq!(txs[0].hash(), early.hash());
+    }
 }
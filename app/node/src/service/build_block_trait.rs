@@ -1,21 +1,22 @@
-/// 区块构建器接口 - 遵循Clean Architecture原则
-///
-/// 设计依据：
-/// - EIP-1559: 费用市场和base fee动态调整
-/// - EIP-3675: PoS共识机制
-/// - EIP-4399: PREVRANDAO随机数
-/// - Geth miner/worker.go: 区块构建流程
-///
-/// 参考文档：
-/// - /consensus-specs/specs/gloas/builder.md
-/// - /EIPs/EIPS/eip-1559.md
-/// - /EIPs/EIPS/eip-3675.md
+//! 区块构建器接口 - 遵循Clean Architecture原则
+//!
+//! 设计依据：
+//! - EIP-1559: 费用市场和base fee动态调整
+//! - EIP-3675: PoS共识机制
+//! - EIP-4399: PREVRANDAO随机数
+//! - Geth miner/worker.go: 区块构建流程
+//!
+//! 参考文档：
+//! - /consensus-specs/specs/gloas/builder.md
+//! - /EIPs/EIPS/eip-1559.md
+//! - /EIPs/EIPS/eip-3675.md
 
-use crate::domain::block_types::{Block, BlockValidationError, BuildEnvironment};
+use crate::domain::block_types::{Block, BlockValidationError, BuildEnvironment, BuiltBlock};
 use async_trait::async_trait;
-use ethereum_types::U64;
+use ethereum_types::{H256, U256, U64};
 use crate::domain::receipt_types::TransactionReceipt;
 use crate::service::repo::block_repo::BlockRepositoryError;
+use tokio::sync::broadcast;
 
 /// 区块构建器接口
 ///
@@ -38,7 +39,9 @@ pub trait BlockBuilder: Send + Sync {
     /// 3. 选择并执行交易 (贪心算法 + 装箱)
     /// 4. 计算Merkle根和状态根
     /// 5. 组装区块头和区块
-    async fn build_block(&self, env: BuildEnvironment) -> Result<Block, BlockValidationError>;
+    ///
+    /// 同时返回收益摘要（优先费总和、销毁的 base fee）
+    async fn build_block(&self, env: BuildEnvironment) -> Result<BuiltBlock, BlockValidationError>;
 
     /// 验证区块 (PoS规则)
     ///
@@ -97,6 +100,9 @@ pub trait BlockChain: Send + Sync {
     /// 2. 更新规范链指针
     /// 3. 更新内存中的链头
     /// 4. 发布 ChainHeadEvent
+    ///
+    /// 链头只前进：区块号不高于当前链头时返回 `BlockValidationError::NotAboveHead`，
+    /// 回退链头需走 `set_head`
     async fn write_block_and_set_head(
         &self,
         block: Block,
@@ -113,6 +119,18 @@ pub trait BlockChain: Send + Sync {
     /// - 回滚到安全点
     async fn set_head(&self, number: U64) -> Result<(), BlockRepositoryError>;
 
+    /// 按分叉选择权重设置链头
+    ///
+    /// 记录区块 `hash` 的权重 `fork_choice_weight`，仅当其权重严格大于当前链头时
+    /// 重组到该区块所在分支；权重相同时哈希较小者胜出。
+    /// 区块须已通过 `insert_block` 写入；返回链头是否切换到该区块。
+    /// 切换时沿父区块回溯改写规范链映射，并推送新链头通知
+    async fn set_head_with_weight(
+        &self,
+        hash: H256,
+        fork_choice_weight: U256,
+    ) -> Result<bool, BlockRepositoryError>;
+
     /// 重置链到创世区块
     ///
     /// 参考: geth BlockChain.Reset()
@@ -120,6 +138,14 @@ pub trait BlockChain: Send + Sync {
     /// 警告: 删除所有区块数据
     async fn reset(&self) -> Result<(), BlockRepositoryError>;
 
+    /// 订阅新的规范链头
+    ///
+    /// 参考: geth BlockChain.SubscribeChainHeadEvent
+    ///
+    /// 每次 `write_block_and_set_head` / `set_head_with_weight` 切换链头后按顺序推送区块；
+    /// `set_head` 回退链头不产生通知
+    fn subscribe_new_heads(&self) -> broadcast::Receiver<Block>;
+
     /// 获取从指定高度开始的区块链
     ///
     /// 用于同步和查询
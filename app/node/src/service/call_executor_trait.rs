@@ -0,0 +1,145 @@
+//! 调用执行器 Trait - 在指定区块状态上模拟执行调用
+//! 遵循Clean Architecture原则：
+//! - 接口位于service层，EVM 实现（revm）位于infrastructure层
+//! - 执行只读：模拟执行不提交任何状态
+//! - 支持静态分发（通过泛型实现）
+
+use crate::domain::command_types::{CallRequest, StateOverride};
+use crate::domain::intrinsic_gas::IntrinsicGasSchedule;
+use ethereum_types::{U256, U64};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+/// 单次模拟执行（eth_call / eth_estimateGas）的资源上限
+///
+/// revm 只限制 1024 层调用深度；这里额外限制 gas 与墙钟时间，
+/// 避免单个请求长期占用工作线程
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CallLimits {
+    /// 单次执行可用的 gas 上限（对应 geth 的 RPCGasCap）
+    pub max_gas: u64,
+    /// 单次执行的最长时间（对应 geth 的 RPCEVMTimeout）
+    pub max_duration: Duration,
+}
+
+impl Default for CallLimits {
+    fn default() -> Self {
+        Self {
+            max_gas: 50_000_000,
+            max_duration: Duration::from_secs(5),
+        }
+    }
+}
+
+/// 执行所遵循的硬分叉规则
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EvmFork {
+    Berlin,
+    London,
+    Shanghai,
+    #[default]
+    Cancun,
+}
+
+/// gas 计费表（私有链 / L2 可覆盖主网计费）
+///
+/// 执行器、eth_estimateGas 与交易固有 gas 校验共用同一份计费表
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasSchedule {
+    /// 硬分叉规则（决定未覆盖部分的计费）
+    pub fork: EvmFork,
+    /// 交易固有 gas 费率
+    pub intrinsic: IntrinsicGasSchedule,
+    /// 操作码 gas 覆盖：操作码 -> 执行该操作码的总 gas（含内存扩展等动态部分）
+    ///
+    /// CALL / CREATE 类操作码会把 gas 转发给子调用，覆盖对它们不生效
+    pub opcode_costs: BTreeMap<u8, u64>,
+}
+
+impl GasSchedule {
+    /// 指定硬分叉的主网计费表
+    pub fn for_fork(fork: EvmFork) -> Self {
+        let intrinsic = match fork {
+            EvmFork::Berlin | EvmFork::London => IntrinsicGasSchedule::BERLIN,
+            EvmFork::Shanghai | EvmFork::Cancun => IntrinsicGasSchedule::SHANGHAI,
+        };
+        Self {
+            fork,
+            intrinsic,
+            opcode_costs: BTreeMap::new(),
+        }
+    }
+
+    /// 覆盖单个操作码的 gas
+    pub fn with_opcode_cost(mut self, opcode: u8, cost: u64) -> Self {
+        self.opcode_costs.insert(opcode, cost);
+        self
+    }
+}
+
+impl Default for GasSchedule {
+    /// 当前主网计费表
+    fn default() -> Self {
+        Self::for_fork(EvmFork::default())
+    }
+}
+
+/// 调用执行环境
+#[derive(Debug, Clone)]
+pub struct CallEnv {
+    /// 执行所基于的区块号
+    pub block_number: U64,
+    /// 区块 gas 上限（单次执行可用 gas 的上界）
+    pub block_gas_limit: u64,
+    /// 调用者余额
+    pub caller_balance: U256,
+    /// 仅对本次执行生效的状态覆盖（在调用者余额之后应用）
+    pub state_override: StateOverride,
+    /// 免费调用模式（eth_call 专用）：gas 价格置零并跳过余额检查，
+    /// 余额不足的调用者在执行时被补足；eth_estimateGas 保持 false
+    pub gas_free: bool,
+    /// gas 计费表
+    pub gas_schedule: GasSchedule,
+    /// 执行截止时间：到期后执行器在下一条指令前中止执行，结果为 `TimedOut`
+    pub deadline: Option<Instant>,
+}
+
+/// 调用执行结果
+#[derive(Debug, Clone, PartialEq)]
+pub enum CallOutcome {
+    /// 执行成功
+    Success { gas_used: u64, output: Vec<u8> },
+    /// REVERT 操作码回滚（返回数据中可能含回滚原因）
+    Revert { gas_used: u64, output: Vec<u8> },
+    /// 异常终止（gas 耗尽、无效操作码等），消耗全部 gas
+    Halt { gas_used: u64, reason: String },
+    /// 交易未能开始执行（固有 gas 不足、余额不足等）
+    Invalid(String),
+    /// 超过 `CallEnv::deadline` 被中止
+    TimedOut,
+}
+
+impl CallOutcome {
+    /// 是否执行成功
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Success { .. })
+    }
+}
+
+/// 调用执行器接口
+pub trait CallExecutor: Send + Sync {
+    /// 以指定 gas 上限执行调用，不修改状态
+    fn execute(&self, call: &CallRequest, gas_limit: u64, env: &CallEnv) -> CallOutcome;
+
+    /// 在同一状态快照上依次执行多个调用（调用请求, gas 上限），不修改仓储
+    ///
+    /// `isolated` 为 false 时后续调用可见前序调用的状态修改，为 true 时每个调用
+    /// 都从快照开始执行。各调用者的余额经 `env.state_override` 注入，
+    /// 不使用 `env.caller_balance`
+    fn execute_bundle(
+        &self,
+        calls: &[(CallRequest, u64)],
+        env: &CallEnv,
+        isolated: bool,
+    ) -> Vec<CallOutcome>;
+}
@@ -0,0 +1,16 @@
+//! 时钟接口 - 统一时间来源
+//!
+//! 依赖时间的逻辑（过期、退避、slot 计时）通过该接口取时间，
+//! 生产环境注入 `SystemClock`，测试注入可手动推进的 `MockClock`，
+//! 避免测试中真实 sleep。
+
+use std::time::Instant;
+
+/// 时钟接口
+pub trait Clock: Send + Sync {
+    /// 单调时钟读数（用于计算时间间隔）
+    fn now(&self) -> Instant;
+
+    /// Unix 时间戳（秒）
+    fn unix_time(&self) -> u64;
+}
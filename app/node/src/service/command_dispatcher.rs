@@ -4,7 +4,9 @@
 
 use crate::domain::command_types::{CommandError, CommandResult, EthCommand};
 use crate::service::ethereum_service_trait::EthereumService;
+use crate::service::repo::handler_repo::HandlerRepository;
 use crate::domain::command_types::BlockTag;
+use crate::domain::version::version;
 use ethereum_types::U64;
 use std::sync::Arc;
 
@@ -12,12 +14,23 @@ use std::sync::Arc;
 #[derive(Clone)]
 pub struct CommandDispatcher<S: EthereumService> {
     service: Arc<S>,
+    /// 自定义方法处理器（None 表示不支持自定义方法）
+    handlers: Option<Arc<dyn HandlerRepository>>,
 }
 
 impl<S: EthereumService> CommandDispatcher<S> {
     /// 创建新的命令分发器
     pub fn new(service: Arc<S>) -> Self {
-        Self { service }
+        Self {
+            service,
+            handlers: None,
+        }
+    }
+
+    /// 挂载自定义方法处理器仓储，非内置方法按方法名路由到其中的处理器
+    pub fn with_handlers(mut self, handlers: Arc<dyn HandlerRepository>) -> Self {
+        self.handlers = Some(handlers);
+        self
     }
 
     /// 处理命令
@@ -40,6 +53,17 @@ impl<S: EthereumService> CommandDispatcher<S> {
                     crate::domain::command_types::BlockId::Tag(BlockTag::Pending) => {
                         return Err(CommandError::UnsupportedCommand("待处理区块".to_string()))
                     }
+                    // 取分叉选择状态，共识层尚未设置时返回 null
+                    tag @ crate::domain::command_types::BlockId::Tag(BlockTag::Safe | BlockTag::Finalized) => {
+                        let result = self.service.get_block_by_id(tag, full_tx).await?;
+                        return Ok(CommandResult::Block(result));
+                    }
+                    // EIP-1898 的哈希形式不适用于 eth_getBlockByNumber
+                    crate::domain::command_types::BlockId::Hash { .. } => {
+                        return Err(CommandError::InvalidParams(
+                            "eth_getBlockByNumber 不接受区块哈希".to_string(),
+                        ))
+                    }
                 };
                 let result = self.service.get_block_by_number(number, full_tx).await?;
                 Ok(CommandResult::Block(result))
@@ -67,6 +91,11 @@ impl<S: EthereumService> CommandDispatcher<S> {
                 Ok(CommandResult::U256(result))
             }
 
+            EthCommand::GetBalances(addresses, block_id) => {
+                let result = self.service.get_balances(addresses, block_id).await?;
+                Ok(CommandResult::Balances(result))
+            }
+
             EthCommand::GetStorageAt(address, position, block_id) => {
                 let result = self
                     .service
@@ -88,12 +117,22 @@ impl<S: EthereumService> CommandDispatcher<S> {
                 Ok(CommandResult::Bytes(result))
             }
 
+            EthCommand::GetAccounts => {
+                let result = self.service.accounts().await?;
+                Ok(CommandResult::Addresses(result))
+            }
+
             // ============ 合约调用命令 ============
-            EthCommand::Call(request, block_id) => {
-                let result = self.service.call(request, block_id).await?;
+            EthCommand::Call(request, block_id, state_override) => {
+                let result = self.service.call(request, block_id, state_override).await?;
                 Ok(CommandResult::Bytes(result))
             }
 
+            EthCommand::CallBundle(requests, block_id, isolated) => {
+                let result = self.service.call_bundle(requests, block_id, isolated).await?;
+                Ok(CommandResult::CallBundle(result))
+            }
+
             EthCommand::EstimateGas(request) => {
                 let result = self.service.estimate_gas(request).await?;
                 Ok(CommandResult::U256(result))
@@ -105,7 +144,10 @@ impl<S: EthereumService> CommandDispatcher<S> {
             }
 
             // ============ 网络信息查询命令 ============
-            EthCommand::GetChainId => Ok(CommandResult::U64(U64::from(1))),
+            EthCommand::GetChainId => {
+                let result = self.service.chain_id().await?;
+                Ok(CommandResult::U64(result))
+            }
 
             EthCommand::GetGasPrice => Ok(CommandResult::U256(ethereum_types::U256::from(
                 20_000_000_000u64,
@@ -113,7 +155,15 @@ impl<S: EthereumService> CommandDispatcher<S> {
 
             EthCommand::GetNetVersion => Ok(CommandResult::String("1".to_string())),
 
-            EthCommand::GetClientVersion => Ok(CommandResult::String("rusteth/0.1.0".to_string())),
+            EthCommand::GetClientVersion => Ok(CommandResult::String(version())),
+
+            EthCommand::GetProtocolVersion => {
+                let result = self.service.protocol_version().await?;
+                Ok(CommandResult::U64(result))
+            }
+
+            // PoS 下没有挖矿
+            EthCommand::GetHashrate => Ok(CommandResult::U64(U64::zero())),
 
             // ============ EIP-1559 交易命令 ============
             EthCommand::SendTransaction(request) => {
@@ -121,16 +171,28 @@ impl<S: EthereumService> CommandDispatcher<S> {
                 Ok(CommandResult::Hash(result))
             }
 
-            EthCommand::SendRawTransaction(raw_tx, sender) => {
-                use crate::inbound::transaction_decoder::decode_raw_transaction;
+            EthCommand::SendRawTransaction(raw_tx) => {
+                use crate::inbound::transaction_decoder::{
+                    decode_raw_transaction, legacy_signature_v,
+                };
+
+                // legacy 交易先做 EIP-155 重放保护检查，链 ID 不符时给出明确错误
+                if let Some(v) = legacy_signature_v(&raw_tx) {
+                    self.service.validate_legacy_chain_id(v).await?;
+                }
 
                 let tx = decode_raw_transaction(&raw_tx)
                     .map_err(|e| CommandError::InvalidParams(format!("RLP解码失败: {}", e)))?;
 
-                let result = self.service.send_raw_transaction(tx, sender).await?;
+                let result = self.service.send_raw_transaction(tx).await?;
                 Ok(CommandResult::Hash(result))
             }
 
+            EthCommand::SignTypedData(address, typed_data) => {
+                let result = self.service.sign_typed_data(address, typed_data).await?;
+                Ok(CommandResult::Bytes(result))
+            }
+
             EthCommand::GetFeeHistory(block_count, newest_block, reward_percentiles) => {
                 let result = self
                     .service
@@ -143,6 +205,32 @@ impl<S: EthereumService> CommandDispatcher<S> {
                 let result = self.service.max_priority_fee_per_gas().await?;
                 Ok(CommandResult::U256(result))
             }
+
+            EthCommand::GetBlobBaseFee => {
+                let result = self.service.blob_base_fee().await?;
+                Ok(CommandResult::U256(result))
+            }
+
+            EthCommand::GetRawTransaction(hash) => {
+                let result = self.service.get_signed_transaction(hash).await?;
+                Ok(CommandResult::RawTransaction(result))
+            }
+
+            EthCommand::GetRawReceipts(block) => {
+                let result = self.service.get_block_receipts(block).await?;
+                Ok(CommandResult::RawReceipts(result))
+            }
+
+            // ============ 自定义命令 ============
+            EthCommand::Custom(method, params) => {
+                let handler = self
+                    .handlers
+                    .as_ref()
+                    .and_then(|handlers| handlers.get(&method))
+                    .ok_or(CommandError::UnsupportedCommand(method))?;
+                let result = handler.call(params).await?;
+                Ok(CommandResult::Json(result))
+            }
         }
     }
 }
@@ -166,4 +254,115 @@ mod tests {
             assert_eq!(num, U64::zero());
         }
     }
+
+    #[tokio::test]
+    async fn test_protocol_version_and_hashrate() {
+        let service = EthereumServiceImpl::new(MockEthereumRepository::new());
+        let dispatcher = CommandDispatcher::new(Arc::new(service.clone()));
+
+        let result = dispatcher.ask(EthCommand::GetProtocolVersion).await.unwrap();
+        assert!(matches!(result, CommandResult::U64(v) if v == U64::from(65)));
+        let result = dispatcher.ask(EthCommand::GetHashrate).await.unwrap();
+        assert!(matches!(result, CommandResult::U64(v) if v.is_zero()));
+
+        let dispatcher = CommandDispatcher::new(Arc::new(service.with_protocol_version(68)));
+        let result = dispatcher.ask(EthCommand::GetProtocolVersion).await.unwrap();
+        assert!(matches!(result, CommandResult::U64(v) if v == U64::from(68)));
+    }
+
+    #[tokio::test]
+    async fn test_get_block_by_safe_and_finalized_tags() {
+        use crate::domain::command_types::BlockId;
+
+        let repo = MockEthereumRepository::new();
+        let dispatcher = CommandDispatcher::new(Arc::new(EthereumServiceImpl::new(repo.clone())));
+        let get = |tag: BlockTag| EthCommand::GetBlockByNumber(BlockId::Tag(tag), false);
+
+        // 共识层尚未设置：区块不存在而非方法不支持
+        for tag in [BlockTag::Safe, BlockTag::Finalized] {
+            let result = dispatcher.ask(get(tag)).await.unwrap();
+            assert!(matches!(result, CommandResult::Block(None)));
+        }
+
+        repo.set_forkchoice(U64::zero(), U64::zero());
+        for tag in [BlockTag::Safe, BlockTag::Finalized] {
+            let result = dispatcher.ask(get(tag)).await.unwrap();
+            assert!(matches!(result, CommandResult::Block(Some(block)) if block.number.is_zero()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_method_routes_to_registered_handler() {
+        use crate::infrastructure::handler_repo_impl::InMemoryHandlerRepository;
+
+        let handlers = Arc::new(InMemoryHandlerRepository::new());
+        handlers.register(
+            "myl2_echo",
+            Arc::new(|params: serde_json::Value| async move {
+                Ok(serde_json::json!({ "echo": params }))
+            }),
+        );
+        let service = Arc::new(EthereumServiceImpl::new(MockEthereumRepository::new()));
+        let dispatcher = CommandDispatcher::new(service).with_handlers(handlers);
+
+        let result = dispatcher
+            .ask(EthCommand::Custom("myl2_echo".to_string(), serde_json::json!([7])))
+            .await
+            .unwrap();
+        assert!(matches!(result, CommandResult::Json(v) if v == serde_json::json!({ "echo": [7] })));
+
+        // 未注册的方法仍然报方法未找到
+        let err = dispatcher
+            .ask(EthCommand::Custom("myl2_missing".to_string(), serde_json::json!([])))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CommandError::UnsupportedCommand(m) if m == "myl2_missing"));
+    }
+
+    /// legacy 交易 rlp([nonce, gas_price, gas_limit, to, value, data, v, r, s])
+    fn legacy_raw_tx(v: u64) -> Vec<u8> {
+        let mut stream = rlp::RlpStream::new_list(9);
+        stream.append(&0u64);
+        stream.append(&1_000_000_000u64);
+        stream.append(&21_000u64);
+        stream.append(&ethereum_types::Address::from_low_u64_be(0x1234));
+        stream.append(&0u64);
+        stream.append(&Vec::<u8>::new());
+        stream.append(&v);
+        stream.append(&1u64);
+        stream.append(&1u64);
+        stream.out().to_vec()
+    }
+
+    #[tokio::test]
+    async fn test_raw_legacy_transaction_checks_chain_id() {
+        let send = |v: u64| EthCommand::SendRawTransaction(legacy_raw_tx(v));
+        let strict = CommandDispatcher::new(Arc::new(EthereumServiceImpl::new(
+            MockEthereumRepository::new(),
+        )));
+
+        // chain_id = 5 的签名（v = 45）发往主网节点
+        let err = strict.ask(send(45)).await.unwrap_err();
+        assert!(
+            matches!(&err, CommandError::ValidationError(m) if m.contains("Wrong chain ID: expected 1, got 5")),
+            "{:?}",
+            err
+        );
+        // EIP-155 之前的签名默认拒绝
+        let err = strict.ask(send(27)).await.unwrap_err();
+        assert!(
+            matches!(&err, CommandError::ValidationError(m) if m.contains("replay-protected")),
+            "{:?}",
+            err
+        );
+
+        // 允许未受保护交易时通过重放保护检查，之后按暂不支持的交易类型拒绝
+        let permissive = CommandDispatcher::new(Arc::new(
+            EthereumServiceImpl::new(MockEthereumRepository::new()).with_allow_unprotected_txs(true),
+        ));
+        for v in [27, 37] {
+            let err = permissive.ask(send(v)).await.unwrap_err();
+            assert!(matches!(err, CommandError::InvalidParams(_)), "{:?}", err);
+        }
+    }
 }
@@ -1,28 +1,472 @@
 use super::ethereum_service_trait::{EthereumService, ServiceError};
 use crate::domain::command_types::{
-    Block, BlockId, BlockTag, CallRequest, FeeHistory, FilterOptions, Log, SendTransactionRequest,
-    Transaction, TransactionReceipt,
+    AccountOverride, Block, BlockId, BlockTag, BlockTransactions, CallBundleResult, CallRequest,
+    FeeHistory, FilterOptions, Log, SendTransactionRequest, StateOverride, Transaction,
+    TransactionReceipt,
 };
+use crate::domain::receipt_types::bloom_may_contain;
+use crate::domain::tx_types::DynamicFeeTx;
+use crate::domain::typed_data::TypedData;
+use crate::domain::units::Gwei;
+use crate::infrastructure::account_state_cache::{AccountStateCacheConfig, CachedAccountStateProvider};
+use crate::infrastructure::keystore_repo_impl::InMemoryKeyStore;
 use crate::infrastructure::mock_repository::MockEthereumRepository;
-use crate::infrastructure::transaction_repo_impl::TxPoolImpl;
+use crate::infrastructure::revm_executor::RevmCallExecutor;
+use crate::infrastructure::transaction_repo_impl::{TxPoolConfig, TxPoolImpl};
+use crate::service::build_block_impl::{BaseFeeCalculator, BlobBaseFeeCalculator};
+use crate::service::call_executor_trait::{
+    CallEnv, CallExecutor, CallLimits, CallOutcome, GasSchedule,
+};
+use crate::service::gas_estimator;
+use crate::service::sender_rate_limiter::{SenderRateLimitConfig, SenderRateLimiter};
+use crate::service::transaction_validator::{TransactionValidator, ValidatorConfig};
+use crate::service::repo::keystore_repo::{KeyStore, KeyStoreError};
+use crate::service::repo::transaction_repo::TxPoolError;
 use async_trait::async_trait;
 use ethereum_types::{Address, H256, U256, U64};
+use futures::{stream, Stream, StreamExt};
+use std::collections::{BTreeMap, HashSet};
+use std::time::Instant;
 
 #[derive(Clone)]
 pub struct EthereumServiceImpl {
     pub repo: MockEthereumRepository,
+    /// 入池验证使用的账户状态（带 TTL 缓存，新链头时失效）
+    pub account_state: CachedAccountStateProvider<MockEthereumRepository>,
     pub tx_pool: TxPoolImpl,
+    pub keystore: InMemoryKeyStore,
+    /// 按发送者限流（None 表示不限流）
+    pub rate_limiter: Option<SenderRateLimiter>,
+    /// eth_protocolVersion 返回的 devp2p eth 协议版本
+    pub protocol_version: u64,
+    /// 链 ID（eth_chainId 与本地签名交易使用）
+    pub chain_id: u64,
+    /// 是否接受未受重放保护的 legacy 交易（EIP-155 之前的签名）
+    pub allow_unprotected_txs: bool,
+    /// eth_call / eth_estimateGas 的 gas 与时间上限
+    pub call_limits: CallLimits,
+    /// gas 计费表（模拟执行与交易固有 gas 校验共用）
+    pub gas_schedule: GasSchedule,
+    /// eth_getLogs 单次最多返回的日志条数
+    pub max_log_results: usize,
+    /// eth_callBundle 单次最多包含的调用数
+    pub max_bundle_size: usize,
 }
 
 impl EthereumServiceImpl {
+    /// 默认链 ID（主网）
+    pub const CHAIN_ID: u64 = 1;
+
+    /// 建议的优先费用（eth_maxPriorityFeePerGas，模拟固定值）
+    pub const SUGGESTED_PRIORITY_FEE: Gwei = Gwei::new(2);
+
+    /// 默认 devp2p eth 协议版本（eth/65）
+    pub const DEFAULT_PROTOCOL_VERSION: u64 = 65;
+
+    /// eth_feeHistory 单次最多返回的区块数（与 geth 一致）
+    pub const MAX_FEE_HISTORY_BLOCKS: u64 = 1024;
+
+    /// eth_getLogs 默认结果上限（与常见公共节点一致）
+    pub const DEFAULT_MAX_LOG_RESULTS: usize = 10_000;
+
+    /// eth_callBundle 默认调用数上限
+    pub const DEFAULT_MAX_BUNDLE_SIZE: usize = 100;
+
     pub fn new(repo: MockEthereumRepository) -> Self {
+        Self::with_keystore(repo, InMemoryKeyStore::new())
+    }
+
+    /// 使用指定密钥库创建服务（eth_sendTransaction 只能从其中的账户发送）
+    pub fn with_keystore(repo: MockEthereumRepository, keystore: InMemoryKeyStore) -> Self {
         Self {
+            account_state: CachedAccountStateProvider::new(
+                repo.clone(),
+                AccountStateCacheConfig::default(),
+            ),
             repo,
             tx_pool: TxPoolImpl::default(),
+            keystore,
+            rate_limiter: None,
+            protocol_version: Self::DEFAULT_PROTOCOL_VERSION,
+            chain_id: Self::CHAIN_ID,
+            allow_unprotected_txs: false,
+            call_limits: CallLimits::default(),
+            gas_schedule: GasSchedule::default(),
+            max_log_results: Self::DEFAULT_MAX_LOG_RESULTS,
+            max_bundle_size: Self::DEFAULT_MAX_BUNDLE_SIZE,
+        }
+    }
+
+    /// 按所属交易与区块 base fee 填充收据的 `type` 与 `effectiveGasPrice`
+    ///
+    /// 仓储中找不到对应交易时保持收据原样
+    fn with_fee_fields(&self, mut receipt: TransactionReceipt) -> TransactionReceipt {
+        let tx = self
+            .repo
+            .transactions
+            .read()
+            .unwrap()
+            .get(&receipt.transaction_hash)
+            .cloned();
+        if let Some(tx) = tx {
+            let base_fee = self
+                .repo
+                .blocks
+                .read()
+                .unwrap()
+                .get(&receipt.block_number)
+                .and_then(|block| block.base_fee_per_gas);
+            receipt.set_fee_fields(&tx, base_fee);
+        }
+        receipt
+    }
+
+    /// 设置 eth_getLogs 单次最多返回的日志条数
+    pub fn with_max_log_results(mut self, max_log_results: usize) -> Self {
+        self.max_log_results = max_log_results;
+        self
+    }
+
+    /// 设置 eth_callBundle 单次最多包含的调用数
+    pub fn with_max_bundle_size(mut self, max_bundle_size: usize) -> Self {
+        self.max_bundle_size = max_bundle_size;
+        self
+    }
+
+    /// 设置 eth_call / eth_estimateGas 的资源上限
+    pub fn with_call_limits(mut self, limits: CallLimits) -> Self {
+        self.call_limits = limits;
+        self
+    }
+
+    /// 设置 gas 计费表（私有链 / L2 自定义计费）
+    pub fn with_gas_schedule(mut self, gas_schedule: GasSchedule) -> Self {
+        self.gas_schedule = gas_schedule;
+        self
+    }
+
+    /// 设置链 ID
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = chain_id;
+        self
+    }
+
+    /// 接受未受重放保护的 legacy 交易（默认拒绝）
+    pub fn with_allow_unprotected_txs(mut self, allow: bool) -> Self {
+        self.allow_unprotected_txs = allow;
+        self
+    }
+
+    /// 设置入池验证账户状态缓存的 TTL 与容量
+    pub fn with_account_state_cache(mut self, config: AccountStateCacheConfig) -> Self {
+        self.account_state = CachedAccountStateProvider::new(self.repo.clone(), config);
+        self
+    }
+
+    /// 入池验证器（按节点链 ID 与重放保护配置，经缓存查询账户状态）
+    fn tx_validator(&self) -> TransactionValidator<CachedAccountStateProvider<MockEthereumRepository>> {
+        let config = ValidatorConfig {
+            chain_id: U64::from(self.chain_id),
+            allow_unprotected_txs: self.allow_unprotected_txs,
+            gas_schedule: self.gas_schedule.intrinsic,
+            ..ValidatorConfig::default()
+        };
+        // 缓存键随仓储链头切换，新区块写入后不再命中旧状态
+        self.account_state
+            .sync_head(*self.repo.current_block_number.read().unwrap());
+        TransactionValidator::new(config, self.account_state.clone())
+    }
+
+    /// 使用指定容量配置的交易池
+    pub fn with_tx_pool_config(mut self, config: TxPoolConfig) -> Self {
+        self.tx_pool = TxPoolImpl::new(config);
+        self
+    }
+
+    /// 设置 eth_protocolVersion 返回的协议版本
+    pub fn with_protocol_version(mut self, version: u64) -> Self {
+        self.protocol_version = version;
+        self
+    }
+
+    /// 启用按发送者限流（eth_sendRawTransaction 入池前检查）
+    pub fn with_sender_rate_limit(mut self, config: SenderRateLimitConfig) -> Self {
+        self.rate_limiter = Some(SenderRateLimiter::new(config));
+        self
+    }
+}
+
+impl EthereumServiceImpl {
+    /// 将区块标识解析为已存在的区块号（pending 视为最新区块）
+    ///
+    /// safe / finalized 取分叉选择状态，共识层尚未通知时视为区块不存在
+    ///
+    /// 按哈希指定时（EIP-1898）：非主链区块在 `require_canonical` 时报错，
+    /// 否则按其高度取状态
+    fn resolve_block_number(&self, block: BlockId) -> Result<U64, ServiceError> {
+        let head = *self.repo.current_block_number.read().unwrap();
+        match block {
+            BlockId::Number(number) if number > head => Err(ServiceError::BlockNotFound),
+            BlockId::Number(number) => Ok(number),
+            BlockId::Tag(BlockTag::Earliest) => Ok(U64::zero()),
+            BlockId::Tag(BlockTag::Latest | BlockTag::Pending) => Ok(head),
+            BlockId::Tag(BlockTag::Safe) => {
+                self.repo.safe_block_number.read().unwrap().ok_or(ServiceError::BlockNotFound)
+            }
+            BlockId::Tag(BlockTag::Finalized) => {
+                self.repo.finalized_block_number.read().unwrap().ok_or(ServiceError::BlockNotFound)
+            }
+            BlockId::Hash { block_hash, require_canonical } => {
+                if let Some(number) = self.repo.block_hashes.read().unwrap().get(&block_hash) {
+                    return Ok(*number);
+                }
+                match self.repo.get_side_block(&block_hash) {
+                    Some(_) if require_canonical => Err(ServiceError::NonCanonicalBlock(block_hash)),
+                    Some(block) => Ok(block.number),
+                    None => Err(ServiceError::BlockNotFound),
+                }
+            }
         }
     }
 }
 
+impl EthereumServiceImpl {
+    /// 构造模拟执行环境（eth_call / eth_estimateGas 共用）
+    async fn call_env(
+        &self,
+        request: &CallRequest,
+        block: BlockId,
+        state_override: StateOverride,
+    ) -> Result<CallEnv, ServiceError> {
+        if let Some(address) = state_override
+            .iter()
+            .find(|(_, account)| account.state.is_some() && account.state_diff.is_some())
+            .map(|(address, _)| address)
+        {
+            return Err(ServiceError::ValidationError(format!(
+                "账户 {:?} 的 state 与 stateDiff 不能同时指定",
+                address
+            )));
+        }
+
+        let block_number = self.resolve_block_number(block.clone())?;
+        let block_gas_limit = self
+            .get_block_by_number(block_number, false)
+            .await?
+            .ok_or(ServiceError::BlockNotFound)?
+            .gas_limit;
+        let caller_balance = self
+            .get_balance(request.from.unwrap_or_default(), block)
+            .await?;
+
+        self.check_call_gas(request)?;
+
+        Ok(CallEnv {
+            block_number,
+            block_gas_limit: to_u64(block_gas_limit, "gasLimit")?.min(self.call_limits.max_gas),
+            caller_balance,
+            state_override,
+            gas_free: false,
+            gas_schedule: self.gas_schedule.clone(),
+            deadline: Some(Instant::now() + self.call_limits.max_duration),
+        })
+    }
+
+    /// 执行可用 gas 不超过 max_gas；显式请求更多 gas 直接拒绝
+    fn check_call_gas(&self, request: &CallRequest) -> Result<(), ServiceError> {
+        let max_gas = self.call_limits.max_gas;
+        if request.gas.is_some_and(|gas| gas > U256::from(max_gas)) {
+            return Err(ServiceError::CallLimitExceeded(format!(
+                "gas 超出模拟执行上限 {}",
+                max_gas
+            )));
+        }
+        Ok(())
+    }
+
+    /// 在阻塞线程池中执行 EVM 模拟，不占用异步工作线程
+    ///
+    /// 超时由执行器按 `CallEnv::deadline` 在执行中途中止，结果为 `CallOutcome::TimedOut`
+    async fn run_blocking<T, F>(&self, task: F) -> Result<T, ServiceError>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        tokio::task::spawn_blocking(task)
+            .await
+            .map_err(|e| ServiceError::InternalError(format!("模拟执行异常退出: {}", e)))
+    }
+}
+
+impl EthereumServiceImpl {
+    /// 按区块依次产出 `[from, to]` 内匹配过滤条件的日志
+    ///
+    /// 先用区块 Bloom 预过滤，只读取可能包含匹配日志的区块收据；
+    /// 迭代器是惰性的，调用方可随时停止
+    fn logs_by_block<'a>(
+        &'a self,
+        from: U64,
+        to: U64,
+        filter: &'a FilterOptions,
+    ) -> impl Iterator<Item = Result<Vec<Log>, ServiceError>> + 'a {
+        let topics = filter.topics.clone().unwrap_or_default();
+        (from.as_u64()..=to.as_u64()).filter_map(move |number| {
+            let blocks = self.repo.blocks.read().unwrap();
+            let block = blocks.get(&U64::from(number))?;
+            if !bloom_may_contain(&block.logs_bloom, filter.address, &topics) {
+                return None;
+            }
+            let receipts = self.repo.receipts.read().unwrap();
+            let logs = block.transactions.hashes().into_iter().try_fold(
+                Vec::new(),
+                |mut logs, hash| {
+                    let receipt = receipts.get(&hash).ok_or_else(|| {
+                        ServiceError::Other(format!("交易 {:?} 的收据缺失", hash))
+                    })?;
+                    logs.extend(
+                        receipt
+                            .logs
+                            .iter()
+                            .filter(|log| log_matches(log, filter.address, &topics))
+                            .cloned(),
+                    );
+                    Ok(logs)
+                },
+            );
+            Some(logs)
+        })
+    }
+
+    /// 按 NDJSON 导出规范链区块：每行一个 JSON 区块，以 `\n` 结尾
+    ///
+    /// 逐个区块读取并序列化，不缓冲整个区间；`to` 超出链头时截断到链头。
+    /// `full_tx` 为 false 时交易列表只保留哈希。
+    /// 区间内缺失的区块或序列化失败作为错误项返回，由调用方决定是否中止导出。
+    pub fn export_blocks(
+        &self,
+        from: U64,
+        to: U64,
+        full_tx: bool,
+    ) -> impl Stream<Item = Result<String, ServiceError>> + '_ {
+        let head = *self.repo.current_block_number.read().unwrap();
+        let to = to.min(head);
+        stream::iter(from.as_u64()..=to.as_u64()).map(move |number| {
+            let block = self
+                .repo
+                .blocks
+                .read()
+                .unwrap()
+                .get(&U64::from(number))
+                .cloned()
+                .ok_or(ServiceError::BlockNotFound)?;
+            let mut line = serde_json::to_string(&block.with_full_transactions(full_tx))
+                .map_err(|e| ServiceError::InternalError(e.to_string()))?;
+            line.push('\n');
+            Ok(line)
+        })
+    }
+}
+
+/// 由 RPC 交易表示还原已签名的 EIP-1559 交易
+///
+/// RPC 表示不含 chain_id 与 access_list，按节点链 ID 和空访问列表还原；
+/// 调用方需用交易哈希校验还原结果
+fn signed_tx_from_rpc(tx: &Transaction, chain_id: U64) -> Option<DynamicFeeTx> {
+    if tx.transaction_type != Some(U64::from(DynamicFeeTx::TRANSACTION_TYPE)) {
+        return None;
+    }
+    Some(DynamicFeeTx {
+        chain_id,
+        nonce: u64::try_from(tx.nonce).ok()?.into(),
+        max_priority_fee_per_gas: tx.max_priority_fee_per_gas?,
+        max_fee_per_gas: tx.max_fee_per_gas?,
+        gas_limit: u64::try_from(tx.gas).ok()?.into(),
+        to: tx.to,
+        value: tx.value,
+        data: tx.input.clone(),
+        access_list: vec![],
+        v: tx.v,
+        r: tx.r,
+        s: tx.s,
+    })
+}
+
+/// eth_getLogs 结果排序：按 (区块号, log_index) 升序；
+/// 因重组被移除的日志（removed = true）统一追加在末尾，按相反顺序排列
+fn order_logs(logs: Vec<Log>) -> Vec<Log> {
+    let key = |log: &Log| (log.block_number, log.log_index);
+    let (mut live, mut removed): (Vec<Log>, Vec<Log>) =
+        logs.into_iter().partition(|log| !log.removed);
+    live.sort_by_key(key);
+    removed.sort_by_key(key);
+    live.extend(removed.into_iter().rev());
+    live
+}
+
+/// 日志是否满足 eth_getLogs 过滤条件（主题按位置匹配，None 表示不限）
+fn log_matches(log: &Log, address: Option<Address>, topics: &[Option<H256>]) -> bool {
+    address.is_none_or(|address| log.address == address)
+        && topics.iter().enumerate().all(|(i, topic)| match topic {
+            Some(topic) => log.topics.get(i) == Some(topic),
+            None => true,
+        })
+}
+
+impl From<KeyStoreError> for ServiceError {
+    fn from(err: KeyStoreError) -> Self {
+        match err {
+            KeyStoreError::UnknownAccount(address) => ServiceError::UnknownAccount(address),
+            other => ServiceError::InternalError(other.to_string()),
+        }
+    }
+}
+
+/// 交易池拒绝属于客户端错误；容量不足与存储故障属于节点自身问题
+impl From<TxPoolError> for ServiceError {
+    fn from(err: TxPoolError) -> Self {
+        match err {
+            TxPoolError::AlreadyExists(_)
+            | TxPoolError::NonceGap { .. }
+            | TxPoolError::ReplacementUnderpriced { .. } => {
+                ServiceError::ValidationError(err.to_string())
+            }
+            TxPoolError::PoolFull { .. } | TxPoolError::Other(_) => {
+                ServiceError::InternalError(err.to_string())
+            }
+        }
+    }
+}
+
+/// 单个调用的执行结果转为 eth_callBundle 响应项；超时使整个 bundle 失败
+fn bundle_result(outcome: CallOutcome) -> Result<CallBundleResult, ServiceError> {
+    let (return_data, gas_used, error) = match outcome {
+        CallOutcome::Success { gas_used, output } => (output, gas_used, None),
+        CallOutcome::Revert { gas_used, output } => {
+            let reason = gas_estimator::decode_revert_reason(&output);
+            let error = match reason {
+                Some(reason) => format!("execution reverted: {}", reason),
+                None => "execution reverted".to_string(),
+            };
+            (output, gas_used, Some(error))
+        }
+        CallOutcome::Halt { gas_used, reason } => (Vec::new(), gas_used, Some(reason)),
+        CallOutcome::Invalid(msg) => (Vec::new(), 0, Some(msg)),
+        CallOutcome::TimedOut => return Err(gas_estimator::timeout_error()),
+    };
+    Ok(CallBundleResult {
+        return_data,
+        gas_used: U64::from(gas_used),
+        status: if error.is_none() { U64::one() } else { U64::zero() },
+        error,
+    })
+}
+
+/// U256 请求字段转 u64（nonce、gas 等）
+fn to_u64(value: U256, field: &str) -> Result<u64, ServiceError> {
+    u64::try_from(value)
+        .map_err(|_| ServiceError::ValidationError(format!("{} 超出 u64 范围", field)))
+}
+
 #[async_trait]
 impl EthereumService for EthereumServiceImpl {
     async fn get_block_number(&self) -> Result<U64, ServiceError> {
@@ -32,24 +476,32 @@ impl EthereumService for EthereumServiceImpl {
     async fn get_block_by_number(
         &self,
         number: U64,
-        _full_tx: bool,
+        full_tx: bool,
     ) -> Result<Option<Block>, ServiceError> {
-        Ok(self.repo.blocks.read().unwrap().get(&number).cloned())
+        let block = self.repo.blocks.read().unwrap().get(&number).cloned();
+        Ok(block.map(|block| block.with_full_transactions(full_tx)))
+    }
+
+    async fn get_block_by_id(
+        &self,
+        block: BlockId,
+        full_tx: bool,
+    ) -> Result<Option<Block>, ServiceError> {
+        let number = match self.resolve_block_number(block) {
+            Ok(number) => number,
+            Err(ServiceError::BlockNotFound) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        self.get_block_by_number(number, full_tx).await
     }
 
     async fn get_block_by_hash(
         &self,
         hash: H256,
-        _full_tx: bool,
+        full_tx: bool,
     ) -> Result<Option<Block>, ServiceError> {
-        Ok(self
-            .repo
-            .blocks
-            .read()
-            .unwrap()
-            .values()
-            .find(|b| b.hash == hash)
-            .cloned())
+        let block = self.repo.get_block_by_hash(&hash);
+        Ok(block.map(|block| block.with_full_transactions(full_tx)))
     }
 
     async fn get_transaction_by_hash(
@@ -63,12 +515,27 @@ impl EthereumService for EthereumServiceImpl {
         &self,
         hash: H256,
     ) -> Result<Option<TransactionReceipt>, ServiceError> {
-        Ok(self.repo.receipts.read().unwrap().get(&hash).cloned())
+        let receipt = self.repo.receipts.read().unwrap().get(&hash).cloned();
+        Ok(receipt.map(|receipt| self.with_fee_fields(receipt)))
+    }
+
+    async fn get_balance(&self, address: Address, _block: BlockId) -> Result<U256, ServiceError> {
+        // 模拟：状态不区分区块
+        Ok(self.repo.balance_of(&address))
     }
 
-    async fn get_balance(&self, _address: Address, _block: BlockId) -> Result<U256, ServiceError> {
-        // 模拟：返回 1 ETH
-        Ok(U256::from(1_000_000_000_000_000_000u64))
+    async fn get_balances(
+        &self,
+        addresses: Vec<Address>,
+        block: BlockId,
+    ) -> Result<BTreeMap<Address, U256>, ServiceError> {
+        let balances = futures::future::try_join_all(
+            addresses
+                .iter()
+                .map(|address| self.get_balance(*address, block.clone())),
+        )
+        .await?;
+        Ok(addresses.into_iter().zip(balances).collect())
     }
 
     async fn get_storage_at(
@@ -83,31 +550,167 @@ impl EthereumService for EthereumServiceImpl {
 
     async fn get_transaction_count(
         &self,
-        _address: Address,
-        _block: BlockId,
+        address: Address,
+        block: BlockId,
     ) -> Result<U256, ServiceError> {
-        // 模拟：返回 nonce 0
-        Ok(U256::zero())
+        use crate::service::repo::transaction_repo::TxPool;
+
+        // 模拟：已上链状态不区分区块
+        let mined = self.repo.nonce_of(&address);
+        if !matches!(block, BlockId::Tag(BlockTag::Pending)) {
+            return Ok(U256::from(mined));
+        }
+
+        // pending：跳过池中从已上链 nonce 起连续的交易，
+        // 即 max(mined, 最高连续池内 nonce + 1)，避免钱包连续发送时复用 nonce
+        let pooled: HashSet<u64> = self
+            .tx_pool
+            .get_pending_by_sender(address)
+            .await
+            .map_err(|e| ServiceError::InternalError(e.to_string()))?
+            .iter()
+            .map(|tx| tx.nonce.as_u64())
+            .collect();
+        let mut next = mined;
+        while pooled.contains(&next) {
+            next += 1;
+        }
+        Ok(U256::from(next))
+    }
+
+    async fn get_code(&self, address: Address, block: BlockId) -> Result<Vec<u8>, ServiceError> {
+        let number = self.resolve_block_number(block)?;
+        Ok(self.repo.code_at(&address, number))
+    }
+
+    async fn accounts(&self) -> Result<Vec<Address>, ServiceError> {
+        Ok(self.keystore.accounts().await?)
+    }
+
+    async fn protocol_version(&self) -> Result<U64, ServiceError> {
+        Ok(U64::from(self.protocol_version))
     }
 
-    async fn get_code(&self, _address: Address, _block: BlockId) -> Result<Vec<u8>, ServiceError> {
-        // 模拟：返回空代码
-        Ok(vec![])
+    async fn chain_id(&self) -> Result<U64, ServiceError> {
+        Ok(U64::from(self.chain_id))
     }
 
-    async fn call(&self, _request: CallRequest, _block: BlockId) -> Result<Vec<u8>, ServiceError> {
-        // 模拟：返回空结果
-        Ok(vec![])
+    async fn call(
+        &self,
+        request: CallRequest,
+        block: BlockId,
+        state_override: Option<StateOverride>,
+    ) -> Result<Vec<u8>, ServiceError> {
+        // eth_call 不要求调用者付得起 gas 与转账金额
+        let env = CallEnv {
+            gas_free: true,
+            ..self
+                .call_env(&request, block, state_override.unwrap_or_default())
+                .await?
+        };
+        let gas_limit = match request.gas {
+            Some(gas) => to_u64(gas, "gas")?,
+            None => env.block_gas_limit,
+        };
+
+        let executor = RevmCallExecutor::new(self.repo.clone());
+        let outcome = self
+            .run_blocking(move || executor.execute(&request, gas_limit, &env))
+            .await?;
+        match outcome {
+            CallOutcome::Success { output, .. } => Ok(output),
+            CallOutcome::Revert { output, .. } => Err(ServiceError::ExecutionReverted {
+                reason: gas_estimator::decode_revert_reason(&output),
+                data: output,
+            }),
+            CallOutcome::Halt { reason, .. } => Err(ServiceError::ExecutionFailed(reason)),
+            CallOutcome::Invalid(msg) => Err(ServiceError::ExecutionFailed(msg)),
+            CallOutcome::TimedOut => Err(gas_estimator::timeout_error()),
+        }
+    }
+
+    async fn call_bundle(
+        &self,
+        requests: Vec<CallRequest>,
+        block: BlockId,
+        isolated: bool,
+    ) -> Result<Vec<CallBundleResult>, ServiceError> {
+        if requests.len() > self.max_bundle_size {
+            return Err(ServiceError::InvalidParams(format!(
+                "bundle 包含 {} 个调用，超出上限 {}",
+                requests.len(),
+                self.max_bundle_size
+            )));
+        }
+        let Some(first) = requests.first() else {
+            return Ok(Vec::new());
+        };
+
+        // 执行器不区分调用者，各调用者的余额以状态覆盖注入
+        let callers: HashSet<Address> = requests
+            .iter()
+            .map(|request| request.from.unwrap_or_default())
+            .collect();
+        let mut state_override = StateOverride::new();
+        for from in callers {
+            let balance = self.get_balance(from, block.clone()).await?;
+            state_override.insert(
+                from,
+                AccountOverride {
+                    balance: Some(balance),
+                    ..Default::default()
+                },
+            );
+        }
+        let env = CallEnv {
+            gas_free: true,
+            ..self.call_env(first, block, state_override).await?
+        };
+
+        let calls = requests
+            .into_iter()
+            .map(|request| {
+                self.check_call_gas(&request)?;
+                let gas_limit = match request.gas {
+                    Some(gas) => to_u64(gas, "gas")?,
+                    None => env.block_gas_limit,
+                };
+                Ok((request, gas_limit))
+            })
+            .collect::<Result<Vec<_>, ServiceError>>()?;
+
+        let executor = RevmCallExecutor::new(self.repo.clone());
+        let outcomes = self
+            .run_blocking(move || executor.execute_bundle(&calls, &env, isolated))
+            .await?;
+        outcomes.into_iter().map(bundle_result).collect()
     }
 
-    async fn estimate_gas(&self, _request: CallRequest) -> Result<U256, ServiceError> {
-        // 模拟：返回 21000 gas（标准转账）
-        Ok(U256::from(21000u64))
+    async fn estimate_gas(&self, request: CallRequest) -> Result<U256, ServiceError> {
+        let env = self
+            .call_env(&request, BlockId::Tag(BlockTag::Latest), StateOverride::default())
+            .await?;
+        let executor = RevmCallExecutor::new(self.repo.clone());
+        self.run_blocking(move || gas_estimator::estimate_gas(&executor, &request, &env))
+            .await?
+            .map(U256::from)
     }
 
-    async fn get_logs(&self, _filter: FilterOptions) -> Result<Vec<Log>, ServiceError> {
-        // 模拟：返回空日志列表
-        Ok(vec![])
+    async fn get_logs(&self, filter: FilterOptions) -> Result<Vec<Log>, ServiceError> {
+        let latest = || BlockId::Tag(BlockTag::Latest);
+        let from = self.resolve_block_number(filter.from_block.clone().unwrap_or_else(latest))?;
+        let to = self.resolve_block_number(filter.to_block.clone().unwrap_or_else(latest))?;
+
+        // 逐块收集，超出上限立即返回，不先物化整个区间的日志
+        let mut logs = Vec::new();
+        for block_logs in self.logs_by_block(from, to, &filter) {
+            let block_logs = block_logs?;
+            if logs.len() + block_logs.len() > self.max_log_results {
+                return Err(ServiceError::TooManyResults(self.max_log_results));
+            }
+            logs.extend(block_logs);
+        }
+        Ok(order_logs(logs))
     }
 
     // EIP-1559 相关方法实现
@@ -116,60 +719,74 @@ impl EthereumService for EthereumServiceImpl {
         &self,
         request: SendTransactionRequest,
     ) -> Result<H256, ServiceError> {
-        // 模拟：生成交易哈希（基于输入参数的简单组合）
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-
-        let mut hasher = DefaultHasher::new();
-        request.from.as_bytes().hash(&mut hasher);
-        if let Some(to) = request.to {
-            to.as_bytes().hash(&mut hasher);
-        }
-        if let Some(value) = request.value {
-            let mut value_bytes = [0u8; 32];
-            value.to_big_endian(&mut value_bytes);
-            value_bytes.hash(&mut hasher);
+        let from = request.from;
+        if !self.keystore.has_account(&from).await? {
+            return Err(ServiceError::UnknownAccount(from));
         }
 
-        let hash_value = hasher.finish();
-        let mut hash = [0u8; 32];
-        hash[0..8].copy_from_slice(&hash_value.to_be_bytes());
-        let tx_hash = H256::from(hash);
+        // 费用字段：Legacy 请求的 gasPrice 同时作为 max fee 和 priority fee，
+        // 与 Legacy 交易在 EIP-1559 下的实际计费等价
+        let max_priority_fee_per_gas = match (request.max_priority_fee_per_gas, request.gas_price) {
+            (Some(tip), _) => tip,
+            (None, Some(gas_price)) => gas_price,
+            (None, None) => self.max_priority_fee_per_gas().await?,
+        };
+        // 未指定时参考 geth：max_fee = tip + 2 * base_fee
+        let max_fee_per_gas = request.max_fee_per_gas.or(request.gas_price).unwrap_or(
+            max_priority_fee_per_gas + (BaseFeeCalculator::initial_base_fee() * 2).as_u256(),
+        );
 
-        // 创建模拟交易
-        let tx = Transaction {
-            hash: tx_hash,
-            nonce: request.nonce.unwrap_or(U256::zero()),
-            block_hash: None,
-            block_number: None,
-            transaction_index: None,
-            from: request.from,
+        let nonce = match request.nonce {
+            Some(nonce) => to_u64(nonce, "nonce")?,
+            None => to_u64(
+                self.get_transaction_count(from, BlockId::Tag(BlockTag::Pending)).await?,
+                "nonce",
+            )?,
+        };
+
+        let gas_limit = match request.gas {
+            Some(gas) => to_u64(gas, "gas")?,
+            None => {
+                let call = CallRequest {
+                    from: Some(from),
+                    to: request.to,
+                    gas: None,
+                    gas_price: request.gas_price,
+                    value: request.value,
+                    data: request.data.clone(),
+                    max_fee_per_gas: Some(max_fee_per_gas),
+                    max_priority_fee_per_gas: Some(max_priority_fee_per_gas),
+                };
+                to_u64(self.estimate_gas(call).await?, "gas")?
+            }
+        };
+
+        let mut tx = DynamicFeeTx {
+            chain_id: U64::from(self.chain_id),
+            nonce: U64::from(nonce),
+            max_priority_fee_per_gas,
+            max_fee_per_gas,
+            gas_limit: U64::from(gas_limit),
             to: request.to,
-            value: request.value.unwrap_or(U256::zero()),
-            gas_price: request.gas_price,
-            gas: request.gas.unwrap_or(U256::from(21000)),
-            input: request.data.unwrap_or_default(),
+            value: request.value.unwrap_or_default(),
+            data: request.data.unwrap_or_default(),
+            access_list: vec![],
             v: U64::zero(),
             r: U256::zero(),
             s: U256::zero(),
-            max_fee_per_gas: request.max_fee_per_gas,
-            max_priority_fee_per_gas: request.max_priority_fee_per_gas,
-            transaction_type: if request.max_fee_per_gas.is_some() {
-                Some(U64::from(2)) // EIP-1559
-            } else {
-                Some(U64::from(0)) // Legacy
-            },
         };
 
-        self.repo.add_transaction(tx);
-        Ok(tx_hash)
+        // 使用本地密钥签名
+        let signature = self.keystore.sign_hash(&from, &tx.signing_hash()).await?;
+        tx.v = U64::from(signature.recovery_id);
+        tx.r = signature.r;
+        tx.s = signature.s;
+
+        // 与原始交易走同一条入池流程
+        self.send_raw_transaction(tx).await
     }
 
-    async fn send_raw_transaction(
-        &self,
-        tx: crate::domain::tx_types::DynamicFeeTx,
-        sender: Address,
-    ) -> Result<H256, ServiceError> {
+    async fn send_raw_transaction(&self, tx: DynamicFeeTx) -> Result<H256, ServiceError> {
         // ========================================================================
         // Service 层职责（业务逻辑处理）
         // ========================================================================
@@ -193,71 +810,42 @@ impl EthereumService for EthereumServiceImpl {
         // - gas_limit >= 最小值（21000）
         // - 交易数据大小 <= 128KB
         // - 签名值有效性（v <= 1）
-        tx.validate_basic().map_err(|e| {
+        tx.validate_basic_with_schedule(&self.gas_schedule.intrinsic).map_err(|e| {
             ServiceError::ValidationError(format!("基本验证失败: {}", e))
         })?;
 
+        // ====================================================================
+        // Step 1.5: Chain ID 验证（EIP-155 重放保护）
+        // ====================================================================
+        let validator = self.tx_validator();
+        validator.validate_chain_id(&tx).map_err(|e| {
+            ServiceError::ValidationError(format!("基本验证失败: {}", e))
+        })?;
+
+        // ====================================================================
+        // Step 1.6: 签名恢复发送者
+        // ====================================================================
+        // 后续的 nonce 校验、限流与入池均以恢复出的地址为准，调用方无法伪造 sender
+        let sender = tx.recover_sender().map_err(|e| {
+            ServiceError::ValidationError(format!("签名验证失败: {}", e))
+        })?;
+
         // ====================================================================
         // Step 2: 状态验证（依赖区块链状态）
         // ====================================================================
-        // TODO: 实现完整的状态验证
-        //
-        // 需要验证的内容：
-        // 1. Chain ID 匹配
-        // 2. Nonce 正确性（必须等于账户当前 nonce）
-        // 3. 账户余额充足（balance >= max_cost = max_fee * gas_limit + value）
-        // 4. 签名有效性（ECDSA 签名验证并恢复发送者地址）
-        // 5. Gas 价格合理性（max_fee_per_gas >= base_fee）
-        //
-        // 实现方式：
-        // ```rust
-        // // 验证 Chain ID
-        // let expected_chain_id = U64::from(1); // 从配置读取
-        // if tx.chain_id != expected_chain_id {
-        //     return Err(ServiceError::ValidationError(
-        //         format!("Chain ID 不匹配: 期望 {}, 实际 {}", expected_chain_id, tx.chain_id)
-        //     ));
-        // }
-        //
-        // // 验证 Nonce
-        // let current_nonce = self.get_transaction_count(sender, BlockId::Tag(BlockTag::Latest)).await?;
-        // if U256::from(tx.nonce.as_u64()) != current_nonce {
-        //     return Err(ServiceError::ValidationError(
-        //         format!("Nonce 不正确: 期望 {}, 实际 {}", current_nonce, tx.nonce)
-        //     ));
-        // }
-        //
-        // // 验证余额
-        // let balance = self.get_balance(sender, BlockId::Tag(BlockTag::Latest)).await?;
-        // let max_cost = tx.max_cost(); // max_fee_per_gas * gas_limit + value
-        // if balance < max_cost {
-        //     return Err(ServiceError::ValidationError(
-        //         format!("余额不足: 需要 {}, 当前 {}", max_cost, balance)
-        //     ));
-        // }
-        //
-        // // 验证签名（需要实现 ECDSA 恢复）
-        // let recovered_sender = tx.recover_sender().map_err(|e| {
-        //     ServiceError::ValidationError(format!("签名验证失败: {}", e))
-        // })?;
-        // if recovered_sender != sender {
-        //     return Err(ServiceError::ValidationError(
-        //         format!("发送者地址不匹配: 签名恢复 {}, 参数提供 {}", recovered_sender, sender)
-        //     ));
-        // }
-        //
-        // // 验证 Gas 价格（需要当前区块的 base_fee）
-        // let current_block = self.get_block_number().await?;
-        // if let Some(block) = self.get_block_by_number(current_block, false).await? {
-        //     if let Some(base_fee) = block.base_fee_per_gas {
-        //         if tx.max_fee_per_gas < base_fee {
-        //             return Err(ServiceError::ValidationError(
-        //                 format!("Max fee 过低: 最低 {} (base fee), 实际 {}", base_fee, tx.max_fee_per_gas)
-        //             ));
-        //         }
-        //     }
-        // }
-        // ```
+        // 账户状态经 TTL 缓存查询，同一发送者连续提交时不重复访问仓储
+        validator.validate_nonce(&tx, sender).await.map_err(|e| {
+            ServiceError::ValidationError(format!("状态验证失败: {}", e))
+        })?;
+
+        // ====================================================================
+        // Step 2.5: 按发送者限流
+        // ====================================================================
+        if let Some(limiter) = &self.rate_limiter {
+            limiter
+                .check(sender)
+                .map_err(|e| ServiceError::RateLimited(e.to_string()))?;
+        }
 
         // ====================================================================
         // Step 3: 防重放检查
@@ -273,9 +861,7 @@ impl EthereumService for EthereumServiceImpl {
         // - 检查替换交易的价格提升（10%）
         // - 按 nonce 排序管理 pending 交易
         // - 返回交易哈希
-        let tx_hash = self.tx_pool.add(tx.clone(), sender).await.map_err(|e| {
-            ServiceError::Other(format!("加入交易池失败: {}", e))
-        })?;
+        let tx_hash = self.tx_pool.add(tx.clone(), sender).await?;
 
         // ====================================================================
         // Step 5: 事件发布（异步，不阻塞返回）
@@ -317,15 +903,122 @@ impl EthereumService for EthereumServiceImpl {
         Ok(tx_hash)
     }
 
+    async fn validate_legacy_chain_id(&self, v: u64) -> Result<(), ServiceError> {
+        self.tx_validator()
+            .validate_legacy_chain_id(v)
+            .map_err(|e| ServiceError::ValidationError(format!("基本验证失败: {}", e)))
+    }
+
+    async fn sign_typed_data(
+        &self,
+        address: Address,
+        typed_data: TypedData,
+    ) -> Result<Vec<u8>, ServiceError> {
+        let hash = typed_data
+            .signing_hash()
+            .map_err(|e| ServiceError::InvalidParams(e.to_string()))?;
+        let signature = self.keystore.sign_hash(&address, &hash).await?;
+
+        // r ‖ s ‖ v，v 沿用 eth_sign 约定的 27/28
+        let mut encoded = vec![0u8; 65];
+        signature.r.to_big_endian(&mut encoded[..32]);
+        signature.s.to_big_endian(&mut encoded[32..64]);
+        encoded[64] = 27 + signature.recovery_id;
+        Ok(encoded)
+    }
+
+    async fn get_signed_transaction(&self, hash: H256) -> Result<Option<DynamicFeeTx>, ServiceError> {
+        use crate::service::repo::transaction_repo::TxPool;
+
+        if let Some(tx) = self.tx_pool.get(&hash).await? {
+            return Ok(Some(tx));
+        }
+
+        let Some(tx) = self.repo.transactions.read().unwrap().get(&hash).cloned() else {
+            return Ok(None);
+        };
+        signed_tx_from_rpc(&tx, U64::from(self.chain_id))
+            .filter(|signed| signed.hash() == hash)
+            .map(Some)
+            .ok_or_else(|| {
+                ServiceError::Other(format!("交易 {:?} 无法还原为 EIP-2718 原始编码", hash))
+            })
+    }
+
+    async fn get_block_receipts(
+        &self,
+        block: BlockId,
+    ) -> Result<Option<Vec<TransactionReceipt>>, ServiceError> {
+        let number = match self.resolve_block_number(block) {
+            Ok(number) => number,
+            Err(ServiceError::BlockNotFound) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let Some(block) = self.repo.blocks.read().unwrap().get(&number).cloned() else {
+            return Ok(None);
+        };
+
+        // 完整交易体直接随区块给出，仓储中查不到交易时也能填充 type 与实际价格
+        let txs: Vec<(H256, Option<&Transaction>)> = match &block.transactions {
+            BlockTransactions::Full(txs) => txs.iter().map(|tx| (tx.hash, Some(tx))).collect(),
+            BlockTransactions::Hashes(hashes) => hashes.iter().map(|hash| (*hash, None)).collect(),
+        };
+
+        let receipts = self.repo.receipts.read().unwrap();
+        txs.into_iter()
+            .map(|(hash, tx)| {
+                let mut receipt = receipts.get(&hash).cloned().ok_or_else(|| {
+                    ServiceError::Other(format!("交易 {:?} 的收据缺失", hash))
+                })?;
+                match tx {
+                    Some(tx) => receipt.set_fee_fields(tx, block.base_fee_per_gas),
+                    None => receipt = self.with_fee_fields(receipt),
+                }
+                Ok(receipt)
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Some)
+    }
+
     async fn fee_history(
         &self,
         block_count: U64,
-        _newest_block: BlockId,
+        newest_block: BlockId,
         reward_percentiles: Option<Vec<f64>>,
     ) -> Result<FeeHistory, ServiceError> {
-        let current_block = self.get_block_number().await?;
-        let oldest_block = if current_block >= block_count {
-            current_block - block_count + U64::from(1)
+        if block_count.is_zero() {
+            return Err(ServiceError::InvalidParams("blockCount 必须大于 0".to_string()));
+        }
+        let block_count = block_count.min(U64::from(Self::MAX_FEE_HISTORY_BLOCKS));
+
+        // 百分位须在 [0, 100] 内且严格递增
+        if let Some(percentiles) = &reward_percentiles {
+            for (i, &p) in percentiles.iter().enumerate() {
+                if !(0.0..=100.0).contains(&p) {
+                    return Err(ServiceError::InvalidParams(format!(
+                        "奖励百分位 {} 超出 [0, 100]",
+                        p
+                    )));
+                }
+                if i > 0 && p <= percentiles[i - 1] {
+                    return Err(ServiceError::InvalidParams(format!(
+                        "奖励百分位须严格递增: #{}:{} <= #{}:{}",
+                        i,
+                        p,
+                        i - 1,
+                        percentiles[i - 1]
+                    )));
+                }
+            }
+        }
+
+        // 超出链头的区块号截断到链头；标签（含 safe / finalized）解析为具体区块号
+        let newest_block = match newest_block {
+            BlockId::Number(number) => number.min(self.get_block_number().await?),
+            tag => self.resolve_block_number(tag)?,
+        };
+        let oldest_block = if newest_block >= block_count {
+            newest_block - block_count + U64::from(1)
         } else {
             U64::zero()
         };
@@ -361,8 +1054,24 @@ impl EthereumService for EthereumServiceImpl {
     }
 
     async fn max_priority_fee_per_gas(&self) -> Result<U256, ServiceError> {
-        // 模拟：返回 2 Gwei 作为建议的优先费用
-        Ok(U256::from(2_000_000_000u64))
+        Self::SUGGESTED_PRIORITY_FEE
+            .to_wei()
+            .map(U256::from)
+            .ok_or_else(|| ServiceError::InternalError("优先费用溢出".to_string()))
+    }
+
+    async fn blob_base_fee(&self) -> Result<U256, ServiceError> {
+        let head = *self.repo.current_block_number.read().unwrap();
+        let excess_blob_gas = self
+            .repo
+            .blocks
+            .read()
+            .unwrap()
+            .get(&head)
+            .ok_or(ServiceError::BlockNotFound)?
+            .excess_blob_gas
+            .unwrap_or_default();
+        Ok(BlobBaseFeeCalculator::blob_base_fee(excess_blob_gas))
     }
 }
 
@@ -372,6 +1081,8 @@ impl EthereumService for EthereumServiceImpl {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::command_types::tests::sample_transaction;
+    use ethereum_types::{Bloom, BloomInput};
 
     #[tokio::test]
     async fn test_mock_repository() {
@@ -399,4 +1110,756 @@ mod tests {
             .unwrap();
         assert_eq!(balance, U256::from(1_000_000_000_000_000_000u64));
     }
+
+    fn transfer_request(from: Address) -> SendTransactionRequest {
+        SendTransactionRequest {
+            from,
+            to: Some(Address::from_low_u64_be(2)),
+            gas: Some(U256::from(21000)),
+            gas_price: None,
+            value: Some(U256::from(1000)),
+            data: None,
+            nonce: Some(U256::zero()),
+            max_fee_per_gas: Some(U256::from(30_000_000_000u64)),
+            max_priority_fee_per_gas: Some(U256::from(2_000_000_000u64)),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_accounts_lists_keystore() {
+        let keystore = InMemoryKeyStore::new();
+        let a = keystore.import_key(&[0x11u8; 32]).unwrap();
+        let b = keystore.import_key(&[0x22u8; 32]).unwrap();
+        let service = EthereumServiceImpl::with_keystore(MockEthereumRepository::new(), keystore);
+
+        assert_eq!(service.accounts().await.unwrap(), vec![a, b]);
+    }
+
+    #[tokio::test]
+    async fn test_pending_nonce_counts_pool_transactions() {
+        use crate::service::repo::transaction_repo::TxPool;
+
+        let keystore = InMemoryKeyStore::new();
+        let from = keystore.import_key(&[0x11u8; 32]).unwrap();
+        let repo = MockEthereumRepository::new();
+        repo.set_nonce(from, 3);
+        let service = EthereumServiceImpl::with_keystore(repo, keystore);
+
+        // 未指定 nonce 的连续发送依次取得 3、4
+        for expected in [3u64, 4] {
+            let mut request = transfer_request(from);
+            request.nonce = None;
+            let hash = service.send_transaction(request).await.unwrap();
+            let tx = service.tx_pool.get(&hash).await.unwrap().unwrap();
+            assert_eq!(tx.nonce.as_u64(), expected);
+        }
+
+        let pending = service
+            .get_transaction_count(from, BlockId::Tag(BlockTag::Pending))
+            .await
+            .unwrap();
+        let latest = service
+            .get_transaction_count(from, BlockId::Tag(BlockTag::Latest))
+            .await
+            .unwrap();
+        assert_eq!(pending, U256::from(5));
+        assert_eq!(latest, U256::from(3));
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_signs_with_managed_key() {
+        use crate::service::repo::transaction_repo::TxPool;
+
+        let keystore = InMemoryKeyStore::new();
+        let from = keystore.import_key(&[0x11u8; 32]).unwrap();
+        let service = EthereumServiceImpl::with_keystore(MockEthereumRepository::new(), keystore);
+
+        let hash = service.send_transaction(transfer_request(from)).await.unwrap();
+
+        let tx = service.tx_pool.get(&hash).await.unwrap().expect("交易应已入池");
+        assert_eq!(tx.hash(), hash);
+        assert_eq!(tx.chain_id, U64::from(EthereumServiceImpl::CHAIN_ID));
+        assert_eq!(tx.recover_sender().unwrap(), from);
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_rejects_unmanaged_account() {
+        let service = EthereumServiceImpl::new(MockEthereumRepository::new());
+        let from = Address::from_low_u64_be(1);
+
+        let result = service.send_transaction(transfer_request(from)).await;
+        assert!(matches!(result, Err(ServiceError::UnknownAccount(addr)) if addr == from));
+    }
+
+    #[tokio::test]
+    async fn test_sign_typed_data_matches_eip712_example() {
+        use crate::domain::typed_data::tests::mail;
+        use sha3::{Digest, Keccak256};
+
+        // EIP-712 规范示例：私钥 keccak256("cow")，对 Mail 消息签名
+        let keystore = InMemoryKeyStore::new();
+        let cow = keystore.import_key(&Keccak256::digest(b"cow")).unwrap();
+        let service = EthereumServiceImpl::with_keystore(MockEthereumRepository::new(), keystore);
+
+        let typed_data = mail();
+
+        let signature = service.sign_typed_data(cow, typed_data.clone()).await.unwrap();
+        assert_eq!(
+            hex::encode(signature),
+            "4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d\
+             07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562\
+             1c"
+        );
+
+        let result = service
+            .sign_typed_data(Address::from_low_u64_be(1), typed_data)
+            .await;
+        assert!(matches!(result, Err(ServiceError::UnknownAccount(_))));
+    }
+
+    #[tokio::test]
+    async fn test_send_raw_transaction_rejects_wrong_chain_id() {
+        use crate::service::repo::transaction_repo::TxPool;
+
+        let keystore = InMemoryKeyStore::new();
+        let from = keystore.import_key(&[0x11u8; 32]).unwrap();
+        let mainnet = EthereumServiceImpl::with_keystore(MockEthereumRepository::new(), keystore);
+        let hash = mainnet.send_transaction(transfer_request(from)).await.unwrap();
+        let signed = mainnet.tx_pool.get(&hash).await.unwrap().unwrap();
+
+        // 主网签名的交易不能在其他链上重放
+        let other_chain = EthereumServiceImpl::new(MockEthereumRepository::new()).with_chain_id(5);
+        let err = other_chain.send_raw_transaction(signed).await.unwrap_err();
+        assert!(
+            matches!(&err, ServiceError::ValidationError(m) if m.contains("Wrong chain ID: expected 5, got 1")),
+            "{:?}",
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_raw_transaction_rejects_used_nonce() {
+        use crate::service::repo::transaction_repo::TxPool;
+
+        let keystore = InMemoryKeyStore::new();
+        let from = keystore.import_key(&[0x11u8; 32]).unwrap();
+        let signer = EthereumServiceImpl::with_keystore(MockEthereumRepository::new(), keystore);
+        let hash = signer.send_transaction(transfer_request(from)).await.unwrap();
+        let signed = signer.tx_pool.get(&hash).await.unwrap().unwrap();
+
+        let repo = MockEthereumRepository::new();
+        repo.set_nonce(from, signed.nonce.as_u64() + 1);
+        let service = EthereumServiceImpl::new(repo.clone());
+        let err = service.send_raw_transaction(signed.clone()).await.unwrap_err();
+        assert!(
+            matches!(&err, ServiceError::ValidationError(m) if m.contains("Nonce too low")),
+            "{:?}",
+            err
+        );
+
+        // 账户状态在同一链头内缓存；仓储写入新区块后重新查询
+        repo.set_nonce(from, signed.nonce.as_u64());
+        assert!(service.send_raw_transaction(signed.clone()).await.is_err());
+        let mut block = repo.get_block_by_hash(&H256::zero()).unwrap();
+        block.number = U64::one();
+        block.hash = H256::repeat_byte(0x01);
+        repo.add_block(block);
+        service.send_raw_transaction(signed).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sender_rate_limit() {
+        use crate::service::sender_rate_limiter::SenderRateLimitConfig;
+        use std::time::Duration;
+
+        let keystore = InMemoryKeyStore::new();
+        let spammer = keystore.import_key(&[0x11u8; 32]).unwrap();
+        let other = keystore.import_key(&[0x22u8; 32]).unwrap();
+        let service = EthereumServiceImpl::with_keystore(MockEthereumRepository::new(), keystore)
+            .with_sender_rate_limit(SenderRateLimitConfig {
+                max_txs: 2,
+                window: Duration::from_secs(60),
+                ..SenderRateLimitConfig::default()
+            });
+
+        let send = |from: Address, nonce: u64| {
+            let mut request = transfer_request(from);
+            request.nonce = Some(U256::from(nonce));
+            service.send_transaction(request)
+        };
+
+        send(spammer, 0).await.unwrap();
+        send(spammer, 1).await.unwrap();
+        let result = send(spammer, 2).await;
+        assert!(matches!(result, Err(ServiceError::RateLimited(_))));
+
+        // 其他发送者不受影响
+        send(other, 0).await.unwrap();
+        send(other, 1).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_fee_history_rejects_zero_block_count() {
+        let service = EthereumServiceImpl::new(MockEthereumRepository::new());
+
+        let result = service
+            .fee_history(U64::zero(), BlockId::Tag(BlockTag::Latest), None)
+            .await;
+        assert!(matches!(result, Err(ServiceError::InvalidParams(_))));
+    }
+
+    #[tokio::test]
+    async fn test_fee_history_clamps_block_count() {
+        let service = EthereumServiceImpl::new(MockEthereumRepository::new());
+
+        let history = service
+            .fee_history(U64::from(5000), BlockId::Tag(BlockTag::Latest), None)
+            .await
+            .unwrap();
+        assert_eq!(history.gas_used_ratio.len(), 1024);
+        assert_eq!(history.base_fee_per_gas.len(), 1025);
+    }
+
+    #[tokio::test]
+    async fn test_fee_history_rejects_invalid_percentiles() {
+        let service = EthereumServiceImpl::new(MockEthereumRepository::new());
+
+        for percentiles in [vec![50.0, 25.0], vec![10.0, 10.0], vec![-1.0], vec![50.0, 100.5]] {
+            let result = service
+                .fee_history(U64::from(1), BlockId::Tag(BlockTag::Latest), Some(percentiles.clone()))
+                .await;
+            assert!(
+                matches!(result, Err(ServiceError::InvalidParams(_))),
+                "{:?} 应被拒绝",
+                percentiles
+            );
+        }
+
+        let result = service
+            .fee_history(U64::from(1), BlockId::Tag(BlockTag::Latest), Some(vec![0.0, 50.0, 100.0]))
+            .await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_fee_history_clamps_newest_block_to_head() {
+        let repo = MockEthereumRepository::new();
+        let mut block = repo.get_block_by_hash(&H256::zero()).unwrap();
+        block.number = U64::from(10);
+        block.hash = H256::from_low_u64_be(10);
+        repo.add_block(block);
+        let service = EthereumServiceImpl::new(repo);
+
+        let history = service
+            .fee_history(U64::from(4), BlockId::Number(U64::from(1000)), None)
+            .await
+            .unwrap();
+        assert_eq!(history.oldest_block, U64::from(7));
+    }
+
+    #[tokio::test]
+    async fn test_fee_history_resolves_newest_block() {
+        let repo = MockEthereumRepository::new();
+        for number in 1..=20u64 {
+            let mut block = repo.get_block_by_hash(&H256::zero()).unwrap();
+            block.number = U64::from(number);
+            block.hash = H256::from_low_u64_be(number);
+            repo.add_block(block);
+        }
+        let service = EthereumServiceImpl::new(repo.clone());
+
+        // 共识层尚未通知 finalized 区块
+        let result = service
+            .fee_history(U64::from(4), BlockId::Tag(BlockTag::Finalized), None)
+            .await;
+        assert!(matches!(result, Err(ServiceError::BlockNotFound)));
+
+        repo.set_forkchoice(U64::from(16), U64::from(12));
+        for (newest_block, expected_newest) in [
+            (BlockId::Tag(BlockTag::Latest), 20u64),
+            (BlockId::Tag(BlockTag::Safe), 16),
+            (BlockId::Tag(BlockTag::Finalized), 12),
+            (BlockId::Number(U64::from(9)), 9),
+        ] {
+            let history = service
+                .fee_history(U64::from(4), newest_block, None)
+                .await
+                .unwrap();
+            // 窗口 [oldest, oldest + 4) 以解析出的区块结尾
+            assert_eq!(history.oldest_block, U64::from(expected_newest - 3));
+            assert_eq!(history.gas_used_ratio.len(), 4);
+        }
+    }
+
+    /// 含一笔交易、一条日志的区块；`bloom` 为区块头中的 logs_bloom
+    fn block_with_log(repo: &MockEthereumRepository, number: u64, address: Address, bloom: Bloom) {
+        block_with_logs(repo, number, address, bloom, &[(0, false)]);
+    }
+
+    /// 含一笔交易的区块，收据中的日志按给定 (log_index, removed) 顺序写入
+    fn block_with_logs(
+        repo: &MockEthereumRepository,
+        number: u64,
+        address: Address,
+        bloom: Bloom,
+        logs: &[(u64, bool)],
+    ) {
+        let block_hash = H256::from_low_u64_be(number);
+        let tx_hash = H256::from_low_u64_be(0x1000 + number);
+        let logs = logs
+            .iter()
+            .map(|&(log_index, removed)| Log {
+                removed,
+                log_index: U256::from(log_index),
+                transaction_index: U256::zero(),
+                transaction_hash: tx_hash,
+                block_hash,
+                block_number: U64::from(number),
+                address,
+                data: vec![],
+                topics: vec![H256::repeat_byte(0x11)],
+            })
+            .collect();
+        repo.add_receipt(TransactionReceipt {
+            transaction_hash: tx_hash,
+            transaction_index: U64::zero(),
+            block_hash,
+            block_number: U64::from(number),
+            from: Address::from_low_u64_be(1),
+            to: Some(address),
+            cumulative_gas_used: U256::from(30000),
+            gas_used: U256::from(30000),
+            effective_gas_price: U256::from(1_000_000_000u64),
+            contract_address: None,
+            logs,
+            logs_bloom: bloom,
+            status: U64::one(),
+            transaction_type: U64::from(2),
+        });
+
+        let mut block = repo.get_block_by_hash(&H256::zero()).unwrap();
+        block.number = U64::from(number);
+        block.hash = block_hash;
+        block.logs_bloom = bloom;
+        block.transactions = BlockTransactions::Hashes(vec![tx_hash]);
+        repo.add_block(block);
+    }
+
+    #[tokio::test]
+    async fn test_get_logs_skips_blocks_by_bloom() {
+        let address = Address::from_low_u64_be(0xc0de);
+        let mut bloom = Bloom::zero();
+        bloom.accrue(BloomInput::Raw(address.as_bytes()));
+        bloom.accrue(BloomInput::Raw(H256::repeat_byte(0x11).as_bytes()));
+
+        let repo = MockEthereumRepository::new();
+        // 区块 1 的 Bloom 不含该地址：即使收据中有日志也不应被扫描
+        block_with_log(&repo, 1, address, Bloom::zero());
+        block_with_log(&repo, 2, address, bloom);
+        let service = EthereumServiceImpl::new(repo);
+
+        let logs = service
+            .get_logs(FilterOptions {
+                from_block: Some(BlockId::Number(U64::one())),
+                to_block: Some(BlockId::Tag(BlockTag::Latest)),
+                address: Some(address),
+                topics: Some(vec![Some(H256::repeat_byte(0x11))]),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].block_number, U64::from(2));
+        assert_eq!(logs[0].address, address);
+    }
+
+    #[tokio::test]
+    async fn test_get_logs_ordered_by_block_and_index() {
+        let address = Address::from_low_u64_be(0xc0de);
+        let mut bloom = Bloom::zero();
+        bloom.accrue(BloomInput::Raw(address.as_bytes()));
+
+        let repo = MockEthereumRepository::new();
+        // 收据中的日志顺序被打乱，并混入重组移除的日志
+        block_with_logs(&repo, 1, address, bloom, &[(2, false), (0, true), (1, false)]);
+        block_with_logs(&repo, 2, address, bloom, &[(1, true), (0, false)]);
+        block_with_logs(&repo, 3, address, bloom, &[(1, false), (0, false)]);
+        let service = EthereumServiceImpl::new(repo);
+
+        let filter = FilterOptions {
+            from_block: Some(BlockId::Number(U64::one())),
+            to_block: Some(BlockId::Number(U64::from(3))),
+            address: Some(address),
+            topics: None,
+        };
+        let order = |logs: Vec<Log>| -> Vec<(u64, u64, bool)> {
+            logs.iter()
+                .map(|log| (log.block_number.as_u64(), log.log_index.as_u64(), log.removed))
+                .collect()
+        };
+
+        let first = order(service.get_logs(filter.clone()).await.unwrap());
+        assert_eq!(
+            first,
+            vec![
+                (1, 1, false),
+                (1, 2, false),
+                (2, 0, false),
+                (3, 0, false),
+                (3, 1, false),
+                // removed 日志在末尾，逆序
+                (2, 1, true),
+                (1, 0, true),
+            ]
+        );
+
+        // 重复查询结果一致
+        assert_eq!(order(service.get_logs(filter).await.unwrap()), first);
+    }
+
+    #[tokio::test]
+    async fn test_receipt_effective_gas_price_uses_block_base_fee() {
+        let gwei = |n: u64| U256::from(n * 1_000_000_000);
+        let repo = MockEthereumRepository::new();
+        let hash = H256::repeat_byte(0x01);
+        // 创世区块 base fee 1 Gwei：1 Gwei + 小费 1 Gwei 未超过 max_fee 3 Gwei
+        repo.add_transaction(Transaction {
+            hash,
+            nonce: U256::zero(),
+            block_hash: Some(H256::zero()),
+            block_number: Some(U64::zero()),
+            transaction_index: Some(U64::zero()),
+            from: Address::from_low_u64_be(1),
+            to: Some(Address::from_low_u64_be(2)),
+            value: U256::zero(),
+            gas_price: None,
+            gas: U256::from(21000),
+            input: vec![],
+            v: U64::zero(),
+            r: U256::one(),
+            s: U256::one(),
+            max_fee_per_gas: Some(gwei(3)),
+            max_priority_fee_per_gas: Some(gwei(1)),
+            transaction_type: Some(U64::from(2)),
+        });
+        repo.add_receipt(TransactionReceipt {
+            transaction_hash: hash,
+            transaction_index: U64::zero(),
+            block_hash: H256::zero(),
+            block_number: U64::zero(),
+            from: Address::from_low_u64_be(1),
+            to: Some(Address::from_low_u64_be(2)),
+            cumulative_gas_used: U256::from(21000),
+            gas_used: U256::from(21000),
+            effective_gas_price: U256::zero(),
+            contract_address: None,
+            logs: vec![],
+            logs_bloom: Bloom::zero(),
+            status: U64::one(),
+            transaction_type: U64::zero(),
+        });
+        let service = EthereumServiceImpl::new(repo);
+
+        let receipt = service.get_transaction_receipt(hash).await.unwrap().unwrap();
+        assert_eq!(receipt.effective_gas_price, gwei(2));
+        assert_eq!(receipt.transaction_type, U64::from(2));
+    }
+
+    #[tokio::test]
+    async fn test_transaction_by_hash_follows_reorg() {
+        let repo = MockEthereumRepository::new();
+        let tx = |hash: H256| Transaction {
+            block_hash: None,
+            block_number: None,
+            transaction_index: None,
+            ..sample_transaction(hash)
+        };
+        let block = |number: u64, hash: H256, txs: Vec<Transaction>| {
+            let mut block = repo.get_block_by_hash(&H256::zero()).unwrap();
+            block.number = U64::from(number);
+            block.hash = hash;
+            block.transactions = BlockTransactions::Full(txs);
+            block
+        };
+        let (moved, dropped) = (H256::repeat_byte(0x01), H256::repeat_byte(0x02));
+        let (block_a, block_b) = (H256::repeat_byte(0xaa), H256::repeat_byte(0xbb));
+
+        repo.add_block(block(1, block_a, vec![tx(moved), tx(dropped)]));
+        let service = EthereumServiceImpl::new(repo.clone());
+        let position = |tx: Transaction| (tx.block_hash, tx.block_number, tx.transaction_index);
+        let found = service.get_transaction_by_hash(moved).await.unwrap().unwrap();
+        assert_eq!(position(found), (Some(block_a), Some(U64::one()), Some(U64::zero())));
+
+        // 重组：同高度的 B 替换 A，moved 在 B 中位于索引 1，dropped 未被打包
+        repo.add_block(block(1, block_b, vec![tx(H256::repeat_byte(0x03)), tx(moved)]));
+        let found = service.get_transaction_by_hash(moved).await.unwrap().unwrap();
+        assert_eq!(position(found), (Some(block_b), Some(U64::one()), Some(U64::one())));
+
+        // 不再被打包的交易退回 pending 形式
+        let pending = service.get_transaction_by_hash(dropped).await.unwrap().unwrap();
+        assert_eq!(position(pending), (None, None, None));
+    }
+
+    #[tokio::test]
+    async fn test_get_logs_stops_at_result_cap() {
+        let address = Address::from_low_u64_be(0xc0de);
+        let mut bloom = Bloom::zero();
+        bloom.accrue(BloomInput::Raw(address.as_bytes()));
+
+        let repo = MockEthereumRepository::new();
+        let per_block: Vec<(u64, bool)> = (0..100).map(|index| (index, false)).collect();
+        for number in 1..=10 {
+            block_with_logs(&repo, number, address, bloom, &per_block);
+        }
+        // 区间末尾的区块收据缺失：若扫描到这里会返回其他错误
+        let mut broken = repo.get_block_by_hash(&H256::zero()).unwrap();
+        broken.number = U64::from(11);
+        broken.hash = H256::from_low_u64_be(11);
+        broken.logs_bloom = bloom;
+        broken.transactions = BlockTransactions::Hashes(vec![H256::repeat_byte(0xee)]);
+        repo.add_block(broken);
+
+        let filter = |to: u64| FilterOptions {
+            from_block: Some(BlockId::Number(U64::one())),
+            to_block: Some(BlockId::Number(U64::from(to))),
+            address: Some(address),
+            topics: None,
+        };
+
+        // 1000 条匹配日志，上限 250：第 3 个区块即超限，不再继续扫描
+        let service = EthereumServiceImpl::new(repo).with_max_log_results(250);
+        let err = service.get_logs(filter(11)).await.unwrap_err();
+        assert!(matches!(err, ServiceError::TooManyResults(250)), "{:?}", err);
+
+        // 恰好达到上限不报错
+        let service = service.with_max_log_results(200);
+        assert_eq!(service.get_logs(filter(2)).await.unwrap().len(), 200);
+    }
+
+    fn export_repo() -> MockEthereumRepository {
+        let repo = MockEthereumRepository::new();
+        for number in 1..=3u64 {
+            let mut block = repo.get_block_by_hash(&H256::zero()).unwrap();
+            block.number = U64::from(number);
+            block.hash = H256::from_low_u64_be(number);
+            block.transactions =
+                BlockTransactions::Full(vec![sample_transaction(H256::from_low_u64_be(0x100 + number))]);
+            repo.add_block(block);
+        }
+        repo
+    }
+
+    #[tokio::test]
+    async fn test_export_blocks_as_ndjson() {
+        let service = EthereumServiceImpl::new(export_repo());
+
+        // 区间末端超出链头，截断到 #3
+        let output: String = service
+            .export_blocks(U64::one(), U64::from(100), false)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        // 每条记录以换行结尾，拼接后仍可逐行解析
+        assert!(output.ends_with('\n'));
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 3);
+        for (line, number) in lines.into_iter().zip(1u64..) {
+            let block: Block = serde_json::from_str(line).unwrap();
+            assert_eq!(block.number, U64::from(number));
+            assert!(matches!(block.transactions, BlockTransactions::Hashes(_)));
+            assert_eq!(block.transactions.hashes(), vec![H256::from_low_u64_be(0x100 + number)]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_blocks_with_full_transactions() {
+        let service = EthereumServiceImpl::new(export_repo());
+
+        let lines: Vec<String> = service
+            .export_blocks(U64::from(2), U64::from(3), true)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert_eq!(lines.len(), 2);
+        for (line, number) in lines.iter().zip(2u64..) {
+            let block: Block = serde_json::from_str(line).unwrap();
+            let BlockTransactions::Full(txs) = block.transactions else {
+                panic!("expected full transactions");
+            };
+            assert_eq!(txs.len(), 1);
+            assert_eq!(txs[0].hash, H256::from_low_u64_be(0x100 + number));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_blocks_reports_missing_block() {
+        let repo = export_repo();
+        repo.blocks.write().unwrap().remove(&U64::from(2));
+        let service = EthereumServiceImpl::new(repo);
+
+        let items: Vec<_> = service.export_blocks(U64::one(), U64::from(3), false).collect().await;
+
+        // 缺失的区块作为错误项返回，不会被静默跳过
+        assert_eq!(items.len(), 3);
+        assert!(items[0].is_ok());
+        assert!(matches!(items[1], Err(ServiceError::BlockNotFound)));
+        assert!(items[2].is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_call_limits_deadline_aborts_execution() {
+        use std::time::Duration;
+
+        let repo = MockEthereumRepository::new();
+        let contract = Address::from_low_u64_be(0x100f);
+        // 死循环：JUMPDEST PUSH1 0 JUMP，不中止则跑满区块 gas 上限
+        repo.set_code(contract, U64::zero(), vec![0x5b, 0x60, 0x00, 0x56]);
+        let limits = CallLimits {
+            max_duration: Duration::from_millis(1),
+            ..CallLimits::default()
+        };
+        let service = EthereumServiceImpl::new(repo).with_call_limits(limits);
+        let request = CallRequest {
+            from: None,
+            to: Some(contract),
+            gas: None,
+            gas_price: None,
+            value: None,
+            data: None,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+        };
+
+        let err = service
+            .call(request.clone(), BlockId::Tag(BlockTag::Latest), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ServiceError::CallLimitExceeded(_)), "{:?}", err);
+        let err = service.estimate_gas(request).await.unwrap_err();
+        assert!(matches!(err, ServiceError::CallLimitExceeded(_)), "{:?}", err);
+    }
+
+    #[tokio::test]
+    async fn test_call_limits_gas_ceiling() {
+        let limits = CallLimits {
+            max_gas: 30_000,
+            ..CallLimits::default()
+        };
+        let service = EthereumServiceImpl::new(MockEthereumRepository::new()).with_call_limits(limits);
+        let request = |gas: u64| CallRequest {
+            from: None,
+            to: Some(Address::from_low_u64_be(0x1234)),
+            gas: Some(U256::from(gas)),
+            gas_price: None,
+            value: None,
+            data: None,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+        };
+        let latest = || BlockId::Tag(BlockTag::Latest);
+
+        // 上限之内的普通调用成功
+        assert_eq!(service.call(request(21_000), latest(), None).await.unwrap(), Vec::<u8>::new());
+
+        // 超出上限：eth_call 与 eth_estimateGas 都被拒绝
+        let err = service.call(request(1_000_000), latest(), None).await.unwrap_err();
+        assert!(matches!(err, ServiceError::CallLimitExceeded(_)), "{:?}", err);
+        let err = service.estimate_gas(request(1_000_000)).await.unwrap_err();
+        assert!(matches!(err, ServiceError::CallLimitExceeded(_)), "{:?}", err);
+    }
+
+    #[tokio::test]
+    async fn test_call_is_gas_free_for_unfunded_sender() {
+        let repo = MockEthereumRepository::new();
+        let sender = Address::from_low_u64_be(0xf00d);
+        repo.set_balance(sender, U256::zero());
+        let service = EthereumServiceImpl::new(repo);
+        let request = CallRequest {
+            from: Some(sender),
+            to: Some(Address::from_low_u64_be(0x1234)),
+            gas: Some(U256::from(21_000)),
+            gas_price: Some(U256::from(1_000_000_000u64)),
+            value: Some(U256::exp10(18)),
+            data: None,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+        };
+
+        // eth_call 跳过余额检查，余额为零的地址也能模拟转账
+        let output = service
+            .call(request.clone(), BlockId::Tag(BlockTag::Latest), None)
+            .await
+            .unwrap();
+        assert!(output.is_empty());
+
+        // eth_estimateGas 仍按真实余额校验
+        assert!(service.estimate_gas(request).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_call_bundle_shares_state_between_calls() {
+        let repo = MockEthereumRepository::new();
+        let counter = Address::from_low_u64_be(0xc0c0);
+        let reverter = Address::from_low_u64_be(0xdead);
+        // 槽 0 加一并返回新值
+        repo.set_code(
+            counter,
+            U64::zero(),
+            vec![
+                0x60, 0x00, 0x54, 0x60, 0x01, 0x01, 0x80, 0x60, 0x00, 0x55, 0x60, 0x00, 0x52,
+                0x60, 0x20, 0x60, 0x00, 0xf3,
+            ],
+        );
+        // PUSH1 0 PUSH1 0 REVERT
+        repo.set_code(reverter, U64::zero(), vec![0x60, 0x00, 0x60, 0x00, 0xfd]);
+        let service = EthereumServiceImpl::new(repo);
+
+        let call = |to| CallRequest {
+            from: Some(Address::from_low_u64_be(0xf00d)),
+            to: Some(to),
+            gas: None,
+            gas_price: None,
+            value: None,
+            data: None,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+        };
+        let requests = vec![call(counter), call(reverter), call(counter)];
+        let run = |isolated| {
+            service.call_bundle(requests.clone(), BlockId::Tag(BlockTag::Latest), isolated)
+        };
+
+        // 回滚的调用不影响后续调用
+        let results = run(false).await.unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].status, U64::one());
+        assert_eq!(results[0].return_data[31], 1);
+        assert_eq!(results[1].status, U64::zero());
+        assert_eq!(results[1].error.as_deref(), Some("execution reverted"));
+        assert_eq!(results[2].return_data[31], 2);
+
+        let results = run(true).await.unwrap();
+        assert_eq!(results[2].return_data[31], 1);
+
+        assert!(service
+            .call_bundle(vec![], BlockId::Tag(BlockTag::Latest), false)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // 超出调用数上限：整体拒绝，恰好达到上限可执行
+        let service = service.with_max_bundle_size(2);
+        let err = service
+            .call_bundle(requests.clone(), BlockId::Tag(BlockTag::Latest), false)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ServiceError::InvalidParams(_)), "{:?}", err);
+        let results = service
+            .call_bundle(requests[..2].to_vec(), BlockId::Tag(BlockTag::Latest), false)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+    }
 }
@@ -16,13 +16,16 @@
 
 use async_trait::async_trait;
 use ethereum_types::{Address, H256, U256, U64};
+use std::collections::BTreeMap;
 use thiserror::Error;
 
 // 导入领域类型
 use crate::domain::command_types::{
-    Block, BlockId, CallRequest, FeeHistory, FilterOptions, Log, SendTransactionRequest,
-    Transaction, TransactionReceipt,
+    Block, BlockId, CallBundleResult, CallRequest, FeeHistory, FilterOptions, Log, SendTransactionRequest,
+    StateOverride, Transaction, TransactionReceipt,
 };
+use crate::domain::tx_types::DynamicFeeTx;
+use crate::domain::typed_data::TypedData;
 
 // CommandHandler 已从 EthereumService 中分离
 // 参见: domain/command_dispatcher.rs 和 infrastructure/service_command_handlers.rs
@@ -77,6 +80,18 @@ pub trait EthereumService: Send + Sync {
         full_tx: bool,
     ) -> Result<Option<Block>, ServiceError>;
 
+    /// 根据区块标识（区块号或 latest / safe / finalized 等标签）获取区块
+    ///
+    /// # 返回
+    /// - `Ok(Some(Block))` - 找到区块
+    /// - `Ok(None)` - 区块不存在，或 safe / finalized 尚未由共识层设置
+    /// - `Err(ServiceError)` - 查询失败
+    async fn get_block_by_id(
+        &self,
+        block: BlockId,
+        full_tx: bool,
+    ) -> Result<Option<Block>, ServiceError>;
+
     /// 根据区块哈希获取区块
     ///
     /// # 参数
@@ -140,6 +155,23 @@ pub trait EthereumService: Send + Sync {
     /// - `Err(ServiceError)` - 查询失败
     async fn get_balance(&self, address: Address, block: BlockId) -> Result<U256, ServiceError>;
 
+    /// 批量获取账户余额（非标准扩展 eth_getBalances）
+    ///
+    /// 各地址并发查询，任一查询失败则整体失败
+    ///
+    /// # 参数
+    /// - `addresses` - 账户地址列表
+    /// - `block` - 区块标识
+    ///
+    /// # 返回
+    /// - `Ok(BTreeMap<Address, U256>)` - 地址 → 余额
+    /// - `Err(ServiceError)` - 查询失败
+    async fn get_balances(
+        &self,
+        addresses: Vec<Address>,
+        block: BlockId,
+    ) -> Result<BTreeMap<Address, U256>, ServiceError>;
+
     /// 获取存储位置的值
     ///
     /// # 参数
@@ -183,6 +215,27 @@ pub trait EthereumService: Send + Sync {
     /// - `Err(ServiceError)` - 查询失败
     async fn get_code(&self, address: Address, block: BlockId) -> Result<Vec<u8>, ServiceError>;
 
+    /// 获取本节点管理的账户列表
+    ///
+    /// # 返回
+    /// - `Ok(Vec<Address>)` - 本地密钥库中的账户地址
+    /// - `Err(ServiceError)` - 查询失败
+    async fn accounts(&self) -> Result<Vec<Address>, ServiceError>;
+
+    /// 获取 devp2p eth 子协议版本（eth_protocolVersion）
+    ///
+    /// # 返回
+    /// - `Ok(U64)` - 协议版本号
+    /// - `Err(ServiceError)` - 查询失败
+    async fn protocol_version(&self) -> Result<U64, ServiceError>;
+
+    /// 获取链 ID（eth_chainId，EIP-695）
+    ///
+    /// # 返回
+    /// - `Ok(U64)` - 链 ID
+    /// - `Err(ServiceError)` - 查询失败
+    async fn chain_id(&self) -> Result<U64, ServiceError>;
+
     // ========================================================================
     // 合约调用和估算方法
     // ========================================================================
@@ -194,11 +247,30 @@ pub trait EthereumService: Send + Sync {
     /// # 参数
     /// - `request` - 调用请求参数
     /// - `block` - 区块标识
+    /// - `state_override` - 仅对本次调用生效的账户状态覆盖
     ///
     /// # 返回
     /// - `Ok(Vec<u8>)` - 调用返回的数据
     /// - `Err(ServiceError)` - 调用失败
-    async fn call(&self, request: CallRequest, block: BlockId) -> Result<Vec<u8>, ServiceError>;
+    async fn call(
+        &self,
+        request: CallRequest,
+        block: BlockId,
+        state_override: Option<StateOverride>,
+    ) -> Result<Vec<u8>, ServiceError>;
+
+    /// 在同一状态快照上依次执行多个只读调用（eth_callBundle）
+    ///
+    /// 只初始化一次目标区块的状态；`isolated` 为 false 时后续调用可见前序调用的
+    /// 状态修改，为 true 时每个调用都从该区块的原始状态开始执行。
+    /// 单个调用回滚或异常不影响其他调用，结果与请求一一对应；
+    /// 调用数超出上限时返回 `InvalidParams`
+    async fn call_bundle(
+        &self,
+        requests: Vec<CallRequest>,
+        block: BlockId,
+        isolated: bool,
+    ) -> Result<Vec<CallBundleResult>, ServiceError>;
 
     /// 估算 Gas 消耗
     ///
@@ -230,10 +302,13 @@ pub trait EthereumService: Send + Sync {
 
     /// 发送交易（返回交易哈希）
     ///
-    /// 支持 Legacy 和 EIP-1559 两种交易类型：
+    /// 由节点使用本地密钥库中 `from` 账户的私钥签名，然后提交到交易池。
+    /// 支持 Legacy 和 EIP-1559 两种请求格式：
     /// - Legacy: 使用 `gas_price` 字段
     /// - EIP-1559: 使用 `max_fee_per_gas` 和 `max_priority_fee_per_gas` 字段
     ///
+    /// `from` 不受本节点管理时返回 `ServiceError::UnknownAccount`
+    ///
     /// # 参数
     /// - `request` - 交易请求参数
     ///
@@ -246,17 +321,34 @@ pub trait EthereumService: Send + Sync {
     /// 发送原始交易（已解码的领域交易对象）
     ///
     /// # 参数
-    /// - `tx` - 已解码和验证签名的领域交易对象
-    /// - `sender` - 交易发送者地址（从签名恢复）
+    /// - `tx` - 已解码的领域交易对象，发送者由其签名恢复
     ///
     /// # 返回
     /// - `Ok(H256)` - 交易哈希
-    /// - `Err(ServiceError)` - 发送失败
-    async fn send_raw_transaction(
+    /// - `Err(ServiceError)` - 发送失败（含签名无法恢复发送者）
+    async fn send_raw_transaction(&self, tx: DynamicFeeTx) -> Result<H256, ServiceError>;
+
+    /// 校验 legacy 交易签名 v 值中的 chain id（EIP-155 重放保护）
+    ///
+    /// EIP-155 之前的签名（v = 27/28）只有在节点允许时才通过
+    async fn validate_legacy_chain_id(&self, v: u64) -> Result<(), ServiceError>;
+
+    /// 对 EIP-712 结构化数据签名（eth_signTypedData_v4）
+    ///
+    /// 使用本地密钥库中 `address` 的私钥对 `typed_data.signing_hash()` 签名
+    ///
+    /// # 参数
+    /// - `address` - 签名账户
+    /// - `typed_data` - 结构化数据
+    ///
+    /// # 返回
+    /// - `Ok(Vec<u8>)` - 65 字节签名 r ‖ s ‖ v（v 为 27/28）
+    /// - `Err(ServiceError)` - 数据无效或账户不受本节点管理
+    async fn sign_typed_data(
         &self,
-        tx: crate::domain::tx_types::DynamicFeeTx,
-        sender: Address,
-    ) -> Result<H256, ServiceError>;
+        address: Address,
+        typed_data: TypedData,
+    ) -> Result<Vec<u8>, ServiceError>;
 
     // ========================================================================
     // EIP-1559 费用相关方法
@@ -289,6 +381,40 @@ pub trait EthereumService: Send + Sync {
     /// - `Ok(U256)` - 建议的最大优先费用（单位：wei）
     /// - `Err(ServiceError)` - 查询失败
     async fn max_priority_fee_per_gas(&self) -> Result<U256, ServiceError>;
+
+    /// 获取当前 blob base fee（EIP-4844）
+    ///
+    /// 由链头区块的 `excess_blob_gas` 按 fake-exponential 公式计算；
+    /// Cancun 之前的区块视为超额为 0，即最低费用 1 wei
+    ///
+    /// # 返回
+    /// - `Ok(U256)` - blob base fee（单位：wei）
+    /// - `Err(ServiceError)` - 查询失败
+    async fn blob_base_fee(&self) -> Result<U256, ServiceError>;
+
+    // ========================================================================
+    // 调试方法
+    // ========================================================================
+
+    /// 获取已签名交易（debug_getRawTransaction）
+    ///
+    /// 先查交易池，再查已上链交易
+    ///
+    /// # 返回
+    /// - `Ok(Some(DynamicFeeTx))` - 找到交易
+    /// - `Ok(None)` - 交易不存在
+    /// - `Err(ServiceError)` - 交易无法还原为可编码的形式
+    async fn get_signed_transaction(&self, hash: H256) -> Result<Option<DynamicFeeTx>, ServiceError>;
+
+    /// 获取区块内所有交易收据，按交易顺序排列（debug_getRawReceipts）
+    ///
+    /// # 返回
+    /// - `Ok(Some(receipts))` - 区块存在
+    /// - `Ok(None)` - 区块不存在
+    async fn get_block_receipts(
+        &self,
+        block: BlockId,
+    ) -> Result<Option<Vec<TransactionReceipt>>, ServiceError>;
 }
 
 // ============================================================================
@@ -308,10 +434,42 @@ pub enum ServiceError {
     #[error("交易未找到")]
     TransactionNotFound,
 
+    /// 按哈希指定的区块不在主链上（EIP-1898 requireCanonical）
+    #[error("区块 {0:?} 不在主链上")]
+    NonCanonicalBlock(H256),
+
+    /// 账户不受本节点管理（无法代为签名）
+    #[error("未知账户: {0:?}")]
+    UnknownAccount(Address),
+
+    /// 请求参数无效
+    #[error("无效参数: {0}")]
+    InvalidParams(String),
+
     /// 交易验证错误
     #[error("交易验证失败: {0}")]
     ValidationError(String),
 
+    /// 执行被 REVERT 回滚（reason 为解析出的 `Error(string)` 原因）
+    #[error("execution reverted{}", .reason.as_ref().map(|r| format!(": {}", r)).unwrap_or_default())]
+    ExecutionReverted { reason: Option<String>, data: Vec<u8> },
+
+    /// 执行失败（gas 超出上限、余额不足等）
+    #[error("执行失败: {0}")]
+    ExecutionFailed(String),
+
+    /// 发送者提交过于频繁
+    #[error("{0}")]
+    RateLimited(String),
+
+    /// 模拟执行超出 gas 或时间上限
+    #[error("{0}")]
+    CallLimitExceeded(String),
+
+    /// 查询结果条数超出上限（如 eth_getLogs）
+    #[error("query returned more than {0} results")]
+    TooManyResults(usize),
+
     /// 内部错误（包含详细错误信息）
     #[error("内部错误: {0}")]
     InternalError(String),
@@ -0,0 +1,63 @@
+//! 分叉选择权重存储
+//!
+//! 记录每个区块的分叉选择权重（如累计证明权重），用于在多个分支之间选出链头：
+//! 权重严格更大的分支胜出；权重相同时选哈希较小的区块，保证所有节点得到相同结果。
+//! 未记录权重的区块视为权重 0。
+
+use ethereum_types::{H256, U256};
+use std::collections::HashMap;
+
+/// 分叉选择权重存储
+#[derive(Debug, Clone, Default)]
+pub struct ForkChoiceStore {
+    /// 区块哈希 -> 权重
+    weights: HashMap<H256, U256>,
+}
+
+impl ForkChoiceStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录区块权重（覆盖旧值）
+    pub fn set_weight(&mut self, hash: H256, weight: U256) {
+        self.weights.insert(hash, weight);
+    }
+
+    /// 区块权重，未记录时为 0
+    pub fn weight(&self, hash: &H256) -> U256 {
+        self.weights.get(hash).copied().unwrap_or_default()
+    }
+
+    /// `candidate` 是否应取代 `current` 成为链头
+    ///
+    /// 权重严格更大时取代；权重相同时哈希较小者胜出
+    pub fn is_preferred(&self, candidate: &H256, current: &H256) -> bool {
+        match self.weight(candidate).cmp(&self.weight(current)) {
+            std::cmp::Ordering::Greater => true,
+            std::cmp::Ordering::Less => false,
+            std::cmp::Ordering::Equal => candidate < current,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heavier_wins_and_ties_break_by_hash() {
+        let low = H256::repeat_byte(0x01);
+        let high = H256::repeat_byte(0x02);
+        let mut store = ForkChoiceStore::new();
+
+        // 都未记录：权重相同，哈希小者胜出
+        assert!(store.is_preferred(&low, &high));
+        assert!(!store.is_preferred(&high, &low));
+        assert!(!store.is_preferred(&low, &low));
+
+        store.set_weight(high, U256::from(10));
+        assert!(store.is_preferred(&high, &low));
+        assert_eq!(store.weight(&low), U256::zero());
+    }
+}
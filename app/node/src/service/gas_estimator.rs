@@ -0,0 +1,277 @@
+//! eth_estimateGas 二分查找
+//!
+//! 参考: geth eth/gasestimator/gasestimator.go
+//!
+//! 流程：
+//! 1. 上界 = min(请求 gas, 区块 gas 上限, 余额可负担的 gas)
+//! 2. 以上界执行一次，失败则直接报错（回滚时带回原因）
+//! 3. 乐观估计：(gas_used + 2300) * 64 / 63，覆盖子调用按 63/64 规则转发 gas 的情况
+//! 4. 在 [gas_used - 1, 上界] 内二分查找最小可成功的 gas
+
+use crate::domain::command_types::CallRequest;
+use crate::service::call_executor_trait::{CallEnv, CallExecutor, CallOutcome};
+use crate::service::ethereum_service_trait::ServiceError;
+use ethereum_types::U256;
+
+/// CALL 携带 value 时附送的 gas 津贴
+const CALL_STIPEND: u64 = 2_300;
+
+/// Solidity `Error(string)` 的函数选择器
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// 估算调用所需的最小 gas
+pub fn estimate_gas<E: CallExecutor>(
+    executor: &E,
+    call: &CallRequest,
+    env: &CallEnv,
+) -> Result<u64, ServiceError> {
+    let data = call.data.as_deref().unwrap_or_default();
+    let floor = env.gas_schedule.intrinsic.intrinsic_gas(data, call.to.is_none(), &[]);
+
+    let mut hi = env.block_gas_limit;
+    if let Some(gas) = call.gas {
+        if gas >= U256::from(floor) && gas < U256::from(hi) {
+            hi = gas.as_u64();
+        }
+    }
+
+    // 指定了 gas 价格时，上界受余额限制
+    let fee_cap = call.max_fee_per_gas.or(call.gas_price).unwrap_or_default();
+    if !fee_cap.is_zero() {
+        let available = env
+            .caller_balance
+            .checked_sub(call.value.unwrap_or_default())
+            .ok_or_else(|| ServiceError::ExecutionFailed("insufficient funds for transfer".to_string()))?;
+        let allowance = available / fee_cap;
+        if allowance < U256::from(hi) {
+            hi = allowance.as_u64();
+        }
+    }
+    if hi < floor {
+        return Err(ServiceError::ExecutionFailed(format!(
+            "gas required exceeds allowance ({})",
+            hi
+        )));
+    }
+
+    let gas_used = match executor.execute(call, hi, env) {
+        CallOutcome::Success { gas_used, .. } => gas_used,
+        CallOutcome::Revert { output, .. } => return Err(revert_error(output)),
+        CallOutcome::Halt { .. } => {
+            return Err(ServiceError::ExecutionFailed(format!(
+                "gas required exceeds allowance ({})",
+                hi
+            )))
+        }
+        CallOutcome::Invalid(msg) => return Err(ServiceError::ExecutionFailed(msg)),
+        CallOutcome::TimedOut => return Err(timeout_error()),
+    };
+
+    // 执行至少需要 gas_used（退款前的消耗只多不少）
+    let mut lo = gas_used.max(floor) - 1;
+
+    let optimistic = (gas_used + CALL_STIPEND) * 64 / 63;
+    if optimistic < hi {
+        if succeeds(executor, call, optimistic, env)? {
+            hi = optimistic;
+        } else {
+            lo = optimistic;
+        }
+    }
+
+    while lo + 1 < hi {
+        let mid = lo + (hi - lo) / 2;
+        if succeeds(executor, call, mid, env)? {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+
+    Ok(hi)
+}
+
+/// 以指定 gas 试执行；任一次执行超时即中止整个估算
+fn succeeds<E: CallExecutor>(
+    executor: &E,
+    call: &CallRequest,
+    gas_limit: u64,
+    env: &CallEnv,
+) -> Result<bool, ServiceError> {
+    match executor.execute(call, gas_limit, env) {
+        CallOutcome::TimedOut => Err(timeout_error()),
+        outcome => Ok(outcome.is_success()),
+    }
+}
+
+/// 模拟执行超过截止时间
+pub fn timeout_error() -> ServiceError {
+    ServiceError::CallLimitExceeded("模拟执行超时".to_string())
+}
+
+/// 将回滚返回数据转换为错误，能解析 `Error(string)` 时附带原因
+fn revert_error(output: Vec<u8>) -> ServiceError {
+    ServiceError::ExecutionReverted {
+        reason: decode_revert_reason(&output),
+        data: output,
+    }
+}
+
+/// 解析 ABI 编码的 `Error(string)` 回滚原因
+pub fn decode_revert_reason(output: &[u8]) -> Option<String> {
+    let payload = output.strip_prefix(&ERROR_SELECTOR)?;
+    let offset = U256::from_big_endian(payload.get(..32)?);
+    let offset = usize::try_from(offset).ok()?;
+    let len_end = offset.checked_add(32)?;
+    let len = U256::from_big_endian(payload.get(offset..len_end)?);
+    let len = usize::try_from(len).ok()?;
+    let bytes = payload.get(len_end..len_end.checked_add(len)?)?;
+    String::from_utf8(bytes.to_vec()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::mock_repository::MockEthereumRepository;
+    use crate::infrastructure::revm_executor::RevmCallExecutor;
+    use crate::service::call_executor_trait::GasSchedule;
+    use ethereum_types::{Address, U64};
+
+    fn env() -> CallEnv {
+        CallEnv {
+            block_number: U64::zero(),
+            block_gas_limit: 30_000_000,
+            caller_balance: U256::from(1_000_000_000_000_000_000u64),
+            state_override: Default::default(),
+            gas_free: false,
+            gas_schedule: GasSchedule::default(),
+            deadline: None,
+        }
+    }
+
+    fn call_to(to: Address) -> CallRequest {
+        CallRequest {
+            from: Some(Address::from_low_u64_be(1)),
+            to: Some(to),
+            gas: None,
+            gas_price: None,
+            value: None,
+            data: None,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+        }
+    }
+
+    /// ABI 编码 Error(reason)
+    fn encode_revert_reason(reason: &str) -> Vec<u8> {
+        let mut out = ERROR_SELECTOR.to_vec();
+        let mut word = [0u8; 32];
+        word[31] = 0x20;
+        out.extend_from_slice(&word);
+        word[31] = reason.len() as u8;
+        out.extend_from_slice(&word);
+        let mut data = reason.as_bytes().to_vec();
+        data.resize(reason.len().div_ceil(32) * 32, 0);
+        out.extend_from_slice(&data);
+        out
+    }
+
+    /// 生成以 `Error(reason)` 回滚的合约代码
+    fn reverting_code(reason: &str) -> Vec<u8> {
+        let data = encode_revert_reason(reason);
+        let mut code = Vec::new();
+        for (i, chunk) in data.chunks(32).enumerate() {
+            let mut word = [0u8; 32];
+            word[..chunk.len()].copy_from_slice(chunk);
+            code.push(0x7f); // PUSH32
+            code.extend_from_slice(&word);
+            code.extend_from_slice(&[0x60, (i * 32) as u8, 0x52]); // PUSH1 offset MSTORE
+        }
+        code.extend_from_slice(&[0x60, data.len() as u8, 0x60, 0x00, 0xfd]); // REVERT
+        code
+    }
+
+    #[test]
+    fn test_estimate_simple_transfer() {
+        let executor = RevmCallExecutor::new(MockEthereumRepository::new());
+        let gas = estimate_gas(&executor, &call_to(Address::from_low_u64_be(0x1234)), &env()).unwrap();
+        assert_eq!(gas, 21_000);
+    }
+
+    #[test]
+    fn test_estimate_contract_call_is_minimal() {
+        let repo = MockEthereumRepository::new();
+        let contract = Address::from_low_u64_be(0xc0de);
+        // PUSH1 1 PUSH1 0 SSTORE STOP
+        repo.set_code(contract, U64::zero(), vec![0x60, 0x01, 0x60, 0x00, 0x55, 0x00]);
+        let executor = RevmCallExecutor::new(repo);
+
+        let call = call_to(contract);
+        let gas = estimate_gas(&executor, &call, &env()).unwrap();
+        assert!(gas > 21_000);
+        assert!(executor.execute(&call, gas, &env()).is_success());
+        assert!(!executor.execute(&call, gas - 1, &env()).is_success());
+    }
+
+    #[test]
+    fn test_estimate_accounts_for_sub_call_forwarding() {
+        let repo = MockEthereumRepository::new();
+        let callee = Address::from_low_u64_be(0xca11ee);
+        let caller = Address::from_low_u64_be(0xca11e4);
+        // callee: 写入存储槽 (约 22100 gas)
+        repo.set_code(callee, U64::zero(), vec![0x60, 0x01, 0x60, 0x00, 0x55, 0x00]);
+        // caller: CALL(gas, callee, 0, 0, 0, 0, 0)，失败时 REVERT
+        let mut code = vec![0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x73];
+        code.extend_from_slice(callee.as_bytes());
+        code.extend_from_slice(&[0x5a, 0xf1]); // GAS CALL
+        code.extend_from_slice(&[0x60, 0x28, 0x57, 0x60, 0x00, 0x80, 0xfd, 0x5b, 0x00]);
+        repo.set_code(caller, U64::zero(), code);
+        let executor = RevmCallExecutor::new(repo);
+
+        let call = call_to(caller);
+        let gas = estimate_gas(&executor, &call, &env()).unwrap();
+        assert!(executor.execute(&call, gas, &env()).is_success());
+        assert!(!executor.execute(&call, gas - 1, &env()).is_success());
+    }
+
+    #[test]
+    fn test_estimate_always_reverting_call() {
+        let repo = MockEthereumRepository::new();
+        let contract = Address::from_low_u64_be(0xdead);
+        repo.set_code(contract, U64::zero(), reverting_code("not allowed"));
+        let executor = RevmCallExecutor::new(repo);
+
+        let err = estimate_gas(&executor, &call_to(contract), &env()).unwrap_err();
+        match err {
+            ServiceError::ExecutionReverted { reason, data } => {
+                assert_eq!(reason.as_deref(), Some("not allowed"));
+                assert_eq!(data, encode_revert_reason("not allowed"));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_estimate_capped_by_balance() {
+        let executor = RevmCallExecutor::new(MockEthereumRepository::new());
+        let mut call = call_to(Address::from_low_u64_be(0x1234));
+        call.gas_price = Some(U256::from(1_000_000_000u64));
+        let env = CallEnv {
+            caller_balance: U256::from(20_000u64 * 1_000_000_000),
+            ..env()
+        };
+
+        let err = estimate_gas(&executor, &call, &env).unwrap_err();
+        assert!(matches!(err, ServiceError::ExecutionFailed(msg) if msg.contains("allowance")));
+    }
+
+    #[test]
+    fn test_decode_revert_reason() {
+        assert_eq!(
+            decode_revert_reason(&encode_revert_reason("boom")).as_deref(),
+            Some("boom")
+        );
+        assert_eq!(decode_revert_reason(&[]), None);
+        assert_eq!(decode_revert_reason(&ERROR_SELECTOR), None);
+    }
+}
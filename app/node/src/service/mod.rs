@@ -7,4 +7,11 @@ pub mod transaction_validator_trait;
 pub mod build_block_trait;
 pub mod build_block_impl;
 pub mod block_production_service;
-mod blockchain_impl;
+pub mod blockchain_impl;
+pub mod clock;
+pub mod call_executor_trait;
+pub mod gas_estimator;
+pub mod slot_clock;
+pub mod sender_rate_limiter;
+pub mod fork_choice;
+pub mod production_scheduler;
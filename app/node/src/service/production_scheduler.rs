@@ -0,0 +1,186 @@
+//! 出块调度器 - 按 slot 驱动区块生产
+//!
+//! 每个 slot 开始时查询出块职责：轮到本节点时以当前链头为父区块、以 slot 起始时间为
+//! 时间戳构建环境，调用 `BlockProductionService::produce_block`；不是本节点的 slot 直接跳过。
+//! 每个 slot 至多出块一次。时间取自 `SlotClock`，测试注入 `MockClock` 即可确定性驱动。
+
+use crate::domain::block_types::Block;
+use crate::domain::slot_types::Slot;
+use crate::service::block_production_service::{BlockProductionError, BlockProductionService};
+use crate::service::clock::Clock;
+use crate::service::slot_clock::SlotClock;
+use ethereum_types::{Address, U64};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// 出块职责来源（如信标节点的 proposer duties）
+pub trait ProposerDuties: Send + Sync {
+    /// 本节点是否为 `slot` 的出块者
+    fn is_proposer(&self, slot: Slot) -> bool;
+}
+
+/// 出块调度器
+pub struct ProductionScheduler<C: Clock> {
+    production: Arc<BlockProductionService>,
+    slot_clock: SlotClock<C>,
+    /// 出块收益地址
+    fee_recipient: Address,
+    /// 最近一次成功出块的 slot（防止同一 slot 重复出块）
+    last_produced: Mutex<Option<Slot>>,
+}
+
+impl<C: Clock> ProductionScheduler<C> {
+    pub fn new(production: Arc<BlockProductionService>, slot_clock: SlotClock<C>) -> Self {
+        Self {
+            production,
+            slot_clock,
+            fee_recipient: Address::zero(),
+            last_produced: Mutex::new(None),
+        }
+    }
+
+    /// 设置出块收益地址
+    pub fn with_fee_recipient(mut self, fee_recipient: Address) -> Self {
+        self.fee_recipient = fee_recipient;
+        self
+    }
+
+    /// 持续运行：每个 slot 开始时检查一次出块职责
+    ///
+    /// 出块失败只记录日志，下一个 slot 继续调度
+    pub async fn run<D: ProposerDuties>(&self, duties: D) {
+        loop {
+            if let Err(e) = self.tick(&duties).await {
+                tracing::warn!(error = %e, "出块失败");
+            }
+            let wait = self.slot_clock.seconds_to_next_slot().max(1);
+            tokio::time::sleep(Duration::from_secs(wait)).await;
+        }
+    }
+
+    /// 检查当前 slot：轮到本节点且本 slot 尚未出块时出块
+    ///
+    /// 返回产出的区块；创世前、非本节点 slot 或本 slot 已出块时返回 `Ok(None)`
+    pub async fn tick<D: ProposerDuties>(
+        &self,
+        duties: &D,
+    ) -> Result<Option<Block>, BlockProductionError> {
+        let Some(slot) = self.slot_clock.current_slot() else {
+            return Ok(None);
+        };
+        if *self.last_produced.lock().unwrap() == Some(slot) || !duties.is_proposer(slot) {
+            return Ok(None);
+        }
+
+        let env = self
+            .production
+            .environment_at(U64::from(self.slot_clock.slot_start_time(slot)), self.fee_recipient)
+            .await?;
+        let block = self.production.produce_block(env).await?;
+        *self.last_produced.lock().unwrap() = Some(slot);
+        tracing::info!(%slot, block_number = %block.number(), "按 slot 出块");
+        Ok(Some(block))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::slot_types::{ChainSpec, GenesisInfo};
+    use crate::infrastructure::clock_impl::MockClock;
+    use crate::infrastructure::transaction_repo_impl::{TxPoolConfig, TxPoolImpl};
+    use crate::service::block_production_service::BlockBroadcaster;
+    use crate::service::blockchain_impl::BlockChainImpl;
+    use crate::service::build_block_impl::BuildBlockService;
+    use crate::service::repo::block_repo::tests::make_block;
+    use crate::service::repo::block_repo::InMemoryBlockRepository;
+    use async_trait::async_trait;
+    use ethereum_types::H256;
+
+    /// 与 `make_block` 夹具的创世时间戳一致
+    const GENESIS_TIME: u64 = 1_700_000_000;
+
+    /// 记录每次广播的区块（区块号, 时间戳）
+    #[derive(Default)]
+    struct RecordingBroadcaster {
+        blocks: Mutex<Vec<(U64, U64)>>,
+    }
+
+    #[async_trait]
+    impl BlockBroadcaster for RecordingBroadcaster {
+        async fn broadcast_block(&self, block: &Block) -> Result<(), String> {
+            self.blocks
+                .lock()
+                .unwrap()
+                .push((block.number(), block.header.timestamp));
+            Ok(())
+        }
+
+        async fn broadcast_to_peer(&self, _block: &Block, _peer_id: &str) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    /// 只负责单个 slot 的出块职责
+    struct SingleSlot(Slot);
+
+    impl ProposerDuties for SingleSlot {
+        fn is_proposer(&self, slot: Slot) -> bool {
+            slot == self.0
+        }
+    }
+
+    #[tokio::test]
+    async fn test_produces_only_at_assigned_slot() {
+        let tx_pool = Arc::new(TxPoolImpl::new(TxPoolConfig::default()));
+        let builder = Arc::new(BuildBlockService::new(tx_pool, Some(30_000_000)));
+        let blockchain = Arc::new(
+            BlockChainImpl::new_with_genesis(
+                Arc::new(InMemoryBlockRepository::new()),
+                make_block(0, H256::zero()),
+            )
+            .await
+            .unwrap(),
+        );
+        let broadcaster = Arc::new(RecordingBroadcaster::default());
+        let production = Arc::new(BlockProductionService::new(
+            builder,
+            blockchain,
+            broadcaster.clone(),
+        ));
+
+        let clock = MockClock::new(GENESIS_TIME);
+        let slot_clock = SlotClock::new(
+            GenesisInfo { genesis_time: GENESIS_TIME },
+            ChainSpec::mainnet(),
+            clock.clone(),
+        );
+        let scheduler = ProductionScheduler::new(production, slot_clock);
+        let duties = SingleSlot(Slot(3));
+        let slot_3_start = GENESIS_TIME + 3 * 12;
+
+        // slot 0..2 不是本节点出块，直到 slot 3 开始前一秒都不出块
+        for _ in 0..(3 * 12 - 1) {
+            assert!(scheduler.tick(&duties).await.unwrap().is_none());
+            clock.advance(Duration::from_secs(1));
+        }
+        assert!(scheduler.tick(&duties).await.unwrap().is_none());
+        assert!(broadcaster.blocks.lock().unwrap().is_empty());
+
+        // slot 3 开始：以链头为父区块出块，时间戳为 slot 起始时间
+        clock.advance(Duration::from_secs(1));
+        let block = scheduler.tick(&duties).await.unwrap().expect("slot 3 应出块");
+        assert_eq!(block.number(), U64::one());
+        assert_eq!(
+            *broadcaster.blocks.lock().unwrap(),
+            vec![(U64::one(), U64::from(slot_3_start))]
+        );
+
+        // 同一 slot 内不重复出块，之后的 slot 也不再出块
+        clock.advance(Duration::from_secs(6));
+        assert!(scheduler.tick(&duties).await.unwrap().is_none());
+        clock.advance(Duration::from_secs(12));
+        assert!(scheduler.tick(&duties).await.unwrap().is_none());
+        assert_eq!(broadcaster.blocks.lock().unwrap().len(), 1);
+    }
+}
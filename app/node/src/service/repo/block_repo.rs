@@ -1,19 +1,20 @@
-/// 区块持久化接口 - 参考 geth/core/blockchain.go
-///
-/// 设计参考：
-/// - geth/core/blockchain.go: BlockChain 主要接口
-/// - geth/core/rawdb: 底层数据库操作
-///
-/// Clean Architecture 分层：
-/// - BlockRepository trait: 领域层接口（底层持久化）
-/// - BlockChain trait: 用例层接口（链状态管理）
-/// - 具体实现: 基础设施层
+//! 区块持久化接口 - 参考 geth/core/blockchain.go
+//!
+//! 设计参考：
+//! - geth/core/blockchain.go: BlockChain 主要接口
+//! - geth/core/rawdb: 底层数据库操作
+//!
+//! Clean Architecture 分层：
+//! - BlockRepository trait: 领域层接口（底层持久化）
+//! - BlockChain trait: 用例层接口（链状态管理）
+//! - 具体实现: 基础设施层
 
-use crate::domain::block_types::{Block, BlockValidationError};
+use crate::domain::block_types::{Block, IndexedTransaction};
 use crate::domain::receipt_types::TransactionReceipt;
 use async_trait::async_trait;
 use ethereum_types::{H256, U256, U64};
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 
 /// 区块持久化错误
 #[derive(Debug, Clone, PartialEq)]
@@ -62,7 +63,9 @@ pub trait BlockRepository: Send + Sync {
     /// 1. 写入区块头
     /// 2. 写入区块体（交易列表）
     /// 3. 写入收据
-    /// 4. 写入区块号->哈希映射
+    /// 4. 写入哈希索引（按哈希查询为 O(1)）
+    ///
+    /// 注意: 规范链的区块号->哈希映射与交易查找索引由 set_canonical_hash 单独维护
     async fn save_block(
         &self,
         block: &Block,
@@ -80,6 +83,14 @@ pub trait BlockRepository: Send + Sync {
     /// 参考: geth rawdb.ReadBlockByNumber
     async fn get_block_by_number(&self, number: U64) -> Result<Option<Block>, BlockRepositoryError>;
 
+    /// 根据交易哈希获取规范链上的交易及其位置（区块哈希、区块号、交易索引）
+    ///
+    /// 参考: geth rawdb.ReadTransaction（经交易查找索引定位区块）
+    async fn get_transaction_by_hash(
+        &self,
+        hash: &H256,
+    ) -> Result<Option<IndexedTransaction>, BlockRepositoryError>;
+
     /// 根据哈希获取收据
     ///
     /// 参考: geth rawdb.ReadReceipts
@@ -98,38 +109,106 @@ pub trait BlockRepository: Send + Sync {
     /// 参考: geth rawdb.ReadCanonicalHash
     async fn get_canonical_hash(&self, number: U64) -> Result<Option<H256>, BlockRepositoryError>;
 
-    /// 设置规范链的区块号->哈希映射
+    /// 设置规范链的区块号->哈希映射，同时为该区块写入交易查找索引
     ///
-    /// 参考: geth rawdb.WriteCanonicalHash
+    /// 参考: geth rawdb.WriteCanonicalHash + WriteTxLookupEntries
     async fn set_canonical_hash(&self, number: U64, hash: H256) -> Result<(), BlockRepositoryError>;
 
-    /// 删除规范链的区块号映射（用于链重组）
+    /// 删除规范链的区块号映射（用于链重组），同时删除该区块的交易查找索引
     ///
-    /// 参考: geth rawdb.DeleteCanonicalHash
+    /// 参考: geth rawdb.DeleteCanonicalHash + DeleteTxLookupEntries
     async fn delete_canonical_hash(&self, number: U64) -> Result<(), BlockRepositoryError>;
-}
 
+    /// 链重组：写入新分支的规范映射，并删除 `head` 之上的规范映射
+    ///
+    /// 整体生效：分支中有未保存的区块时不做任何修改，读者也不会看到新旧分支混合的规范链
+    ///
+    /// 参考: geth BlockChain.reorg（writeHeadBlock 前批量写入 canonical hash）
+    async fn reorg_canonical_chain(
+        &self,
+        branch: &[(U64, H256)],
+        head: U64,
+    ) -> Result<(), BlockRepositoryError>;
+}
 
 /// 内存版区块存储（用于测试和单机版）
 ///
-/// 使用 Arc + Mutex 实现线程安全
+/// 使用 Arc + RwLock 实现线程安全，区块以哈希为主键存储，
+/// 按区块号查询经由规范链映射 (number -> hash) 完成，两种查询均为 O(1)
+#[derive(Clone, Default)]
 pub struct InMemoryBlockRepository {
-    // TODO: 实现内存版本
-    // - blocks: HashMap<H256, Block>
-    // - receipts: HashMap<H256, Vec<TransactionReceipt>>
-    // - block_numbers: HashMap<U64, H256>
-    // - total_difficulties: HashMap<H256, U256>
-    // - current_head: AtomicPtr<H256>
+    state: Arc<RwLock<InMemoryBlockState>>,
+}
+
+/// 内存存储的内部状态
+#[derive(Default)]
+struct InMemoryBlockState {
+    /// 区块哈希 -> 区块
+    blocks: HashMap<H256, Block>,
+    /// 区块哈希 -> 收据列表
+    receipts: HashMap<H256, Vec<TransactionReceipt>>,
+    /// 规范链: 区块号 -> 区块哈希
+    canonical_hashes: HashMap<U64, H256>,
+    /// 区块哈希 -> 总难度
+    total_difficulties: HashMap<H256, U256>,
+    /// 交易查找索引: 交易哈希 -> (区块号, 交易索引)，只覆盖规范链区块
+    ///
+    /// 参考: geth rawdb.WriteTxLookupEntries
+    tx_lookup: HashMap<H256, (U64, U64)>,
+}
+
+impl InMemoryBlockState {
+    /// 把区块写入规范链，替换同高度的旧区块并同步交易查找索引
+    fn insert_canonical(&mut self, number: U64, hash: H256) {
+        if let Some(old) = self.canonical_hashes.insert(number, hash) {
+            if old == hash {
+                return;
+            }
+            self.unindex_transactions(&old);
+        }
+        if let Some(block) = self.blocks.get(&hash) {
+            for (index, tx) in block.transactions.iter().enumerate() {
+                self.tx_lookup.insert(tx.hash(), (number, U64::from(index)));
+            }
+        }
+    }
+
+    /// 把区块移出规范链，并删除其交易查找索引
+    fn remove_canonical(&mut self, number: U64) {
+        if let Some(old) = self.canonical_hashes.remove(&number) {
+            self.unindex_transactions(&old);
+        }
+    }
+
+    /// 删除区块中交易的查找索引
+    fn unindex_transactions(&mut self, hash: &H256) {
+        if let Some(block) = self.blocks.get(hash) {
+            for tx in &block.transactions {
+                self.tx_lookup.remove(&tx.hash());
+            }
+        }
+    }
 }
 
 impl InMemoryBlockRepository {
     pub fn new() -> Self {
-        Self {}
+        Self::default()
     }
 
-    pub fn with_genesis(_genesis: Block) -> Self {
-        // TODO: 初始化创世区块
-        Self {}
+    /// 创建带创世区块的存储（创世区块写入规范链 #0）
+    pub fn with_genesis(genesis: Block) -> Self {
+        let repo = Self::new();
+        {
+            let mut state = repo.state.write().unwrap();
+            let (number, hash) = (genesis.number(), genesis.hash());
+            state
+                .total_difficulties
+                .insert(hash, genesis.header.difficulty);
+            state.receipts.insert(hash, Vec::new());
+            state.blocks.insert(hash, genesis);
+            state.insert_canonical(number, hash);
+        }
+        repo
     }
 }
 
@@ -137,50 +216,130 @@ impl InMemoryBlockRepository {
 impl BlockRepository for InMemoryBlockRepository {
     async fn save_block(
         &self,
-        _block: &Block,
-        _receipts: &[TransactionReceipt],
-        _total_difficulty: U256,
+        block: &Block,
+        receipts: &[TransactionReceipt],
+        total_difficulty: U256,
     ) -> Result<(), BlockRepositoryError> {
-        todo!("实现内存版本的区块保存")
+        let hash = block.hash();
+        let mut state = self.state.write().unwrap();
+        if state.blocks.contains_key(&hash) {
+            return Err(BlockRepositoryError::BlockAlreadyExists { hash });
+        }
+        state.blocks.insert(hash, block.clone());
+        state.receipts.insert(hash, receipts.to_vec());
+        state.total_difficulties.insert(hash, total_difficulty);
+        Ok(())
     }
 
-    async fn get_block_by_hash(&self, _hash: &H256) -> Result<Option<Block>, BlockRepositoryError> {
-        todo!("实现内存版本的区块查询")
+    async fn get_block_by_hash(&self, hash: &H256) -> Result<Option<Block>, BlockRepositoryError> {
+        Ok(self.state.read().unwrap().blocks.get(hash).cloned())
     }
 
-    async fn get_block_by_number(&self, _number: U64) -> Result<Option<Block>, BlockRepositoryError> {
-        todo!("实现内存版本的区块查询")
+    async fn get_block_by_number(&self, number: U64) -> Result<Option<Block>, BlockRepositoryError> {
+        let state = self.state.read().unwrap();
+        Ok(state
+            .canonical_hashes
+            .get(&number)
+            .and_then(|hash| state.blocks.get(hash))
+            .cloned())
+    }
+
+    async fn get_transaction_by_hash(
+        &self,
+        hash: &H256,
+    ) -> Result<Option<IndexedTransaction>, BlockRepositoryError> {
+        let Some((number, index)) = self.state.read().unwrap().tx_lookup.get(hash).copied() else {
+            return Ok(None);
+        };
+        let Some(block) = self.get_block_by_number(number).await? else {
+            return Ok(None);
+        };
+
+        // 索引随规范链维护，仍以交易哈希确认位置
+        Ok(block
+            .transactions
+            .get(index.as_usize())
+            .filter(|tx| tx.hash() == *hash)
+            .map(|tx| IndexedTransaction {
+                transaction: tx.clone(),
+                block_hash: block.hash(),
+                block_number: number,
+                transaction_index: index,
+            }))
     }
 
     async fn get_receipts_by_hash(
         &self,
-        _hash: &H256,
+        hash: &H256,
     ) -> Result<Vec<TransactionReceipt>, BlockRepositoryError> {
-        todo!("实现内存版本的收据查询")
+        self.state
+            .read()
+            .unwrap()
+            .receipts
+            .get(hash)
+            .cloned()
+            .ok_or(BlockRepositoryError::BlockNotFound { hash: *hash })
     }
 
-    async fn get_total_difficulty(&self, _hash: &H256) -> Result<Option<U256>, BlockRepositoryError> {
-        todo!("实现内存版本的难度查询")
+    async fn get_total_difficulty(&self, hash: &H256) -> Result<Option<U256>, BlockRepositoryError> {
+        Ok(self.state.read().unwrap().total_difficulties.get(hash).copied())
     }
 
-    async fn get_canonical_hash(&self, _number: U64) -> Result<Option<H256>, BlockRepositoryError> {
-        todo!("实现内存版本的规范哈希查询")
+    async fn get_canonical_hash(&self, number: U64) -> Result<Option<H256>, BlockRepositoryError> {
+        Ok(self.state.read().unwrap().canonical_hashes.get(&number).copied())
     }
 
-    async fn set_canonical_hash(&self, _number: U64, _hash: H256) -> Result<(), BlockRepositoryError> {
-        todo!("实现内存版本的规范哈希设置")
+    async fn set_canonical_hash(&self, number: U64, hash: H256) -> Result<(), BlockRepositoryError> {
+        let mut state = self.state.write().unwrap();
+        if !state.blocks.contains_key(&hash) {
+            return Err(BlockRepositoryError::BlockNotFound { hash });
+        }
+        state.insert_canonical(number, hash);
+        Ok(())
     }
 
-    async fn delete_canonical_hash(&self, _number: U64) -> Result<(), BlockRepositoryError> {
-        todo!("实现内存版本的规范哈希删除")
+    async fn delete_canonical_hash(&self, number: U64) -> Result<(), BlockRepositoryError> {
+        self.state.write().unwrap().remove_canonical(number);
+        Ok(())
     }
-}
 
+    async fn reorg_canonical_chain(
+        &self,
+        branch: &[(U64, H256)],
+        head: U64,
+    ) -> Result<(), BlockRepositoryError> {
+        let mut state = self.state.write().unwrap();
+        if let Some((_, hash)) = branch.iter().find(|(_, hash)| !state.blocks.contains_key(hash)) {
+            return Err(BlockRepositoryError::BlockNotFound { hash: *hash });
+        }
+
+        // 先移出被替换与高于新链头的区块，再写入新分支，
+        // 使在两条分支间移动的交易最终指向新分支
+        let stale: Vec<U64> = state
+            .canonical_hashes
+            .keys()
+            .filter(|number| **number > head)
+            .copied()
+            .chain(branch.iter().map(|(number, _)| *number))
+            .collect();
+        for number in stale {
+            state.remove_canonical(number);
+        }
+        for (number, hash) in branch {
+            state.insert_canonical(*number, *hash);
+        }
+        Ok(())
+    }
+}
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
+    use crate::domain::block_types::{BlockHeader, BlockValidationError};
+    use crate::domain::tx_types::DynamicFeeTx;
     use crate::service::blockchain_impl::BlockChainImpl;
+    use crate::service::build_block_trait::BlockChain;
+    use ethereum_types::{Address, Bloom};
 
     #[test]
     fn test_block_repository_error_display() {
@@ -202,4 +361,356 @@ mod tests {
         let _blockchain = BlockChainImpl::new(repo);
         // 基本创建测试
     }
+
+    /// 空区块测试夹具：时间戳为 1_700_000_000 + number * 12
+    pub(crate) fn make_block(number: u64, parent_hash: H256) -> Block {
+        Block {
+            header: BlockHeader {
+                parent_hash,
+                ommers_hash: BlockHeader::empty_ommers_hash(),
+                fee_recipient: Address::zero(),
+                state_root: H256::zero(),
+                transactions_root: H256::zero(),
+                receipts_root: H256::zero(),
+                logs_bloom: Bloom::zero(),
+                difficulty: U256::zero(),
+                number: U64::from(number),
+                gas_limit: U64::from(30_000_000),
+                gas_used: U64::zero(),
+                timestamp: U64::from(1_700_000_000 + number * 12),
+                extra_data: vec![],
+                mix_hash: H256::zero(),
+                nonce: 0,
+                base_fee_per_gas: Some(U256::from(1_000_000_000u64)),
+                withdrawals_root: None,
+                blob_gas_used: None,
+                excess_blob_gas: None,
+                parent_beacon_block_root: None,
+            },
+            transactions: vec![],
+            withdrawals: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_repository_hash_and_number_lookup() {
+        let genesis = make_block(0, H256::zero());
+        let repo = InMemoryBlockRepository::with_genesis(genesis.clone());
+
+        let mut parent = genesis.hash();
+        let mut hashes = vec![parent];
+        for number in 1..=2_000u64 {
+            let block = make_block(number, parent);
+            parent = block.hash();
+            repo.save_block(&block, &[], U256::zero()).await.unwrap();
+            repo.set_canonical_hash(block.number(), parent).await.unwrap();
+            hashes.push(parent);
+        }
+
+        for (number, hash) in hashes.iter().enumerate() {
+            let by_hash = repo.get_block_by_hash(hash).await.unwrap().unwrap();
+            assert_eq!(by_hash.number(), U64::from(number));
+            let by_number = repo.get_block_by_number(U64::from(number)).await.unwrap().unwrap();
+            assert_eq!(by_number.hash(), *hash);
+        }
+        assert!(repo.get_block_by_hash(&H256::repeat_byte(0xff)).await.unwrap().is_none());
+
+        // 重复写入同一区块
+        let dup = make_block(1, genesis.hash());
+        assert_eq!(
+            repo.save_block(&dup, &[], U256::zero()).await,
+            Err(BlockRepositoryError::BlockAlreadyExists { hash: dup.hash() })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_blockchain_impl_write_and_set_head() {
+        let genesis = make_block(0, H256::zero());
+        let repo = Arc::new(InMemoryBlockRepository::new());
+        let chain = BlockChainImpl::new_with_genesis(repo.clone(), genesis.clone())
+            .await
+            .unwrap();
+        assert_eq!(chain.current_block_number().await.unwrap(), U64::zero());
+
+        let mut parent = genesis.hash();
+        for number in 1..=5u64 {
+            let block = make_block(number, parent);
+            parent = block.hash();
+            chain.write_block_and_set_head(block, vec![]).await.unwrap();
+        }
+        assert_eq!(chain.current_block().await.unwrap().hash(), parent);
+        assert_eq!(chain.get_blocks_from(U64::from(2), 10).await.unwrap().len(), 4);
+
+        // 回滚链头：高于新链头的规范映射被删除，区块本身仍可按哈希查询
+        chain.set_head(U64::from(3)).await.unwrap();
+        assert_eq!(chain.current_block_number().await.unwrap(), U64::from(3));
+        assert!(repo.get_block_by_number(U64::from(5)).await.unwrap().is_none());
+        assert!(repo.get_block_by_hash(&parent).await.unwrap().is_some());
+
+        chain.reset().await.unwrap();
+        assert_eq!(chain.current_block().await.unwrap(), chain.genesis().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_new_heads_in_order() {
+        let genesis = make_block(0, H256::zero());
+        let repo = Arc::new(InMemoryBlockRepository::new());
+        let chain = BlockChainImpl::new_with_genesis(repo, genesis.clone())
+            .await
+            .unwrap();
+        let mut heads = chain.subscribe_new_heads();
+
+        let first = make_block(1, genesis.hash());
+        let second = make_block(2, first.hash());
+        chain.write_block_and_set_head(first.clone(), vec![]).await.unwrap();
+        chain.write_block_and_set_head(second.clone(), vec![]).await.unwrap();
+
+        assert_eq!(heads.recv().await.unwrap(), first);
+        assert_eq!(heads.recv().await.unwrap(), second);
+
+        // 被拒绝的写入与链头回退都不产生通知
+        assert!(chain.write_block_and_set_head(second, vec![]).await.is_err());
+        chain.set_head(U64::one()).await.unwrap();
+        assert!(matches!(
+            heads.try_recv(),
+            Err(tokio::sync::broadcast::error::TryRecvError::Empty)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_set_head_with_weight_picks_heavier_branch() {
+        let genesis = make_block(0, H256::zero());
+        let repo = Arc::new(InMemoryBlockRepository::new());
+        let chain = BlockChainImpl::new_with_genesis(repo.clone(), genesis.clone())
+            .await
+            .unwrap();
+
+        // 分支 A: genesis -> a1 -> a2；分支 B: genesis -> b1
+        let a1 = make_block(1, genesis.hash());
+        let a2 = make_block(2, a1.hash());
+        let mut b1 = make_block(1, genesis.hash());
+        b1.header.extra_data = b"b".to_vec();
+        for block in [&a1, &a2, &b1] {
+            chain.insert_block(block.clone(), vec![]).await.unwrap();
+        }
+
+        assert!(chain.set_head_with_weight(a2.hash(), U256::from(10)).await.unwrap());
+        assert_eq!(chain.current_block().await.unwrap(), a2);
+        assert_eq!(repo.get_canonical_hash(U64::one()).await.unwrap(), Some(a1.hash()));
+
+        // 权重更小：不重组
+        assert!(!chain.set_head_with_weight(b1.hash(), U256::from(5)).await.unwrap());
+        assert_eq!(chain.current_block().await.unwrap(), a2);
+
+        // 权重更大：重组到 B，A 多出的规范映射被删除
+        assert!(chain.set_head_with_weight(b1.hash(), U256::from(20)).await.unwrap());
+        assert_eq!(chain.current_block().await.unwrap(), b1);
+        assert!(repo.get_canonical_hash(U64::from(2)).await.unwrap().is_none());
+
+        // 权重相同：哈希较小者胜出，结果与调用顺序无关
+        let mut a3 = make_block(3, a2.hash());
+        let mut b2 = make_block(2, b1.hash());
+        a3.header.extra_data = b"a3".to_vec();
+        b2.header.extra_data = b"b2".to_vec();
+        chain.insert_block(a3.clone(), vec![]).await.unwrap();
+        chain.insert_block(b2.clone(), vec![]).await.unwrap();
+        chain.set_head_with_weight(a3.hash(), U256::from(30)).await.unwrap();
+        chain.set_head_with_weight(b2.hash(), U256::from(30)).await.unwrap();
+
+        let winner = if a3.hash() < b2.hash() { &a3 } else { &b2 };
+        assert_eq!(chain.current_block().await.unwrap(), *winner);
+        let canonical_1 = repo.get_canonical_hash(U64::one()).await.unwrap();
+        assert_eq!(canonical_1, Some(if winner == &a3 { a1.hash() } else { b1.hash() }));
+
+        // 未写入的区块
+        assert_eq!(
+            chain.set_head_with_weight(H256::repeat_byte(0xee), U256::MAX).await,
+            Err(BlockRepositoryError::BlockNotFound { hash: H256::repeat_byte(0xee) })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_head_with_weight_missing_ancestor_keeps_canonical_chain() {
+        let genesis = make_block(0, H256::zero());
+        let repo = Arc::new(InMemoryBlockRepository::new());
+        let chain = BlockChainImpl::new_with_genesis(repo.clone(), genesis.clone())
+            .await
+            .unwrap();
+        let a1 = make_block(1, genesis.hash());
+        let a2 = make_block(2, a1.hash());
+        for block in [&a1, &a2] {
+            chain.insert_block(block.clone(), vec![]).await.unwrap();
+        }
+        chain.set_head_with_weight(a2.hash(), U256::from(10)).await.unwrap();
+
+        // c3 的父区块 c2 从未写入：回溯中途失败
+        let missing = make_block(2, H256::repeat_byte(0x99));
+        let c3 = make_block(3, missing.hash());
+        chain.insert_block(c3.clone(), vec![]).await.unwrap();
+        assert_eq!(
+            chain.set_head_with_weight(c3.hash(), U256::from(20)).await,
+            Err(BlockRepositoryError::BlockNotFound { hash: missing.hash() })
+        );
+
+        // 规范链与链头都未被部分改写
+        assert_eq!(chain.current_block().await.unwrap(), a2);
+        assert!(repo.get_canonical_hash(U64::from(3)).await.unwrap().is_none());
+        assert_eq!(repo.get_canonical_hash(U64::from(2)).await.unwrap(), Some(a2.hash()));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_writers_never_regress_head() {
+        let genesis = make_block(0, H256::zero());
+        let repo = Arc::new(InMemoryBlockRepository::new());
+        let chain = Arc::new(
+            BlockChainImpl::new_with_genesis(repo, genesis.clone())
+                .await
+                .unwrap(),
+        );
+
+        let mut blocks = Vec::new();
+        let mut parent = genesis.hash();
+        for number in 1..=64u64 {
+            let block = make_block(number, parent);
+            parent = block.hash();
+            blocks.push(block);
+        }
+
+        // 观察者持续采样链头，确认从未回退
+        let done = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let observer = {
+            let (chain, done) = (chain.clone(), done.clone());
+            tokio::spawn(async move {
+                let mut last = U64::zero();
+                while !done.load(std::sync::atomic::Ordering::SeqCst) {
+                    let head = chain.current_block_number().await.unwrap();
+                    assert!(head >= last, "链头从 {} 回退到 {}", last, head);
+                    last = head;
+                    tokio::task::yield_now().await;
+                }
+            })
+        };
+
+        // 每个区块两个并发写入者，写入顺序不确定
+        let writers: Vec<_> = blocks
+            .iter()
+            .chain(blocks.iter())
+            .cloned()
+            .map(|block| {
+                let chain = chain.clone();
+                tokio::spawn(async move { chain.write_block_and_set_head(block, vec![]).await })
+            })
+            .collect();
+        for writer in writers {
+            match writer.await.unwrap() {
+                Ok(()) | Err(BlockValidationError::NotAboveHead { .. }) => {}
+                Err(e) => panic!("意外错误: {}", e),
+            }
+        }
+        done.store(true, std::sync::atomic::Ordering::SeqCst);
+        observer.await.unwrap();
+
+        assert_eq!(chain.current_block_number().await.unwrap(), U64::from(64));
+        assert_eq!(chain.current_block().await.unwrap().hash(), parent);
+
+        // 同高度重复写入被拒绝
+        let err = chain
+            .write_block_and_set_head(blocks[63].clone(), vec![])
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            BlockValidationError::NotAboveHead {
+                number: U64::from(64),
+                head: U64::from(64)
+            }
+        );
+    }
+
+    fn tx(nonce: u64) -> DynamicFeeTx {
+        DynamicFeeTx {
+            chain_id: U64::one(),
+            nonce: U64::from(nonce),
+            max_priority_fee_per_gas: U256::from(1_000_000_000u64),
+            max_fee_per_gas: U256::from(2_000_000_000u64),
+            gas_limit: U64::from(21_000),
+            to: Some(Address::from_low_u64_be(0x1234)),
+            value: U256::zero(),
+            data: vec![],
+            access_list: vec![],
+            v: U64::zero(),
+            r: U256::one(),
+            s: U256::one(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transaction_lookup_returns_position() {
+        let genesis = make_block(0, H256::zero());
+        let repo = InMemoryBlockRepository::with_genesis(genesis.clone());
+
+        let mut block = make_block(1, genesis.hash());
+        block.transactions = vec![tx(0), tx(1)];
+        repo.save_block(&block, &[], U256::zero()).await.unwrap();
+        repo.set_canonical_hash(block.number(), block.hash()).await.unwrap();
+
+        let found = repo
+            .get_transaction_by_hash(&tx(1).hash())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.transaction, tx(1));
+        assert_eq!(found.block_hash, block.hash());
+        assert_eq!(found.block_number, U64::one());
+        assert_eq!(found.transaction_index, U64::one());
+
+        assert!(repo.get_transaction_by_hash(&tx(2).hash()).await.unwrap().is_none());
+
+        // 区块被移出规范链后不再返回
+        repo.delete_canonical_hash(U64::one()).await.unwrap();
+        assert!(repo.get_transaction_by_hash(&tx(1).hash()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_transaction_lookup_follows_reorg() {
+        let genesis = make_block(0, H256::zero());
+        let repo = Arc::new(InMemoryBlockRepository::new());
+        let chain = BlockChainImpl::new_with_genesis(repo.clone(), genesis.clone())
+            .await
+            .unwrap();
+
+        // a1: [tx0, tx1]；b1: [tx2, tx1]，tx1 在两条分支上的位置不同
+        let mut a1 = make_block(1, genesis.hash());
+        a1.transactions = vec![tx(0), tx(1)];
+        let mut b1 = make_block(1, genesis.hash());
+        b1.transactions = vec![tx(2), tx(1)];
+        b1.header.extra_data = b"b".to_vec();
+        let position = |found: Option<IndexedTransaction>| {
+            found.map(|found| (found.block_hash, found.transaction_index))
+        };
+
+        chain.insert_block(a1.clone(), vec![]).await.unwrap();
+        chain.insert_block(b1.clone(), vec![]).await.unwrap();
+        chain.set_head_with_weight(a1.hash(), U256::from(10)).await.unwrap();
+
+        // 只有规范链区块被索引：b1 已保存但其交易不可查
+        let lookup = |hash: H256| {
+            let repo = repo.clone();
+            async move { position(repo.get_transaction_by_hash(&hash).await.unwrap()) }
+        };
+        assert_eq!(lookup(tx(0).hash()).await, Some((a1.hash(), U64::zero())));
+        assert_eq!(lookup(tx(1).hash()).await, Some((a1.hash(), U64::one())));
+        assert_eq!(lookup(tx(2).hash()).await, None);
+
+        // 重组到 b1：a1 独有的交易移出索引，共有的交易指向 b1 中的位置
+        chain.set_head_with_weight(b1.hash(), U256::from(20)).await.unwrap();
+        assert_eq!(lookup(tx(0).hash()).await, None);
+        assert_eq!(lookup(tx(1).hash()).await, Some((b1.hash(), U64::one())));
+        assert_eq!(lookup(tx(2).hash()).await, Some((b1.hash(), U64::zero())));
+
+        // 回退链头：被移出规范链的区块交易不再可查
+        chain.set_head(U64::zero()).await.unwrap();
+        assert_eq!(lookup(tx(1).hash()).await, None);
+        assert!(repo.state.read().unwrap().tx_lookup.is_empty());
+    }
 }
@@ -0,0 +1,39 @@
+//! 自定义 RPC 方法处理器接口 - 遵循Clean Architecture原则
+//!
+//! 设计原则：
+//! - 运行时注册：私有链 / L2 可挂载自己的命名空间（如 `myl2_*`），无需修改 EthCommand
+//! - 内置方法优先：只有 CommandMapper 不认识的方法才会路由到这里
+//! - 参数与结果保持原始 JSON，编解码由处理器自行负责
+
+use crate::domain::command_types::CommandError;
+use async_trait::async_trait;
+use std::future::Future;
+use std::sync::Arc;
+
+/// 自定义方法处理器
+#[async_trait]
+pub trait CommandHandlerFn: Send + Sync {
+    /// 处理一次调用，`params` 为请求中的原始 params
+    async fn call(&self, params: serde_json::Value) -> Result<serde_json::Value, CommandError>;
+}
+
+/// 异步闭包即可作为处理器
+#[async_trait]
+impl<F, Fut> CommandHandlerFn for F
+where
+    F: Fn(serde_json::Value) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<serde_json::Value, CommandError>> + Send,
+{
+    async fn call(&self, params: serde_json::Value) -> Result<serde_json::Value, CommandError> {
+        self(params).await
+    }
+}
+
+/// 自定义方法处理器仓储
+pub trait HandlerRepository: Send + Sync {
+    /// 注册方法处理器，同名方法覆盖旧处理器
+    fn register(&self, method: &str, handler: Arc<dyn CommandHandlerFn>);
+
+    /// 按方法名查找处理器
+    fn get(&self, method: &str) -> Option<Arc<dyn CommandHandlerFn>>;
+}
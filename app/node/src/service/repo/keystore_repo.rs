@@ -0,0 +1,61 @@
+//! 本地密钥库接口 - 遵循Clean Architecture原则
+//! 参考 geth accounts/keystore
+//!
+//! 设计原则：
+//! - 密钥不出库：调用方只拿到地址和签名，拿不到私钥
+//! - 线程安全：所有方法都是Send + Sync
+//! - 只签哈希：交易/消息的编码由调用方负责
+
+use async_trait::async_trait;
+use ethereum_types::{Address, H256, U256};
+
+/// 密钥库错误
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeyStoreError {
+    /// 账户不受本节点管理
+    UnknownAccount(Address),
+    /// 无效的私钥
+    InvalidKey(String),
+    /// 签名失败
+    SigningFailed(String),
+}
+
+impl std::fmt::Display for KeyStoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::UnknownAccount(address) => write!(f, "Unknown account: {:?}", address),
+            Self::InvalidKey(msg) => write!(f, "Invalid key: {}", msg),
+            Self::SigningFailed(msg) => write!(f, "Signing failed: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for KeyStoreError {}
+
+/// 可恢复的 secp256k1 签名
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecoverableSignature {
+    /// recovery id (0/1)
+    pub recovery_id: u8,
+    /// 签名 r 值
+    pub r: U256,
+    /// 签名 s 值（low-s 规范化）
+    pub s: U256,
+}
+
+/// 密钥库接口
+#[async_trait]
+pub trait KeyStore: Send + Sync {
+    /// 返回所有受管理的账户地址（按导入顺序）
+    async fn accounts(&self) -> Result<Vec<Address>, KeyStoreError>;
+
+    /// 账户是否受本节点管理
+    async fn has_account(&self, address: &Address) -> Result<bool, KeyStoreError>;
+
+    /// 使用账户私钥对 32 字节哈希签名
+    async fn sign_hash(
+        &self,
+        address: &Address,
+        hash: &H256,
+    ) -> Result<RecoverableSignature, KeyStoreError>;
+}
@@ -1,3 +1,5 @@
 pub mod transaction_repo;
 pub mod command_repo;
-pub mod block_repo;
\ No newline at end of file
+pub mod block_repo;
+pub mod keystore_repo;
+pub mod handler_repo;
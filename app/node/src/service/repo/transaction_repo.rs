@@ -1,10 +1,10 @@
-/// 交易内存池接口 - 遵循Clean Architecture原则
-/// 参考 geth txpool 和相关EIP标准
-///
-/// 设计原则：
-/// - 无状态接口：TxPool不保存状态，只定义行为
-/// - 线程安全：所有方法都是Send + Sync
-/// - 异步操作：支持高并发场景
+//! 交易内存池接口 - 遵循Clean Architecture原则
+//! 参考 geth txpool 和相关EIP标准
+//!
+//! 设计原则：
+//! - 无状态接口：TxPool不保存状态，只定义行为
+//! - 线程安全：所有方法都是Send + Sync
+//! - 异步操作：支持高并发场景
 
 use crate::domain::tx_types::DynamicFeeTx;
 use async_trait::async_trait;
@@ -56,6 +56,14 @@ pub struct TxPoolStats {
     pub capacity: usize,
 }
 
+/// 可打包交易及其入池顺序
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingTx {
+    pub tx: DynamicFeeTx,
+    /// 入池时间（相对交易池创建时刻的纳秒数，严格递增，越小越早入池）
+    pub arrival: u64,
+}
+
 /// 交易内存池接口
 ///
 /// 交易状态管理：
@@ -84,6 +92,11 @@ pub trait TxPool: Send + Sync {
     /// - base_fee: 当前区块的base fee，用于过滤
     async fn get_pending(&self, max_count: usize, base_fee: Option<u64>) -> Result<Vec<DynamicFeeTx>, TxPoolError>;
 
+    /// 获取可打包的交易及其入池序号（参数与排序同 `get_pending`）
+    ///
+    /// 出块时按排序策略重新排序，FIFO 等策略依赖入池序号
+    async fn get_pending_candidates(&self, max_count: usize, base_fee: Option<u64>) -> Result<Vec<PendingTx>, TxPoolError>;
+
     /// 移除交易（已打包或过期）
     async fn remove(&self, hash: &H256) -> Result<(), TxPoolError>;
 
@@ -0,0 +1,182 @@
+//! 按发送者限流 - 防止单一地址刷 eth_sendRawTransaction
+//!
+//! 固定窗口计数：每个发送者在一个窗口内最多提交 `max_txs` 笔交易，
+//! 窗口从该发送者本窗口的第一笔交易开始计时。白名单地址不受限制。
+//! 计数键为签名恢复出的发送者地址，不能由请求方伪造。
+
+use crate::infrastructure::clock_impl::SystemClock;
+use crate::service::clock::Clock;
+use ethereum_types::Address;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// 限流配置
+#[derive(Debug, Clone)]
+pub struct SenderRateLimitConfig {
+    /// 每个窗口内单一发送者最多提交的交易数
+    pub max_txs: u32,
+    /// 窗口长度
+    pub window: Duration,
+    /// 不受限流的地址
+    pub whitelist: HashSet<Address>,
+}
+
+impl Default for SenderRateLimitConfig {
+    fn default() -> Self {
+        Self {
+            max_txs: 16,
+            window: Duration::from_secs(12),
+            whitelist: HashSet::new(),
+        }
+    }
+}
+
+/// 超出限流
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitExceeded {
+    pub sender: Address,
+    pub max_txs: u32,
+    pub window: Duration,
+}
+
+impl fmt::Display for RateLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "发送者 {:?} 提交过于频繁: 每 {} 秒最多 {} 笔交易",
+            self.sender,
+            self.window.as_secs(),
+            self.max_txs
+        )
+    }
+}
+
+impl std::error::Error for RateLimitExceeded {}
+
+/// 单个发送者的当前窗口
+#[derive(Debug, Clone, Copy)]
+struct SenderWindow {
+    started_at: Instant,
+    count: u32,
+}
+
+/// 按发送者限流器
+///
+/// 计数状态在 `Arc<Mutex<..>>` 中共享，克隆的限流器共用同一份计数。
+#[derive(Clone)]
+pub struct SenderRateLimiter<C: Clock = SystemClock> {
+    config: SenderRateLimitConfig,
+    clock: C,
+    windows: Arc<Mutex<HashMap<Address, SenderWindow>>>,
+}
+
+impl SenderRateLimiter {
+    /// 使用系统时钟创建限流器
+    pub fn new(config: SenderRateLimitConfig) -> Self {
+        Self::with_clock(config, SystemClock)
+    }
+}
+
+impl<C: Clock> SenderRateLimiter<C> {
+    /// 使用指定时钟创建限流器
+    pub fn with_clock(config: SenderRateLimitConfig, clock: C) -> Self {
+        Self {
+            config,
+            clock,
+            windows: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// 登记一笔来自 `sender` 的提交；超出限额时返回错误且不计数
+    pub fn check(&self, sender: Address) -> Result<(), RateLimitExceeded> {
+        if self.config.whitelist.contains(&sender) {
+            return Ok(());
+        }
+
+        let now = self.clock.now();
+        let window = self.config.window;
+        let mut windows = self.windows.lock().unwrap();
+
+        // 顺带清理已过期的窗口，避免大量一次性地址占用内存
+        if !windows.contains_key(&sender) {
+            windows.retain(|_, w| now.duration_since(w.started_at) < window);
+        }
+
+        let entry = windows.entry(sender).or_insert(SenderWindow {
+            started_at: now,
+            count: 0,
+        });
+        if now.duration_since(entry.started_at) >= window {
+            *entry = SenderWindow {
+                started_at: now,
+                count: 0,
+            };
+        }
+
+        if entry.count >= self.config.max_txs {
+            return Err(RateLimitExceeded {
+                sender,
+                max_txs: self.config.max_txs,
+                window,
+            });
+        }
+        entry.count += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::clock_impl::MockClock;
+
+    fn limiter(max_txs: u32, whitelist: &[Address]) -> (SenderRateLimiter<MockClock>, MockClock) {
+        let clock = MockClock::default();
+        let config = SenderRateLimitConfig {
+            max_txs,
+            window: Duration::from_secs(10),
+            whitelist: whitelist.iter().copied().collect(),
+        };
+        (SenderRateLimiter::with_clock(config, clock.clone()), clock)
+    }
+
+    #[test]
+    fn test_limit_is_per_sender() {
+        let (limiter, _) = limiter(2, &[]);
+        let spammer = Address::from_low_u64_be(1);
+        let other = Address::from_low_u64_be(2);
+
+        assert!(limiter.check(spammer).is_ok());
+        assert!(limiter.check(spammer).is_ok());
+        let err = limiter.check(spammer).unwrap_err();
+        assert_eq!(err.sender, spammer);
+        assert_eq!(err.max_txs, 2);
+
+        assert!(limiter.check(other).is_ok());
+        assert!(limiter.check(other).is_ok());
+    }
+
+    #[test]
+    fn test_window_resets() {
+        let (limiter, clock) = limiter(1, &[]);
+        let sender = Address::from_low_u64_be(1);
+
+        assert!(limiter.check(sender).is_ok());
+        clock.advance(Duration::from_secs(9));
+        assert!(limiter.check(sender).is_err());
+        clock.advance(Duration::from_secs(1));
+        assert!(limiter.check(sender).is_ok());
+    }
+
+    #[test]
+    fn test_whitelist_bypasses_limit() {
+        let trusted = Address::from_low_u64_be(7);
+        let (limiter, _) = limiter(1, &[trusted]);
+
+        for _ in 0..10 {
+            assert!(limiter.check(trusted).is_ok());
+        }
+    }
+}
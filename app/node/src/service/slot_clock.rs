@@ -0,0 +1,149 @@
+//! Slot 时钟 - 基于创世时间和链规格计算当前 slot/epoch
+//!
+//! slot 起始时间 = genesis_time + slot * SECONDS_PER_SLOT，
+//! 创世之前没有 slot，相关方法返回 None。
+
+use crate::domain::slot_types::{ChainSpec, Epoch, GenesisInfo, Slot};
+use crate::service::clock::Clock;
+
+/// Slot 时钟
+#[derive(Debug, Clone)]
+pub struct SlotClock<C: Clock> {
+    genesis_time: u64,
+    spec: ChainSpec,
+    clock: C,
+}
+
+impl<C: Clock> SlotClock<C> {
+    /// 创建 slot 时钟
+    ///
+    /// # Panics
+    /// `seconds_per_slot` 或 `slots_per_epoch` 为 0 时 panic（规格非法）
+    pub fn new(genesis: GenesisInfo, spec: ChainSpec, clock: C) -> Self {
+        assert!(
+            spec.seconds_per_slot > 0 && spec.slots_per_epoch > 0,
+            "SECONDS_PER_SLOT 和 SLOTS_PER_EPOCH 必须大于 0"
+        );
+        Self {
+            genesis_time: genesis.genesis_time,
+            spec,
+            clock,
+        }
+    }
+
+    /// 链规格
+    pub fn spec(&self) -> &ChainSpec {
+        &self.spec
+    }
+
+    /// 当前 slot（创世前为 None）
+    pub fn current_slot(&self) -> Option<Slot> {
+        self.slot_at(self.clock.unix_time())
+    }
+
+    /// 当前 epoch（创世前为 None）
+    pub fn current_epoch(&self) -> Option<Epoch> {
+        self.epoch_at(self.clock.unix_time())
+    }
+
+    /// 指定时间戳所在的 slot
+    pub fn slot_at(&self, timestamp: u64) -> Option<Slot> {
+        let elapsed = timestamp.checked_sub(self.genesis_time)?;
+        Some(Slot(elapsed / self.spec.seconds_per_slot))
+    }
+
+    /// 指定时间戳所在的 epoch
+    pub fn epoch_at(&self, timestamp: u64) -> Option<Epoch> {
+        self.slot_at(timestamp)
+            .map(|slot| slot.epoch(self.spec.slots_per_epoch))
+    }
+
+    /// slot 起始时间（Unix 秒，溢出时饱和）
+    pub fn slot_start_time(&self, slot: Slot) -> u64 {
+        slot.0
+            .saturating_mul(self.spec.seconds_per_slot)
+            .saturating_add(self.genesis_time)
+    }
+
+    /// epoch 起始时间（Unix 秒，溢出时饱和）
+    pub fn epoch_start_time(&self, epoch: Epoch) -> u64 {
+        self.slot_start_time(epoch.start_slot(self.spec.slots_per_epoch))
+    }
+
+    /// 距下一个 slot 开始的秒数（创世前为距创世的秒数）
+    pub fn seconds_to_next_slot(&self) -> u64 {
+        let now = self.clock.unix_time();
+        match self.slot_at(now) {
+            Some(slot) => self.slot_start_time(Slot(slot.0 + 1)) - now,
+            None => self.genesis_time - now,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::clock_impl::MockClock;
+    use std::time::Duration;
+
+    const GENESIS: u64 = 1_606_824_023;
+
+    fn mainnet_clock(now: u64) -> (SlotClock<MockClock>, MockClock) {
+        let clock = MockClock::new(now);
+        (
+            SlotClock::new(GenesisInfo::mainnet(), ChainSpec::mainnet(), clock.clone()),
+            clock,
+        )
+    }
+
+    #[test]
+    fn test_before_genesis() {
+        let (slot_clock, _) = mainnet_clock(GENESIS - 1);
+        assert_eq!(slot_clock.current_slot(), None);
+        assert_eq!(slot_clock.current_epoch(), None);
+        assert_eq!(slot_clock.seconds_to_next_slot(), 1);
+    }
+
+    #[test]
+    fn test_genesis_is_slot_zero() {
+        let (slot_clock, clock) = mainnet_clock(GENESIS);
+        assert_eq!(slot_clock.current_slot(), Some(Slot(0)));
+        assert_eq!(slot_clock.current_epoch(), Some(Epoch(0)));
+
+        clock.advance(Duration::from_secs(11));
+        assert_eq!(slot_clock.current_slot(), Some(Slot(0)));
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(slot_clock.current_slot(), Some(Slot(1)));
+        assert_eq!(slot_clock.seconds_to_next_slot(), 12);
+    }
+
+    #[test]
+    fn test_mainnet_epoch_boundaries() {
+        let (slot_clock, _) = mainnet_clock(GENESIS);
+        assert_eq!(slot_clock.epoch_start_time(Epoch(1)), GENESIS + 384);
+        assert_eq!(slot_clock.epoch_at(GENESIS + 383), Some(Epoch(0)));
+        assert_eq!(slot_clock.epoch_at(GENESIS + 384), Some(Epoch(1)));
+    }
+
+    #[test]
+    fn test_mainnet_known_fork_timestamps() {
+        let (slot_clock, _) = mainnet_clock(GENESIS);
+
+        // Bellatrix：epoch 144896，2022-09-06 11:34:47 UTC
+        assert_eq!(slot_clock.epoch_start_time(Epoch(144_896)), 1_662_464_087);
+        // Capella（Shapella）：slot 6209536，2023-04-12 22:27:35 UTC
+        assert_eq!(slot_clock.slot_start_time(Slot(6_209_536)), 1_681_338_455);
+        assert_eq!(slot_clock.slot_at(1_681_338_455), Some(Slot(6_209_536)));
+        assert_eq!(slot_clock.epoch_at(1_681_338_455), Some(Epoch(194_048)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_seconds_per_slot_rejected() {
+        let spec = ChainSpec {
+            seconds_per_slot: 0,
+            slots_per_epoch: 32,
+        };
+        SlotClock::new(GenesisInfo::mainnet(), spec, MockClock::new(GENESIS));
+    }
+}
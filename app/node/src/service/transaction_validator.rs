@@ -1,12 +1,13 @@
-/// 交易验证器实现 - 包含状态相关的验证逻辑
-/// 遵循Clean Architecture原则，验证器依赖抽象接口而非具体实现
-///
-/// # 架构分层
-/// - Trait定义: domain::transaction_validator_trait (领域层抽象)
-/// - 具体实现: service::transaction_validator (服务层实现)
-/// - 状态查询: AccountStateProvider trait (基础设施层接口)
-
-use crate::domain::tx_types::{DynamicFeeTx, TransactionValidationError};
+//! 交易验证器实现 - 包含状态相关的验证逻辑
+//! 遵循Clean Architecture原则，验证器依赖抽象接口而非具体实现
+//!
+//! # 架构分层
+//! - Trait定义: domain::transaction_validator_trait (领域层抽象)
+//! - 具体实现: service::transaction_validator (服务层实现)
+//! - 状态查询: AccountStateProvider trait (基础设施层接口)
+
+use crate::domain::intrinsic_gas::IntrinsicGasSchedule;
+use crate::domain::tx_types::{legacy_chain_id, DynamicFeeTx, TransactionValidationError};
 use crate::service::transaction_validator_trait::TransactionValidator as TransactionValidatorTrait;
 use async_trait::async_trait;
 use ethereum_types::{Address, U256, U64};
@@ -57,6 +58,10 @@ pub struct ValidatorConfig {
     pub min_gas_price: U256,
     /// 当前区块的base fee (EIP-1559)
     pub base_fee_per_gas: U256,
+    /// 是否接受未受重放保护的 legacy 交易（EIP-155 之前的签名）
+    pub allow_unprotected_txs: bool,
+    /// 固有 gas 费率表（随硬分叉变化）
+    pub gas_schedule: IntrinsicGasSchedule,
 }
 
 impl Default for ValidatorConfig {
@@ -65,6 +70,8 @@ impl Default for ValidatorConfig {
             chain_id: U64::from(1), // 主网
             min_gas_price: U256::from(1_000_000_000u64), // 1 Gwei
             base_fee_per_gas: U256::from(20_000_000_000u64), // 20 Gwei
+            allow_unprotected_txs: false,
+            gas_schedule: IntrinsicGasSchedule::default(),
         }
     }
 }
@@ -92,7 +99,7 @@ impl<S: AccountStateProvider> TransactionValidator<S> {
         sender: Address,
     ) -> Result<(), TransactionValidationError> {
         // 1. 基本验证（无状态）
-        tx.validate_basic()?;
+        tx.validate_basic_with_schedule(&self.config.gas_schedule)?;
 
         // 2. Chain ID验证
         self.validate_chain_id(tx)?;
@@ -106,10 +113,10 @@ impl<S: AccountStateProvider> TransactionValidator<S> {
         Ok(())
     }
 
-    /// 验证Chain ID
-    fn validate_chain_id(&self, tx: &DynamicFeeTx) -> Result<(), TransactionValidationError> {
+    /// 验证Chain ID（EIP-155 重放保护）
+    pub fn validate_chain_id(&self, tx: &DynamicFeeTx) -> Result<(), TransactionValidationError> {
         if tx.chain_id != self.config.chain_id {
-            return Err(TransactionValidationError::InvalidChainId {
+            return Err(TransactionValidationError::WrongChainId {
                 expected: self.config.chain_id,
                 actual: tx.chain_id,
             });
@@ -117,6 +124,23 @@ impl<S: AccountStateProvider> TransactionValidator<S> {
         Ok(())
     }
 
+    /// 验证 legacy 交易签名中的 Chain ID（EIP-155）
+    ///
+    /// 未受保护的签名只有在 `allow_unprotected_txs` 打开时才接受
+    pub fn validate_legacy_chain_id(&self, v: u64) -> Result<(), TransactionValidationError> {
+        match legacy_chain_id(v)? {
+            Some(chain_id) if chain_id != self.config.chain_id => {
+                Err(TransactionValidationError::WrongChainId {
+                    expected: self.config.chain_id,
+                    actual: chain_id,
+                })
+            }
+            Some(_) => Ok(()),
+            None if self.config.allow_unprotected_txs => Ok(()),
+            None => Err(TransactionValidationError::UnprotectedTransaction),
+        }
+    }
+
     /// 验证Gas价格
     fn validate_gas_price(&self, tx: &DynamicFeeTx) -> Result<(), TransactionValidationError> {
         // EIP-1559: max_fee_per_gas 必须 >= base_fee
@@ -144,7 +168,16 @@ impl<S: AccountStateProvider> TransactionValidator<S> {
         tx: &DynamicFeeTx,
         sender: Address,
     ) -> Result<(), TransactionValidationError> {
-        // 1. 验证nonce
+        self.validate_nonce(tx, sender).await?;
+        self.validate_balance(tx, sender).await
+    }
+
+    /// 验证 nonce 未被使用（不低于账户已上链 nonce）
+    pub async fn validate_nonce(
+        &self,
+        tx: &DynamicFeeTx,
+        sender: Address,
+    ) -> Result<(), TransactionValidationError> {
         let current_nonce = self
             .state_provider
             .get_nonce(sender)
@@ -159,8 +192,15 @@ impl<S: AccountStateProvider> TransactionValidator<S> {
                 actual: tx.nonce,
             });
         }
+        Ok(())
+    }
 
-        // 2. 验证余额
+    /// 验证余额足以支付最大成本
+    async fn validate_balance(
+        &self,
+        tx: &DynamicFeeTx,
+        sender: Address,
+    ) -> Result<(), TransactionValidationError> {
         let balance = self
             .state_provider
             .get_balance(sender)
@@ -185,7 +225,7 @@ impl<S: AccountStateProvider> TransactionValidator<S> {
 
     /// 快速验证（仅基本验证，用于快速拒绝明显无效的交易）
     pub fn validate_basic_internal(&self, tx: &DynamicFeeTx) -> Result<(), TransactionValidationError> {
-        tx.validate_basic()?;
+        tx.validate_basic_with_schedule(&self.config.gas_schedule)?;
         self.validate_chain_id(tx)?;
         self.validate_gas_price(tx)?;
         Ok(())
@@ -354,6 +394,8 @@ mod tests {
             chain_id: U64::from(1),
             min_gas_price: U256::from(1_000_000_000u64),
             base_fee_per_gas: U256::from(100_000_000_000u64), // 100 Gwei
+            allow_unprotected_txs: false,
+            gas_schedule: IntrinsicGasSchedule::default(),
         };
 
         let validator = TransactionValidator::new(config, mock_state);
@@ -392,4 +434,81 @@ mod tests {
             TransactionValidationError::PriorityFeeExceedsMaxFee
         ));
     }
+
+    #[test]
+    fn test_matching_chain_id_accepted() {
+        let validator = TransactionValidator::new(ValidatorConfig::default(), MockStateProvider::new());
+        assert!(validator.validate_basic_internal(&create_valid_tx()).is_ok());
+    }
+
+    #[test]
+    fn test_mismatched_chain_id_rejected() {
+        let validator = TransactionValidator::new(ValidatorConfig::default(), MockStateProvider::new());
+        let mut tx = create_valid_tx();
+        tx.chain_id = U64::from(5);
+
+        assert_eq!(
+            validator.validate_basic_internal(&tx),
+            Err(TransactionValidationError::WrongChainId {
+                expected: U64::from(1),
+                actual: U64::from(5),
+            })
+        );
+    }
+
+    #[test]
+    fn test_legacy_eip155_chain_id() {
+        let validator = TransactionValidator::new(ValidatorConfig::default(), MockStateProvider::new());
+
+        // chain_id = 1: v ∈ {37, 38}
+        assert!(validator.validate_legacy_chain_id(37).is_ok());
+        assert!(validator.validate_legacy_chain_id(38).is_ok());
+        // chain_id = 5: v = 45
+        assert_eq!(
+            validator.validate_legacy_chain_id(45),
+            Err(TransactionValidationError::WrongChainId {
+                expected: U64::from(1),
+                actual: U64::from(5),
+            })
+        );
+        assert_eq!(
+            validator.validate_legacy_chain_id(30),
+            Err(TransactionValidationError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_legacy_pre_eip155_requires_flag() {
+        let strict = TransactionValidator::new(ValidatorConfig::default(), MockStateProvider::new());
+        assert_eq!(
+            strict.validate_legacy_chain_id(27),
+            Err(TransactionValidationError::UnprotectedTransaction)
+        );
+
+        let config = ValidatorConfig {
+            allow_unprotected_txs: true,
+            ..ValidatorConfig::default()
+        };
+        let permissive = TransactionValidator::new(config, MockStateProvider::new());
+        assert!(permissive.validate_legacy_chain_id(27).is_ok());
+        assert!(permissive.validate_legacy_chain_id(28).is_ok());
+    }
+
+    #[test]
+    fn test_gas_limit_below_intrinsic_rejected() {
+        let validator = TransactionValidator::new(ValidatorConfig::default(), MockStateProvider::new());
+        let mut tx = create_valid_tx();
+        tx.data = vec![0xff; 10]; // 固有 gas = 21000 + 10 * 16
+
+        assert_eq!(
+            validator.validate_basic_internal(&tx),
+            Err(TransactionValidationError::InsufficientGas {
+                min: 21_160,
+                actual: 21_000,
+            })
+        );
+
+        tx.gas_limit = U64::from(21_160);
+        assert!(validator.validate_basic_internal(&tx).is_ok());
+    }
 }
@@ -1,8 +1,8 @@
-/// 交易验证器 Trait - 定义交易验证的核心接口
-/// 遵循Clean Architecture原则：
-/// - 位于domain层，定义业务逻辑契约
-/// - 不依赖外部实现细节
-/// - 支持静态分发（通过泛型实现）
+//! 交易验证器 Trait - 定义交易验证的核心接口
+//! 遵循Clean Architecture原则：
+//! - 位于domain层，定义业务逻辑契约
+//! - 不依赖外部实现细节
+//! - 支持静态分发（通过泛型实现）
 
 use crate::domain::tx_types::{DynamicFeeTx, TransactionValidationError};
 use async_trait::async_trait;
@@ -0,0 +1,130 @@
+//! 集成测试公共设施
+//!
+//! `TestNode` 按 main.rs 的依赖链（MockEthereumRepository → EthereumServiceImpl →
+//! CommandDispatcher → EthJsonRpcHandler → HTTP 服务器）启动一个完整节点，
+//! 监听 127.0.0.1 的随机端口，供黑盒 HTTP 测试使用。
+
+#![allow(dead_code)]
+
+use ethereum_types::U256;
+use node::domain::rpc_config::ServerConfig;
+use node::domain::tx_types::DynamicFeeTx;
+use node::inbound::json_rpc::EthJsonRpcHandler;
+use node::inbound::server::create_server_with_config;
+use node::infrastructure::mock_repository::MockEthereumRepository;
+use node::service::command_dispatcher::CommandDispatcher;
+use node::service::ethereum_service_impl::EthereumServiceImpl;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+
+/// 用私钥 `key` 为交易签名（覆盖原有的 v / r / s）
+pub fn sign_tx(mut tx: DynamicFeeTx, key: &[u8; 32]) -> DynamicFeeTx {
+    let signing_key = k256::ecdsa::SigningKey::from_slice(key).unwrap();
+    let (signature, recovery_id) = signing_key
+        .sign_prehash_recoverable(tx.signing_hash().as_bytes())
+        .unwrap();
+    let bytes = signature.to_bytes();
+    tx.v = recovery_id.to_byte().into();
+    tx.r = U256::from_big_endian(&bytes[..32]);
+    tx.s = U256::from_big_endian(&bytes[32..]);
+    tx
+}
+
+/// 运行在随机端口上的测试节点，drop 时停止服务器
+pub struct TestNode {
+    /// 服务器实际绑定的地址
+    pub addr: SocketAddr,
+    /// 节点使用的仓储（可在测试中预置区块、代码等数据）
+    pub repository: MockEthereumRepository,
+    client: reqwest::Client,
+    server: JoinHandle<()>,
+}
+
+impl TestNode {
+    /// 使用默认服务器配置启动
+    pub async fn start() -> Self {
+        Self::start_with_config(ServerConfig::default()).await
+    }
+
+    /// 使用指定服务器配置启动
+    pub async fn start_with_config(config: ServerConfig) -> Self {
+        let repository = MockEthereumRepository::new();
+        let service = Arc::new(EthereumServiceImpl::new(repository.clone()));
+        let rpc_handler = EthJsonRpcHandler::new(CommandDispatcher::new(service));
+        let app = create_server_with_config(rpc_handler, config);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("绑定测试端口失败");
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        Self {
+            addr,
+            repository,
+            client: reqwest::Client::new(),
+            server,
+        }
+    }
+
+    /// JSON-RPC 端点 URL
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// HTTP 客户端（用于需要自定义请求头的测试）
+    pub fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    /// 发送 JSON-RPC 请求，返回完整响应体
+    pub async fn rpc(&self, method: &str, params: serde_json::Value) -> serde_json::Value {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+            "id": 1
+        });
+        self.client
+            .post(self.url())
+            .json(&request)
+            .send()
+            .await
+            .expect("请求发送失败")
+            .json()
+            .await
+            .expect("响应不是合法 JSON")
+    }
+
+    /// 发送 JSON-RPC 请求，成功返回 result，失败返回 error 对象
+    pub async fn call(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, serde_json::Value> {
+        let mut response = self.rpc(method, params).await;
+        match response.get_mut("error") {
+            Some(error) => Err(error.take()),
+            None => Ok(response["result"].take()),
+        }
+    }
+
+    /// 请求健康检查端点
+    pub async fn health(&self) -> reqwest::StatusCode {
+        self.client
+            .get(format!("{}/health", self.url()))
+            .send()
+            .await
+            .expect("请求发送失败")
+            .status()
+    }
+}
+
+impl Drop for TestNode {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
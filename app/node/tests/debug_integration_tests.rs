@@ -0,0 +1,172 @@
+//! debug_getRawTransaction / debug_getRawReceipts 集成测试
+
+mod common;
+
+use common::{sign_tx, TestNode};
+use ethereum_types::{Address, Bloom, H256, U256, U64};
+use node::domain::command_types::{Log, Transaction, TransactionReceipt};
+use node::domain::tx_types::DynamicFeeTx;
+use node::inbound::transaction_decoder::decode_raw_transaction;
+
+fn signed_tx(nonce: u64) -> DynamicFeeTx {
+    let tx = DynamicFeeTx {
+        chain_id: U64::from(1),
+        nonce: U64::from(nonce),
+        max_priority_fee_per_gas: U256::from(1_000_000_000u64),
+        max_fee_per_gas: U256::from(2_000_000_000u64),
+        gas_limit: U64::from(30000),
+        to: Some(Address::from_low_u64_be(0x1234)),
+        value: U256::from(1_000u64),
+        data: vec![0xab, 0xcd],
+        access_list: vec![],
+        v: U64::zero(),
+        r: U256::zero(),
+        s: U256::zero(),
+    };
+    sign_tx(tx, &[0x11; 32])
+}
+
+/// 已上链交易的 RPC 表示
+fn mined_transaction(tx: &DynamicFeeTx, block_number: u64, index: u64) -> Transaction {
+    Transaction {
+        hash: tx.hash(),
+        nonce: U256::from(tx.nonce.as_u64()),
+        block_hash: Some(H256::from_low_u64_be(block_number)),
+        block_number: Some(U64::from(block_number)),
+        transaction_index: Some(U64::from(index)),
+        from: Address::from_low_u64_be(0x5678),
+        to: tx.to,
+        value: tx.value,
+        gas_price: None,
+        gas: U256::from(tx.gas_limit.as_u64()),
+        input: tx.data.clone(),
+        v: tx.v,
+        r: tx.r,
+        s: tx.s,
+        max_fee_per_gas: Some(tx.max_fee_per_gas),
+        max_priority_fee_per_gas: Some(tx.max_priority_fee_per_gas),
+        transaction_type: Some(U64::from(2)),
+    }
+}
+
+fn receipt(tx: &Transaction, cumulative_gas_used: u64, logs: Vec<Log>) -> TransactionReceipt {
+    let mut receipt = TransactionReceipt {
+        transaction_hash: tx.hash,
+        transaction_index: tx.transaction_index.unwrap(),
+        block_hash: tx.block_hash.unwrap(),
+        block_number: tx.block_number.unwrap(),
+        from: tx.from,
+        to: tx.to,
+        cumulative_gas_used: U256::from(cumulative_gas_used),
+        gas_used: U256::from(21000),
+        effective_gas_price: U256::zero(),
+        contract_address: None,
+        logs,
+        logs_bloom: Bloom::zero(),
+        status: U64::one(),
+        transaction_type: U64::zero(),
+    };
+    receipt.set_fee_fields(tx, Some(U256::from(1_000_000_000u64)));
+    receipt
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+#[tokio::test]
+async fn test_raw_transaction_from_pool_round_trips() {
+    let node = TestNode::start().await;
+    let tx = signed_tx(0);
+    let raw = to_hex(&tx.encode_2718());
+
+    let hash = node
+        .call("eth_sendRawTransaction", serde_json::json!([raw]))
+        .await
+        .unwrap();
+    let result = node
+        .call("debug_getRawTransaction", serde_json::json!([hash]))
+        .await
+        .unwrap();
+    assert_eq!(result, serde_json::json!(raw));
+
+    let bytes = hex::decode(result.as_str().unwrap().trim_start_matches("0x")).unwrap();
+    assert_eq!(decode_raw_transaction(&bytes).unwrap(), tx);
+}
+
+#[tokio::test]
+async fn test_raw_transaction_of_mined_transaction() {
+    let node = TestNode::start().await;
+    let tx = signed_tx(3);
+    node.repository.add_transaction(mined_transaction(&tx, 1, 0));
+
+    let result = node
+        .call("debug_getRawTransaction", serde_json::json!([tx.hash()]))
+        .await
+        .unwrap();
+    assert_eq!(result, serde_json::json!(to_hex(&tx.encode_2718())));
+}
+
+#[tokio::test]
+async fn test_raw_transaction_unknown_hash_is_null() {
+    let node = TestNode::start().await;
+
+    let result = node
+        .call("debug_getRawTransaction", serde_json::json!([H256::repeat_byte(0xee)]))
+        .await
+        .unwrap();
+    assert_eq!(result, serde_json::Value::Null);
+}
+
+#[tokio::test]
+async fn test_raw_receipts_of_block() {
+    let node = TestNode::start().await;
+    let first = mined_transaction(&signed_tx(0), 1, 0);
+    let second = mined_transaction(&signed_tx(1), 1, 1);
+    let log = Log {
+        removed: false,
+        log_index: U256::zero(),
+        transaction_index: U256::one(),
+        transaction_hash: second.hash,
+        block_hash: H256::from_low_u64_be(1),
+        block_number: U64::one(),
+        address: Address::from_low_u64_be(0xc0de),
+        data: vec![0x01],
+        topics: vec![H256::repeat_byte(0x11)],
+    };
+
+    let mut block = node.repository.get_block_by_hash(&H256::zero()).unwrap();
+    block.number = U64::one();
+    block.hash = H256::from_low_u64_be(1);
+    block.transactions = vec![first.clone(), second.clone()].into();
+    node.repository.add_block(block);
+    node.repository.add_receipt(receipt(&first, 21000, vec![]));
+    node.repository.add_receipt(receipt(&second, 42000, vec![log]));
+
+    let result = node
+        .call("debug_getRawReceipts", serde_json::json!(["0x1"]))
+        .await
+        .unwrap();
+    let receipts = result.as_array().unwrap();
+    assert_eq!(receipts.len(), 2);
+
+    // 类型化收据：0x02 || rlp([status, cumulativeGasUsed, logsBloom, logs])
+    let second_raw = hex::decode(receipts[1].as_str().unwrap().trim_start_matches("0x")).unwrap();
+    assert_eq!(second_raw[0], 0x02);
+    let rlp = rlp::Rlp::new(&second_raw[1..]);
+    assert_eq!(rlp.item_count().unwrap(), 4);
+    assert_eq!(rlp.val_at::<u64>(0).unwrap(), 1);
+    assert_eq!(rlp.val_at::<u64>(1).unwrap(), 42000);
+    assert_eq!(rlp.at(3).unwrap().item_count().unwrap(), 1);
+}
+
+#[tokio::test]
+async fn test_raw_receipts_unknown_block_is_null() {
+    let node = TestNode::start().await;
+
+    let result = node
+        .call("debug_getRawReceipts", serde_json::json!(["0x64"]))
+        .await
+        .unwrap();
+    assert_eq!(result, serde_json::Value::Null);
+}
@@ -6,23 +6,38 @@
 //! - 合约调用测试
 //! - EIP-1559 费用历史测试
 
+use node::infrastructure::keystore_repo_impl::InMemoryKeyStore;
 use node::infrastructure::mock_repository::MockEthereumRepository;
 use node::service::ethereum_service_impl::EthereumServiceImpl;
-use ethereum_types::{Address, U256, U64};
+use ethereum_types::{Address, H256, U256, U64};
 use node::inbound::json_rpc::EthJsonRpcHandler;
 use node::inbound::json_types::{JsonRpcRequest, JsonRpcResponse, RequestId};
 use node::domain::command_types::{BlockId, BlockTag, CallRequest, SendTransactionRequest};
 use node::service::command_dispatcher::CommandDispatcher;
 use std::sync::Arc;
 use rlp::RlpStream;
+use node::domain::tx_types::DynamicFeeTx;
+use common::sign_tx;
+
+mod common;
 
 #[cfg(test)]
 mod eth_api_client_test;
 
-/// 创建测试用的 Handler
+/// 测试账户私钥（仅用于测试）
+const TEST_ACCOUNT_KEY: [u8; 32] = [0x11; 32];
+
+/// 测试账户地址（由 TEST_ACCOUNT_KEY 推导）
+fn test_account() -> Address {
+    InMemoryKeyStore::new().import_key(&TEST_ACCOUNT_KEY).unwrap()
+}
+
+/// 创建测试用的 Handler（密钥库中预置测试账户）
 fn create_test_handler() -> EthJsonRpcHandler<EthereumServiceImpl> {
     let repository = MockEthereumRepository::new();
-    let service = EthereumServiceImpl::new(repository);
+    let keystore = InMemoryKeyStore::new();
+    keystore.import_key(&TEST_ACCOUNT_KEY).unwrap();
+    let service = EthereumServiceImpl::with_keystore(repository, keystore);
     let dispatcher = CommandDispatcher::new(Arc::new(service));
     EthJsonRpcHandler::new(dispatcher)
 }
@@ -57,7 +72,7 @@ async fn test_eip1559_send_transaction_basic() {
     let handler = create_test_handler();
 
     // 创建 EIP-1559 转账请求
-    let from_addr = Address::from_low_u64_be(1);
+    let from_addr = test_account();
     let to_addr = Address::from_low_u64_be(2);
 
     let tx_request = SendTransactionRequest {
@@ -87,7 +102,7 @@ async fn test_eip1559_send_transaction_basic() {
 async fn test_eip1559_send_transaction_with_data() {
     let handler = create_test_handler();
 
-    let from_addr = Address::from_low_u64_be(100);
+    let from_addr = test_account();
     let to_addr = Address::from_low_u64_be(200);
 
     // 包含数据的 EIP-1559 交易
@@ -113,7 +128,7 @@ async fn test_eip1559_send_transaction_with_data() {
 async fn test_legacy_send_transaction() {
     let handler = create_test_handler();
 
-    let from_addr = Address::from_low_u64_be(10);
+    let from_addr = test_account();
     let to_addr = Address::from_low_u64_be(20);
 
     // Legacy 交易（使用 gasPrice）
@@ -135,6 +150,38 @@ async fn test_legacy_send_transaction() {
     assert!(result.is_ok(), "Legacy 交易应该成功");
 }
 
+#[tokio::test]
+async fn test_accounts_lists_managed_keys() {
+    let handler = create_test_handler();
+
+    let result = call_rpc(&handler, "eth_accounts", serde_json::json!([])).await;
+
+    let accounts: Vec<Address> = serde_json::from_value(result.unwrap()).unwrap();
+    assert_eq!(accounts, vec![test_account()]);
+}
+
+#[tokio::test]
+async fn test_send_transaction_from_unmanaged_account() {
+    let handler = create_test_handler();
+
+    let tx_request = SendTransactionRequest {
+        from: Address::from_low_u64_be(1),
+        to: Some(Address::from_low_u64_be(2)),
+        gas: Some(U256::from(21000)),
+        gas_price: None,
+        value: Some(U256::from(1)),
+        data: None,
+        nonce: Some(U256::zero()),
+        max_fee_per_gas: Some(U256::from(30_000_000_000u64)),
+        max_priority_fee_per_gas: Some(U256::from(2_000_000_000u64)),
+    };
+
+    let result = call_rpc(&handler, "eth_sendTransaction", serde_json::json!([tx_request])).await;
+
+    let err = result.expect_err("未受管账户发送交易应该失败");
+    assert!(err.starts_with("-32000"), "应返回 -32000 错误: {}", err);
+}
+
 #[tokio::test]
 async fn test_send_raw_transaction() {
     let handler = create_test_handler();
@@ -174,6 +221,72 @@ async fn test_send_raw_transaction() {
     assert!(tx_hash.is_string(), "交易哈希应该是字符串");
 }
 
+/// 测试账户签名的转账交易（0x 前缀的 EIP-2718 编码）
+fn raw_transfer(nonce: u64) -> String {
+    let tx = DynamicFeeTx {
+        chain_id: U64::from(1),
+        nonce: U64::from(nonce),
+        max_priority_fee_per_gas: U256::from(1_000_000_000u64),
+        max_fee_per_gas: U256::from(2_000_000_000u64),
+        gas_limit: U64::from(21000),
+        to: Some(Address::from_low_u64_be(0x1234)),
+        value: U256::from(1_000u64),
+        data: vec![],
+        access_list: vec![],
+        v: U64::zero(),
+        r: U256::zero(),
+        s: U256::zero(),
+    };
+    format!("0x{}", hex::encode(sign_tx(tx, &TEST_ACCOUNT_KEY).encode_2718()))
+}
+
+#[tokio::test]
+async fn test_send_raw_transaction_uses_recovered_sender() {
+    let handler = create_test_handler();
+    let pending = |address: Address| {
+        let handler = &handler;
+        async move {
+            call_rpc(handler, "eth_getTransactionCount", serde_json::json!([address, "pending"]))
+                .await
+                .unwrap()
+        }
+    };
+
+    call_rpc(&handler, "eth_sendRawTransaction", serde_json::json!([raw_transfer(0)]))
+        .await
+        .unwrap();
+
+    // 交易记在签名者名下，而不是某个占位地址
+    assert_eq!(pending(test_account()).await, serde_json::json!("0x1"));
+    assert_eq!(pending(Address::from_low_u64_be(0x9999)).await, serde_json::json!("0x0"));
+}
+
+#[tokio::test]
+async fn test_pending_nonce_counts_raw_transactions() {
+    let repository = MockEthereumRepository::new();
+    repository.set_nonce(test_account(), 3);
+    let service = EthereumServiceImpl::new(repository);
+    let handler = EthJsonRpcHandler::new(CommandDispatcher::new(Arc::new(service)));
+
+    for nonce in [3, 4] {
+        call_rpc(&handler, "eth_sendRawTransaction", serde_json::json!([raw_transfer(nonce)]))
+            .await
+            .unwrap();
+    }
+
+    // 已上链 nonce 3，池中连续的 3、4 两笔：pending 返回 5，latest 不受影响
+    let count = |tag: &'static str| {
+        let handler = &handler;
+        async move {
+            call_rpc(handler, "eth_getTransactionCount", serde_json::json!([test_account(), tag]))
+                .await
+                .unwrap()
+        }
+    };
+    assert_eq!(count("pending").await, serde_json::json!("0x5"));
+    assert_eq!(count("latest").await, serde_json::json!("0x3"));
+}
+
 // ============================================================================
 // 合约部署测试
 // ============================================================================
@@ -182,7 +295,7 @@ async fn test_send_raw_transaction() {
 async fn test_contract_deployment_eip1559() {
     let handler = create_test_handler();
 
-    let from_addr = Address::from_low_u64_be(1000);
+    let from_addr = test_account();
 
     // 合约字节码（示例：简单的存储合约）
     let contract_bytecode = hex::decode(
@@ -212,7 +325,7 @@ async fn test_contract_deployment_eip1559() {
 async fn test_contract_deployment_with_constructor_args() {
     let handler = create_test_handler();
 
-    let from_addr = Address::from_low_u64_be(1001);
+    let from_addr = test_account();
 
     // 合约字节码 + 构造函数参数编码
     let contract_bytecode_with_args = hex::decode(
@@ -241,7 +354,7 @@ async fn test_contract_deployment_with_constructor_args() {
 async fn test_contract_deployment_with_value() {
     let handler = create_test_handler();
 
-    let from_addr = Address::from_low_u64_be(1002);
+    let from_addr = test_account();
 
     // Payable 构造函数的合约部署
     let contract_bytecode = hex::decode("608060405234801561001057600080fd5b50").unwrap();
@@ -293,6 +406,62 @@ async fn test_estimate_gas_for_contract_deployment() {
     }
 }
 
+#[tokio::test]
+async fn test_estimate_gas_simple_transfer() {
+    let handler = create_test_handler();
+
+    let call_request = CallRequest {
+        from: Some(test_account()),
+        to: Some(Address::from_low_u64_be(4001)),
+        gas: None,
+        gas_price: None,
+        value: Some(U256::from(1_000u64)),
+        data: None,
+        max_fee_per_gas: None,
+        max_priority_fee_per_gas: None,
+    };
+
+    let result = call_rpc(&handler, "eth_estimateGas", serde_json::json!([call_request])).await;
+    let gas: U256 = serde_json::from_value(result.unwrap()).unwrap();
+    assert_eq!(gas, U256::from(21000));
+}
+
+#[tokio::test]
+async fn test_estimate_gas_reverting_call() {
+    let repository = MockEthereumRepository::new();
+    let contract_addr = Address::from_low_u64_be(5006);
+    // PUSH1 0 PUSH1 0 REVERT
+    repository.set_code(contract_addr, U64::zero(), vec![0x60, 0x00, 0x60, 0x00, 0xfd]);
+    let service = EthereumServiceImpl::new(repository);
+    let handler = EthJsonRpcHandler::new(CommandDispatcher::new(Arc::new(service)));
+
+    let call_request = CallRequest {
+        from: Some(test_account()),
+        to: Some(contract_addr),
+        gas: None,
+        gas_price: None,
+        value: None,
+        data: None,
+        max_fee_per_gas: None,
+        max_priority_fee_per_gas: None,
+    };
+    let request = JsonRpcRequest {
+        jsonrpc: "2.0".to_string(),
+        method: "eth_estimateGas".to_string(),
+        params: serde_json::json!([call_request]),
+        id: RequestId::Number(1),
+    };
+
+    match handler.handle(request).await {
+        JsonRpcResponse::Error { error, .. } => {
+            assert_eq!(error.code, 3);
+            assert_eq!(error.message, "execution reverted");
+            assert_eq!(error.data, Some(serde_json::json!("0x")));
+        }
+        JsonRpcResponse::Success { result, .. } => panic!("回滚调用不应估算成功: {}", result),
+    }
+}
+
 // ============================================================================
 // 合约调用测试
 // ============================================================================
@@ -353,11 +522,50 @@ async fn test_contract_call_with_value() {
     assert!(result.is_ok(), "带 value 的合约调用应该成功");
 }
 
+#[tokio::test]
+async fn test_contract_call_with_state_override() {
+    let handler = create_test_handler();
+    let contract_addr = Address::from_low_u64_be(5007);
+
+    let call_request = CallRequest {
+        from: Some(test_account()),
+        to: Some(contract_addr),
+        gas: None,
+        gas_price: None,
+        value: None,
+        data: None,
+        max_fee_per_gas: None,
+        max_priority_fee_per_gas: None,
+    };
+    // 覆盖代码：PUSH1 0 SLOAD PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN（返回槽位 0）
+    let state_override = serde_json::json!({
+        format!("{:?}", contract_addr): {
+            "code": "0x60005460005260206000f3",
+            "stateDiff": {
+                "0x0000000000000000000000000000000000000000000000000000000000000000":
+                    "0x000000000000000000000000000000000000000000000000000000000000002a"
+            }
+        }
+    });
+
+    let params = serde_json::json!([call_request, "latest", state_override]);
+    let result = call_rpc(&handler, "eth_call", params).await.unwrap();
+    assert_eq!(
+        result,
+        serde_json::json!("0x000000000000000000000000000000000000000000000000000000000000002a")
+    );
+
+    // 不带覆盖时地址上没有代码，返回空
+    let params = serde_json::json!([call_request, "latest"]);
+    let result = call_rpc(&handler, "eth_call", params).await.unwrap();
+    assert_eq!(result, serde_json::json!("0x"));
+}
+
 #[tokio::test]
 async fn test_contract_transaction_eip1559() {
     let handler = create_test_handler();
 
-    let from_addr = Address::from_low_u64_be(2001);
+    let from_addr = test_account();
     let contract_addr = Address::from_low_u64_be(5002);
 
     // ERC20 transfer(address, uint256)
@@ -430,6 +638,46 @@ async fn test_get_contract_code() {
     assert!(result.is_ok(), "获取合约代码应该成功");
 }
 
+#[tokio::test]
+async fn test_get_code_at_historical_block() {
+    let repository = MockEthereumRepository::new();
+    let service = EthereumServiceImpl::new(repository.clone());
+    let handler = EthJsonRpcHandler::new(CommandDispatcher::new(Arc::new(service)));
+
+    // 合约在区块 3 部署，链头推进到区块 5
+    let contract_addr = Address::from_low_u64_be(5005);
+    let code = vec![0x60, 0x80, 0x60, 0x40, 0x52];
+    repository.set_code(contract_addr, U64::from(3), code.clone());
+    let genesis = repository.get_block_by_hash(&Default::default()).unwrap();
+    for number in 1..=5u64 {
+        let mut block = genesis.clone();
+        block.number = U64::from(number);
+        block.hash = ethereum_types::H256::from_low_u64_be(number);
+        repository.add_block(block);
+    }
+
+    let code_at = |block: serde_json::Value| {
+        let handler = &handler;
+        async move {
+            let result = call_rpc(handler, "eth_getCode", serde_json::json!([contract_addr, block]))
+                .await
+                .unwrap();
+            result.as_str().unwrap().to_string()
+        }
+    };
+
+    let expected = format!("0x{}", hex::encode(&code));
+    assert_eq!(code_at(serde_json::json!("0x2")).await, "0x", "部署前代码应为空");
+    assert_eq!(code_at(serde_json::json!("0x3")).await, expected);
+    assert_eq!(code_at(serde_json::json!("0x5")).await, expected);
+    assert_eq!(code_at(serde_json::json!("latest")).await, expected);
+    assert_eq!(code_at(serde_json::json!("earliest")).await, "0x");
+
+    // 超过链头的区块
+    let result = call_rpc(&handler, "eth_getCode", serde_json::json!([contract_addr, "0x6"])).await;
+    assert!(result.unwrap_err().starts_with("-32000"));
+}
+
 // ============================================================================
 // EIP-1559 费用相关测试
 // ============================================================================
@@ -543,6 +791,26 @@ async fn test_gas_price_legacy() {
     }
 }
 
+#[tokio::test]
+async fn test_blob_base_fee() {
+    let repository = MockEthereumRepository::new();
+    let mut head = repository.get_block_by_hash(&H256::zero()).unwrap();
+    head.number = U64::one();
+    head.hash = H256::from_low_u64_be(1);
+    head.excess_blob_gas = Some(U64::from(10 * 1024 * 1024));
+    repository.add_block(head);
+    let service = EthereumServiceImpl::new(repository);
+    let handler = EthJsonRpcHandler::new(CommandDispatcher::new(Arc::new(service)));
+
+    // EIP-4844 参考值：excess_blob_gas = 10 MiB 时 blob base fee 为 23 wei
+    let result = call_rpc(&handler, "eth_blobBaseFee", serde_json::json!([])).await;
+    assert_eq!(result, Ok(serde_json::json!("0x17")));
+
+    // Cancun 之前的链头（无 excess_blob_gas）取最低费用 1 wei
+    let result = call_rpc(&create_test_handler(), "eth_blobBaseFee", serde_json::json!([])).await;
+    assert_eq!(result, Ok(serde_json::json!("0x1")));
+}
+
 // ============================================================================
 // 综合集成测试
 // ============================================================================
@@ -551,7 +819,7 @@ async fn test_gas_price_legacy() {
 async fn test_complete_eip1559_transaction_lifecycle() {
     let handler = create_test_handler();
 
-    let from_addr = Address::from_low_u64_be(3000);
+    let from_addr = test_account();
     let to_addr = Address::from_low_u64_be(4000);
 
     // 1. 获取账户 nonce
@@ -605,7 +873,7 @@ async fn test_complete_eip1559_transaction_lifecycle() {
 async fn test_complete_contract_deployment_lifecycle() {
     let handler = create_test_handler();
 
-    let from_addr = Address::from_low_u64_be(3001);
+    let from_addr = test_account();
     let contract_bytecode = hex::decode("608060405234801561001057600080fd5b50").unwrap();
 
     // 1. 估算部署 gas
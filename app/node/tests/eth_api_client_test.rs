@@ -5,8 +5,62 @@
 
 #[cfg(test)]
 mod tests {
-    use node::infrastructure::eth_api_client::EthApiClient;
+    use axum::{http::HeaderMap, routing::post, Json, Router};
+    use node::infrastructure::eth_api_client::{EthApiClient, EthApiClientConfig};
     use node::infrastructure::json_rpc_trait::EthJsonRpc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    /// 启动本地 mock RPC 服务，记录收到的 User-Agent
+    async fn spawn_mock_server(seen_user_agents: Arc<Mutex<Vec<String>>>) -> String {
+        let app = Router::new().route(
+            "/",
+            post(move |headers: HeaderMap| {
+                let seen = seen_user_agents.clone();
+                async move {
+                    if let Some(ua) = headers.get("user-agent").and_then(|v| v.to_str().ok()) {
+                        seen.lock().unwrap().push(ua.to_string());
+                    }
+                    Json(serde_json::json!({"jsonrpc": "2.0", "result": "0x10", "id": 1}))
+                }
+            }),
+        );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        format!("http://{}", addr)
+    }
+
+    /// 启动记录最大并发数的 mock RPC 服务（每个请求处理 50ms）
+    async fn spawn_slow_server(in_flight: Arc<AtomicUsize>, max_seen: Arc<AtomicUsize>) -> String {
+        let app = Router::new().route(
+            "/",
+            post(move || {
+                let in_flight = in_flight.clone();
+                let max_seen = max_seen.clone();
+                async move {
+                    let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_seen.fetch_max(current, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                    Json(serde_json::json!({"jsonrpc": "2.0", "result": "0x10", "id": 1}))
+                }
+            }),
+        );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        format!("http://{}", addr)
+    }
 
     /// 测试客户端创建
     #[tokio::test]
@@ -18,6 +72,65 @@ mod tests {
         assert!(client.is_ok(), "客户端创建应该成功");
     }
 
+    /// 测试自定义配置生效（User-Agent 出现在请求头中）
+    #[tokio::test]
+    async fn test_with_config_applies_user_agent() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let url = spawn_mock_server(seen.clone()).await;
+
+        let config = EthApiClientConfig {
+            pool_max_idle_per_host: 2,
+            user_agent: "rusteth-test/1.0".to_string(),
+            ..EthApiClientConfig::default()
+        };
+        let client = EthApiClient::with_config(url, config).expect("客户端创建失败");
+
+        let result = client.eth_block_number().await;
+        assert_eq!(result.unwrap(), serde_json::json!("0x10"));
+        assert_eq!(seen.lock().unwrap().as_slice(), ["rusteth-test/1.0"]);
+    }
+
+    /// 测试默认配置使用 rusteth User-Agent
+    #[tokio::test]
+    async fn test_default_config_user_agent() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let url = spawn_mock_server(seen.clone()).await;
+
+        let client = EthApiClient::new(url).expect("客户端创建失败");
+        client.eth_block_number().await.unwrap();
+
+        let seen = seen.lock().unwrap();
+        assert!(seen[0].starts_with("rusteth/"));
+    }
+
+    /// 测试并发上限：超出的请求排队，对端看到的在途请求数不超过配置值
+    #[tokio::test]
+    async fn test_max_concurrent_requests_enforced() {
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_seen = Arc::new(AtomicUsize::new(0));
+        let url = spawn_slow_server(in_flight, max_seen.clone()).await;
+
+        let config = EthApiClientConfig {
+            max_concurrent_requests: Some(3),
+            ..EthApiClientConfig::default()
+        };
+        let client = Arc::new(EthApiClient::with_config(url, config).expect("客户端创建失败"));
+
+        let handles: Vec<_> = (0..12)
+            .map(|_| {
+                let client = client.clone();
+                tokio::spawn(async move { client.eth_block_number().await })
+            })
+            .collect();
+        for handle in handles {
+            assert!(handle.await.unwrap().is_ok());
+        }
+
+        let max = max_seen.load(Ordering::SeqCst);
+        assert!(max <= 3, "在途请求数 {} 超过上限 3", max);
+        assert!(max > 1, "请求应并发执行");
+    }
+
     /// 测试 eth_blockNumber
     ///
     /// 注意: 此测试需要网络访问,默认禁用
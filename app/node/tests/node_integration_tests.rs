@@ -0,0 +1,284 @@
+//! 节点冒烟测试：通过 HTTP 访问完整依赖链
+
+mod common;
+
+use common::TestNode;
+use ethereum_types::{Address, H256, U256, U64};
+use node::domain::rpc_config::ServerConfig;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn test_block_number_over_http() {
+    let node = TestNode::start().await;
+
+    let response = node.rpc("eth_blockNumber", serde_json::json!([])).await;
+    assert_eq!(
+        response,
+        serde_json::json!({"jsonrpc": "2.0", "result": "0x0", "id": 1})
+    );
+}
+
+#[tokio::test]
+async fn test_health_over_http() {
+    let node = TestNode::start().await;
+    assert_eq!(node.health().await, reqwest::StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_unknown_method_over_http() {
+    let node = TestNode::start().await;
+
+    let error = node.call("eth_unknownMethod", serde_json::json!([])).await.unwrap_err();
+    assert_eq!(error["code"], -32601);
+}
+
+#[tokio::test]
+async fn test_malformed_body_is_parse_error_over_http() {
+    let node = TestNode::start().await;
+
+    let response = node
+        .client()
+        .post(node.url())
+        .header("Content-Type", "application/json")
+        .body(r#"{"jsonrpc":"2.0","id":"abc","#)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["error"]["code"], -32700);
+    assert!(body["id"].is_null());
+}
+
+/// 以指定 Content-Type（None 表示不携带）发送 eth_blockNumber
+async fn post_block_number(node: &TestNode, content_type: Option<&str>) -> reqwest::Response {
+    let mut request = node
+        .client()
+        .post(node.url())
+        .body(r#"{"jsonrpc":"2.0","method":"eth_blockNumber","params":[],"id":1}"#);
+    if let Some(content_type) = content_type {
+        request = request.header("Content-Type", content_type);
+    }
+    request.send().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_content_type_enforced_over_http() {
+    let node = TestNode::start().await;
+
+    let response = post_block_number(&node, Some("application/json")).await;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+    for content_type in [Some("text/plain"), None] {
+        let response = post_block_number(&node, content_type).await;
+        assert_eq!(response.status(), reqwest::StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["error"]["code"], -32600);
+    }
+}
+
+#[tokio::test]
+async fn test_missing_content_type_accepted_when_lenient() {
+    let node = TestNode::start_with_config(ServerConfig {
+        lenient_content_type: true,
+        ..ServerConfig::default()
+    })
+    .await;
+
+    let response = post_block_number(&node, None).await;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body.get("result").is_some());
+
+    let response = post_block_number(&node, Some("text/plain")).await;
+    assert_eq!(response.status(), reqwest::StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
+
+#[tokio::test]
+async fn test_body_size_limit_over_http() {
+    let node = TestNode::start_with_config(ServerConfig {
+        max_body_size: 1024,
+        ..ServerConfig::default()
+    })
+    .await;
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0", "method": "eth_blockNumber", "params": [], "id": 1
+    });
+    let post = |batch: Vec<serde_json::Value>| {
+        node.client()
+            .post(node.url())
+            .header("Content-Type", "application/json")
+            .body(serde_json::to_vec(&batch).unwrap())
+            .send()
+    };
+
+    let response = post(vec![request.clone(); 2]).await.unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body.as_array().unwrap().len(), 2);
+
+    // 条数未超过批量上限，但整体请求体超过字节上限
+    let response = post(vec![request; 50]).await.unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn test_get_balances_over_http() {
+    let node = TestNode::start().await;
+    let seeded = [
+        (Address::from_low_u64_be(0xa1), U256::from(5u64)),
+        (Address::from_low_u64_be(0xb2), U256::from(10u64).pow(U256::from(20))),
+        (Address::from_low_u64_be(0xc3), U256::zero()),
+    ];
+    for (address, balance) in seeded {
+        node.repository.set_balance(address, balance);
+    }
+
+    let addresses: Vec<Address> = seeded.iter().map(|(address, _)| *address).collect();
+    let result = node
+        .call("eth_getBalances", serde_json::json!([addresses, "latest"]))
+        .await
+        .unwrap();
+
+    let balances: HashMap<Address, U256> = serde_json::from_value(result).unwrap();
+    assert_eq!(balances, HashMap::from(seeded));
+}
+
+#[tokio::test]
+async fn test_oversized_quantity_is_invalid_params() {
+    let node = TestNode::start().await;
+
+    // 区块号超出 U64
+    let error = node
+        .call("eth_getBlockByNumber", serde_json::json!(["0x10000000000000000", false]))
+        .await
+        .unwrap_err();
+    assert_eq!(error["code"], -32602);
+
+    // value 超出 U256
+    let too_large = format!("0x1{}", "0".repeat(64));
+    let call = serde_json::json!({
+        "to": "0x0000000000000000000000000000000000001234",
+        "value": too_large,
+    });
+    let error = node
+        .call("eth_call", serde_json::json!([call, "latest"]))
+        .await
+        .unwrap_err();
+    assert_eq!(error["code"], -32602);
+}
+
+/// 返回单字节 `value` 的合约：MSTORE8 后 RETURN 1 字节
+fn return_byte_code(value: u8) -> Vec<u8> {
+    vec![0x60, value, 0x60, 0x00, 0x53, 0x60, 0x01, 0x60, 0x00, 0xf3]
+}
+
+/// 在链头之上追加区块 `number`，哈希为 `hash`
+fn add_block(node: &TestNode, number: u64, hash: H256) {
+    let mut block = node.repository.get_block_by_hash(&H256::zero()).unwrap();
+    block.number = U64::from(number);
+    block.hash = hash;
+    node.repository.add_block(block);
+}
+
+#[tokio::test]
+async fn test_call_pinned_to_block_hash() {
+    let node = TestNode::start().await;
+    let contract = Address::from_low_u64_be(0xc0de);
+    node.repository.set_code(contract, U64::one(), return_byte_code(0x01));
+    node.repository.set_code(contract, U64::from(2), return_byte_code(0x02));
+    add_block(&node, 1, H256::repeat_byte(0x01));
+    add_block(&node, 2, H256::repeat_byte(0x02));
+
+    let call = serde_json::json!({ "to": contract });
+    let at_hash = serde_json::json!({ "blockHash": H256::repeat_byte(0x01), "requireCanonical": true });
+    let result = node.call("eth_call", serde_json::json!([call, at_hash])).await.unwrap();
+    assert_eq!(result, "0x01");
+
+    let result = node.call("eth_call", serde_json::json!([call, "latest"])).await.unwrap();
+    assert_eq!(result, "0x02");
+}
+
+#[tokio::test]
+async fn test_call_on_non_canonical_hash() {
+    let node = TestNode::start().await;
+    let contract = Address::from_low_u64_be(0xc0de);
+    node.repository.set_code(contract, U64::one(), return_byte_code(0x01));
+
+    // 区块 1 被同高度的另一区块替换（重组）
+    let orphaned = H256::repeat_byte(0xaa);
+    add_block(&node, 1, orphaned);
+    add_block(&node, 1, H256::repeat_byte(0xbb));
+
+    let call = serde_json::json!({ "to": contract });
+    let error = node
+        .call(
+            "eth_call",
+            serde_json::json!([call, { "blockHash": orphaned, "requireCanonical": true }]),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error["code"], -32000);
+    assert!(error["message"].as_str().unwrap().contains("不在主链上"));
+
+    // 未要求主链时按该区块高度执行
+    let result = node
+        .call("eth_call", serde_json::json!([call, { "blockHash": orphaned }]))
+        .await
+        .unwrap();
+    assert_eq!(result, "0x01");
+
+    // 未知哈希
+    let error = node
+        .call("eth_call", serde_json::json!([call, { "blockHash": H256::repeat_byte(0xcc) }]))
+        .await
+        .unwrap_err();
+    assert_eq!(error["code"], -32000);
+}
+
+#[tokio::test]
+async fn test_node_handle_shutdown_stops_server() {
+    use node::inbound::json_rpc::EthJsonRpcHandler;
+    use node::domain::rpc_config::ServerConfig;
+    use node::inbound::server::serve_until;
+    use node::infrastructure::mock_repository::MockEthereumRepository;
+    use node::infrastructure::node_handle::NodeHandle;
+    use node::service::command_dispatcher::CommandDispatcher;
+    use node::service::ethereum_service_impl::EthereumServiceImpl;
+    use std::sync::Arc;
+
+    let service = Arc::new(EthereumServiceImpl::new(MockEthereumRepository::new()));
+    let rpc_handler = EthJsonRpcHandler::new(CommandDispatcher::new(service));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let grace = Duration::from_secs(2);
+    let mut node = NodeHandle::new(grace);
+    node.spawn("json-rpc", |signal| async move {
+        serve_until(listener, rpc_handler, ServerConfig::default(), signal.recv())
+            .await
+            .unwrap();
+    });
+    node.spawn("ticker", |signal| async move {
+        let mut interval = tokio::time::interval(Duration::from_millis(10));
+        tokio::select! {
+            _ = signal.recv() => {}
+            _ = async { loop { interval.tick().await; } } => {}
+        }
+    });
+
+    let health = reqwest::get(format!("http://{}/health", addr)).await.unwrap();
+    assert_eq!(health.status(), reqwest::StatusCode::OK);
+
+    let started = Instant::now();
+    let report = node.shutdown().await;
+    assert!(started.elapsed() < grace);
+    assert_eq!(report.stopped, vec!["json-rpc", "ticker"]);
+    assert!(report.aborted.is_empty());
+
+    // 服务器已停止监听
+    assert!(tokio::net::TcpStream::connect(addr).await.is_err());
+}
@@ -0,0 +1,196 @@
+//! HTTP 服务器集成测试
+//!
+//! 启动本地 JSON-RPC 服务器，验证响应压缩协商与按连接的并发限制
+
+#[cfg(test)]
+mod tests {
+    use ethereum_types::{Address, U64};
+    use flate2::read::GzDecoder;
+    use node::inbound::json_rpc::EthJsonRpcHandler;
+    use futures::channel::mpsc;
+    use node::domain::rpc_config::{ConnectionOverflow, ServerConfig};
+    use node::inbound::server::create_server_with_config;
+    use node::infrastructure::mock_repository::MockEthereumRepository;
+    use node::service::command_dispatcher::CommandDispatcher;
+    use node::service::ethereum_service_impl::EthereumServiceImpl;
+    use std::io::Read;
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    const CONTRACT: u64 = 0xc0de;
+
+    /// 启动服务器，预置一个 16KB 合约代码用于产生大响应
+    async fn spawn_server(config: ServerConfig) -> String {
+        let repository = MockEthereumRepository::new();
+        repository.set_code(Address::from_low_u64_be(CONTRACT), U64::zero(), vec![0x5b; 16 * 1024]);
+        let service = EthereumServiceImpl::new(repository);
+        let handler = EthJsonRpcHandler::new(CommandDispatcher::new(Arc::new(service)));
+        let app = create_server_with_config(handler, config);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+                .await
+                .unwrap();
+        });
+
+        format!("http://{}", addr)
+    }
+
+    async fn post_gzip(url: &str, body: serde_json::Value) -> reqwest::Response {
+        reqwest::Client::new()
+            .post(url)
+            .header("Accept-Encoding", "gzip")
+            .json(&body)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    fn get_code_request() -> serde_json::Value {
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "eth_getCode",
+            "params": [Address::from_low_u64_be(CONTRACT), "latest"],
+            "id": 1
+        })
+    }
+
+    #[tokio::test]
+    async fn test_large_response_is_gzip_compressed() {
+        let url = spawn_server(ServerConfig::default()).await;
+
+        let response = post_gzip(&url, get_code_request()).await;
+        assert_eq!(response.headers()["content-encoding"], "gzip");
+
+        let compressed = response.bytes().await.unwrap();
+        let mut body = String::new();
+        GzDecoder::new(&compressed[..]).read_to_string(&mut body).unwrap();
+        assert!(compressed.len() < body.len());
+
+        let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+        let code = json["result"].as_str().unwrap();
+        assert_eq!(code.len(), 2 + 2 * 16 * 1024);
+    }
+
+    #[tokio::test]
+    async fn test_small_response_is_not_compressed() {
+        let url = spawn_server(ServerConfig::default()).await;
+
+        let request = serde_json::json!({
+            "jsonrpc": "2.0", "method": "eth_blockNumber", "params": [], "id": 1
+        });
+        let response = post_gzip(&url, request).await;
+        assert!(response.headers().get("content-encoding").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_compression_can_be_disabled() {
+        let config = ServerConfig {
+            compression: false,
+            ..ServerConfig::default()
+        };
+        let url = spawn_server(config).await;
+
+        let response = post_gzip(&url, get_code_request()).await;
+        assert!(response.headers().get("content-encoding").is_none());
+        let json: serde_json::Value = response.json().await.unwrap();
+        assert!(json["result"].as_str().unwrap().starts_with("0x5b5b"));
+    }
+
+    fn block_number_request() -> serde_json::Value {
+        serde_json::json!({
+            "jsonrpc": "2.0", "method": "eth_blockNumber", "params": [], "id": 1
+        })
+    }
+
+    /// 每个客户端使用独立的 HTTP/2 连接，请求在该连接上并发复用
+    fn http2_client() -> reqwest::Client {
+        reqwest::Client::builder()
+            .http2_prior_knowledge()
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_per_connection_limit_rejects_excess_requests() {
+        let config = ServerConfig {
+            max_requests_per_connection: Some(2),
+            connection_overflow: ConnectionOverflow::Reject,
+            ..ServerConfig::default()
+        };
+        let url = spawn_server(config).await;
+        let slow_client = http2_client();
+
+        // 两个请求体迟迟不发完的请求占满连接 A 的额度
+        let mut stalled = Vec::new();
+        for _ in 0..2 {
+            let (body_tx, body_rx) = mpsc::unbounded::<Result<Vec<u8>, std::io::Error>>();
+            body_tx.unbounded_send(Ok(b"{\"jsonrpc\":\"2.0\",".to_vec())).unwrap();
+            let request = slow_client
+                .post(&url)
+                .header("Content-Type", "application/json")
+                .body(reqwest::Body::wrap_stream(body_rx))
+                .send();
+            stalled.push((body_tx, tokio::spawn(request)));
+        }
+
+        // 等待占位请求到达服务器：之后连接 A 上的请求返回 429
+        let mut rejected = false;
+        for _ in 0..200 {
+            let response = slow_client
+                .post(&url)
+                .json(&block_number_request())
+                .send()
+                .await
+                .unwrap();
+            if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                let json: serde_json::Value = response.json().await.unwrap();
+                assert_eq!(json["error"]["code"], -32005);
+                rejected = true;
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(rejected, "超出连接额度的请求应返回 429");
+
+        // 连接 A 上并发的超额请求全部被拒绝
+        let excess = futures::future::join_all(
+            (0..5).map(|_| slow_client.post(&url).json(&block_number_request()).send()),
+        )
+        .await;
+        for response in excess {
+            assert_eq!(response.unwrap().status(), reqwest::StatusCode::TOO_MANY_REQUESTS);
+        }
+
+        // 另一个连接不受影响
+        let response = http2_client()
+            .post(&url)
+            .json(&block_number_request())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        let json: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(json["result"], "0x0");
+
+        // 占位请求发完后正常响应，连接 A 恢复额度
+        for (body_tx, request) in stalled {
+            body_tx
+                .unbounded_send(Ok(b"\"method\":\"eth_blockNumber\",\"params\":[],\"id\":1}".to_vec()))
+                .unwrap();
+            drop(body_tx);
+            let response = request.await.unwrap().unwrap();
+            assert_eq!(response.status(), reqwest::StatusCode::OK);
+        }
+        let response = slow_client
+            .post(&url)
+            .json(&block_number_request())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+    }
+}